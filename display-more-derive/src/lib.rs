@@ -0,0 +1,455 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Derive macros for [display-more](https://docs.rs/display-more).
+//!
+//! Use them through the `derive` feature of `display-more` rather than depending on this crate
+//! directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse_macro_input;
+use syn::Attribute;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Fields;
+use syn::LitInt;
+use syn::LitStr;
+use syn::Member;
+use syn::Path;
+use syn::Type;
+
+/// Options specified by `#[display(...)]` on a field.
+#[derive(Default)]
+struct FieldAttrs {
+    skip: bool,
+    redact: bool,
+    with: Option<Path>,
+    limit: Option<usize>,
+}
+
+impl FieldAttrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut res = FieldAttrs::default();
+
+        for attr in attrs.iter().filter(|a| a.path().is_ident("display")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    res.skip = true;
+                } else if meta.path.is_ident("redact") {
+                    res.redact = true;
+                } else if meta.path.is_ident("with") {
+                    let s: LitStr = meta.value()?.parse()?;
+                    res.with = Some(s.parse()?);
+                } else if meta.path.is_ident("limit") {
+                    let n: LitInt = meta.value()?.parse()?;
+                    res.limit = Some(n.base10_parse()?);
+                } else {
+                    return Err(meta.error("unknown display attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(res)
+    }
+}
+
+/// Options specified by `#[display(...)]` on an enum or a variant.
+#[derive(Default)]
+struct VariantAttrs {
+    name_only: bool,
+}
+
+impl VariantAttrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut res = VariantAttrs::default();
+
+        for attr in attrs.iter().filter(|a| a.path().is_ident("display")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name_only") {
+                    res.name_only = true;
+                } else {
+                    return Err(meta.error("unknown display attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(res)
+    }
+}
+
+/// A field to display: its name, a place expression to access it, its type and options.
+struct DisplayField<'a> {
+    ident: Option<&'a syn::Ident>,
+    place: TokenStream2,
+    ty: &'a Type,
+    attrs: FieldAttrs,
+}
+
+/// Derive `Display` rendering `TypeName{field1=.., field2=..}`.
+///
+/// Fields are displayed with the wrappers of `display-more` according to their types:
+/// - `Option<T>` with `DisplayOptionExt`,
+/// - `Result<T, E>` with `DisplayResultExt`,
+/// - `Vec<T>`, `[T; N]` and `[T]` with `DisplaySliceExt`,
+/// - `BTreeSet<T>` with `DisplayBTreeSetExt`,
+/// - `SystemTime` with `DisplayUnixTimeStampExt`,
+/// - any other type with its own `Display` implementation.
+///
+/// Tuple structs are rendered as `TypeName(.., ..)` and unit structs as `TypeName`.
+///
+/// Enums are rendered as the variant name followed by its fields in the same way, such as
+/// `Leader{term=3}`, `Follower(1)` or `Learner`. With `#[display(name_only)]` on the enum or on a
+/// variant, only the variant name is rendered, which is useful for variants with large payloads.
+///
+/// Fields accept the following attributes:
+/// - `#[display(skip)]`: do not display the field.
+/// - `#[display(with = "path::to::fn")]`: display the field with a function of signature
+///   `fn(&T, &mut fmt::Formatter<'_>) -> fmt::Result`.
+/// - `#[display(redact)]`: display `<redacted>` instead of the value.
+/// - `#[display(limit = 3)]`: display at most 3 elements of a `Vec`, array, slice or `BTreeSet`.
+#[proc_macro_derive(DisplayMore, attributes(display))]
+pub fn derive_display_more(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let fields = data
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| {
+                    let member = match &field.ident {
+                        Some(ident) => Member::Named(ident.clone()),
+                        None => Member::Unnamed(i.into()),
+                    };
+                    Ok(DisplayField {
+                        ident: field.ident.as_ref(),
+                        place: quote!(self.#member),
+                        ty: &field.ty,
+                        attrs: FieldAttrs::parse(&field.attrs)?,
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            display_fields(&name.to_string(), &data.fields, &fields)?
+        }
+        Data::Enum(data) => {
+            let enum_attrs = VariantAttrs::parse(&input.attrs)?;
+
+            let mut arms = Vec::new();
+            for variant in &data.variants {
+                let variant_attrs = VariantAttrs::parse(&variant.attrs)?;
+                let v = &variant.ident;
+                let v_name = v.to_string();
+
+                if enum_attrs.name_only || variant_attrs.name_only {
+                    let pat = match &variant.fields {
+                        Fields::Named(_) => quote!(Self::#v { .. }),
+                        Fields::Unnamed(_) => quote!(Self::#v(..)),
+                        Fields::Unit => quote!(Self::#v),
+                    };
+                    arms.push(quote!(#pat => write!(__dm_f, #v_name),));
+                    continue;
+                }
+
+                // Bind fields by reference to generated names, so that a field can not shadow
+                // the formatter; skipped fields are not bound.
+                let mut fields = Vec::new();
+                let mut bindings = Vec::new();
+                for (i, field) in variant.fields.iter().enumerate() {
+                    let attrs = FieldAttrs::parse(&field.attrs)?;
+                    let binding = quote::format_ident!("__dm_field_{}", i);
+
+                    if attrs.skip {
+                        bindings.push(quote!(_));
+                    } else {
+                        bindings.push(quote!(#binding));
+                    }
+
+                    fields.push(DisplayField {
+                        ident: field.ident.as_ref(),
+                        place: quote!((*#binding)),
+                        ty: &field.ty,
+                        attrs,
+                    });
+                }
+
+                let pat = match &variant.fields {
+                    Fields::Named(_) => {
+                        let named = fields
+                            .iter()
+                            .zip(&bindings)
+                            .filter(|(x, _)| !x.attrs.skip)
+                            .map(|(x, b)| {
+                                let ident = x.ident.unwrap();
+                                quote!(#ident: #b)
+                            });
+                        quote!(Self::#v { #(#named,)* .. })
+                    }
+                    Fields::Unnamed(_) => quote!(Self::#v(#(#bindings),*)),
+                    Fields::Unit => quote!(Self::#v),
+                };
+
+                let body = display_fields(&v_name, &variant.fields, &fields)?;
+                arms.push(quote!(#pat => { #body }));
+            }
+
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "DisplayMore can not be derived for unions",
+            ));
+        }
+    };
+
+    // The formatter is named so that it can not collide with a field binding.
+    Ok(quote! {
+        impl #impl_generics ::core::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, __dm_f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #body
+            }
+        }
+    })
+}
+
+/// Build the statements that write `name` followed by the fields.
+fn display_fields(
+    name: &str,
+    fields: &Fields,
+    display_fields: &[DisplayField<'_>],
+) -> syn::Result<TokenStream2> {
+    let (open, close) = match fields {
+        Fields::Named(_) => ("{{", "}}"),
+        Fields::Unnamed(_) => ("(", ")"),
+        Fields::Unit => ("", ""),
+    };
+
+    let mut writes = Vec::new();
+
+    for field in display_fields.iter().filter(|x| !x.attrs.skip) {
+        let sep = if writes.is_empty() { "" } else { ", " };
+        let label = match field.ident {
+            Some(ident) => format!("{}{}={{}}", sep, ident),
+            None => format!("{}{{}}", sep),
+        };
+        let value = field_value(field)?;
+        writes.push(quote! {
+            write!(__dm_f, #label, #value)?;
+        });
+    }
+
+    let head = format!("{}{}", name, open);
+
+    Ok(quote! {
+        write!(__dm_f, #head)?;
+        #(#writes)*
+        write!(__dm_f, #close)
+    })
+}
+
+/// Build an expression that displays a field according to its attributes and type.
+fn field_value(field: &DisplayField<'_>) -> syn::Result<TokenStream2> {
+    let place = &field.place;
+    let attrs = &field.attrs;
+
+    if attrs.redact {
+        return Ok(quote!("<redacted>"));
+    }
+
+    if let Some(with) = &attrs.with {
+        return Ok(quote!(::display_more::display_once(|__dm_f| #with(&#place, __dm_f))));
+    }
+
+    let (kind, value) = wrap(field.ty, place.clone());
+
+    match (attrs.limit, kind) {
+        (None, _) => Ok(value),
+        (Some(n), WrapKind::Collection) => Ok(quote!(#value.at_most(Some(#n)))),
+        (Some(_), WrapKind::Other) => Err(syn::Error::new_spanned(
+            field.ty,
+            "`limit` only applies to Vec, array, slice or BTreeSet fields",
+        )),
+    }
+}
+
+/// Whether a wrapped field supports collection options such as `at_most()`.
+enum WrapKind {
+    Collection,
+    Other,
+}
+
+/// Build an expression that displays the field at `place` with a wrapper chosen by its type.
+fn wrap(ty: &Type, place: TokenStream2) -> (WrapKind, TokenStream2) {
+    // Dereference reference fields so that the trait methods receive the referent.
+    let mut ty = ty;
+    let mut place = place;
+    while let Type::Reference(r) = ty {
+        ty = &r.elem;
+        place = quote!((*#place));
+    }
+
+    let slice = quote!(::display_more::DisplaySliceExt::display(&#place[..]));
+
+    match ty {
+        Type::Array(_) | Type::Slice(_) => (WrapKind::Collection, slice),
+        Type::Path(p) => {
+            let last = p.path.segments.last().map(|s| s.ident.to_string());
+            match last.as_deref() {
+                Some("Vec") => (WrapKind::Collection, slice),
+                Some("BTreeSet") => (
+                    WrapKind::Collection,
+                    quote!(::display_more::DisplayBTreeSetExt::display(&#place)),
+                ),
+                Some("Option") => (
+                    WrapKind::Other,
+                    quote!(::display_more::DisplayOptionExt::display(&#place)),
+                ),
+                Some("Result") => (
+                    WrapKind::Other,
+                    quote!(::display_more::DisplayResultExt::display(&#place)),
+                ),
+                Some("SystemTime") => (
+                    WrapKind::Other,
+                    quote!(::display_more::DisplayUnixTimeStampExt::display_unix_timestamp(&#place)),
+                ),
+                _ => (WrapKind::Other, quote!(&#place)),
+            }
+        }
+        _ => (WrapKind::Other, quote!(&#place)),
+    }
+}
+
+/// Options specified by `#[display(...)]` on a newtype for `DisplayForward`.
+#[derive(Default)]
+struct ForwardAttrs {
+    prefix: Option<String>,
+}
+
+impl ForwardAttrs {
+    fn parse(name: &syn::Ident, attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut res = ForwardAttrs::default();
+
+        for attr in attrs.iter().filter(|a| a.path().is_ident("display")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("prefix") {
+                    if meta.input.peek(syn::Token![=]) {
+                        let s: LitStr = meta.value()?.parse()?;
+                        res.prefix = Some(s.value());
+                    } else {
+                        res.prefix = Some(name.to_string());
+                    }
+                } else {
+                    return Err(meta.error("unknown display attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(res)
+    }
+}
+
+/// Derive `Display` and `Debug` for a single-field newtype by forwarding to the field.
+///
+/// Formatting options such as width and precision are passed on to the field. With
+/// `#[display(prefix)]` the output is wrapped in the type name, such as `JobId(42)`, and with
+/// `#[display(prefix = "job")]` in the given name, such as `job(42)`.
+///
+/// Each impl is bounded on the field type implementing the same trait, so a generic newtype
+/// gets `Debug` only when its field is `Debug`.
+#[proc_macro_derive(DisplayForward, attributes(display))]
+pub fn derive_display_forward(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_forward(&input) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand_forward(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let attrs = ForwardAttrs::parse(name, &input.attrs)?;
+
+    let field = match &input.data {
+        Data::Struct(data) if data.fields.len() == 1 => data.fields.iter().next().unwrap(),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "DisplayForward can only be derived for structs with exactly one field",
+            ));
+        }
+    };
+
+    let member = match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(0.into()),
+    };
+    let ty = &field.ty;
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let predicates = where_clause.map(|w| &w.predicates);
+
+    let imp = |tr: TokenStream2| {
+        let body = match &attrs.prefix {
+            None => quote!(#tr::fmt(&self.#member, f)),
+            Some(prefix) => {
+                let open = format!("{}(", prefix);
+                quote! {
+                    f.write_str(#open)?;
+                    #tr::fmt(&self.#member, f)?;
+                    f.write_str(")")
+                }
+            }
+        };
+
+        quote! {
+            impl #impl_generics #tr for #name #ty_generics
+            where
+                #ty: #tr,
+                #predicates
+            {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    #body
+                }
+            }
+        }
+    };
+
+    let display = imp(quote!(::core::fmt::Display));
+    let debug = imp(quote!(::core::fmt::Debug));
+
+    Ok(quote! {
+        #display
+        #debug
+    })
+}
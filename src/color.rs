@@ -0,0 +1,263 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ANSI styling of the output, enabled by the `color` feature.
+//!
+//! Wrappers style parts of their output: `Err(..)` is red, `None` is dim and timestamps are
+//! cyan. Styling is off until the application enables it with [`set_color_mode`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use display_more::color::with_color_mode;
+//! use display_more::color::ColorMode;
+//! use display_more::DisplayResultExt;
+//!
+//! let r = Result::<u64, &str>::Err("boom");
+//! let s = with_color_mode(ColorMode::Always, || r.display().to_string());
+//! assert_eq!(s, "\x1b[31mErr(boom)\x1b[0m");
+//!
+//! assert_eq!(r.display().to_string(), "Err(boom)");
+//! ```
+
+use core::cell::Cell;
+use core::fmt;
+use std::io::IsTerminal;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering;
+use std::sync::OnceLock;
+
+/// When to emit ANSI escape codes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorMode {
+    /// Never style the output. This is the default.
+    #[default]
+    Never,
+    /// Style the output only if stdout is a terminal.
+    Auto,
+    /// Always style the output.
+    Always,
+}
+
+impl ColorMode {
+    fn from_u8(v: u8) -> Self {
+        match v {
+            1 => ColorMode::Auto,
+            2 => ColorMode::Always,
+            _ => ColorMode::Never,
+        }
+    }
+
+    fn as_u8(self) -> u8 {
+        match self {
+            ColorMode::Never => 0,
+            ColorMode::Auto => 1,
+            ColorMode::Always => 2,
+        }
+    }
+}
+
+static MODE: AtomicU8 = AtomicU8::new(0);
+
+thread_local! {
+    static THREAD_MODE: Cell<Option<ColorMode>> = const { Cell::new(None) };
+}
+
+/// Set the process-wide color mode.
+pub fn set_color_mode(mode: ColorMode) {
+    MODE.store(mode.as_u8(), Ordering::Relaxed);
+}
+
+/// Return the color mode in effect for the current thread.
+pub fn color_mode() -> ColorMode {
+    THREAD_MODE
+        .with(|m| m.get())
+        .unwrap_or_else(|| ColorMode::from_u8(MODE.load(Ordering::Relaxed)))
+}
+
+/// Run `f` with the color mode overridden for the current thread.
+pub fn with_color_mode<R>(mode: ColorMode, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<ColorMode>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            THREAD_MODE.with(|m| m.set(self.0));
+        }
+    }
+
+    let _restore = Restore(THREAD_MODE.with(|m| m.replace(Some(mode))));
+    f()
+}
+
+/// Whether output should be styled now.
+fn enabled() -> bool {
+    static STDOUT_IS_TERMINAL: OnceLock<bool> = OnceLock::new();
+
+    match color_mode() {
+        ColorMode::Never => false,
+        ColorMode::Auto => *STDOUT_IS_TERMINAL.get_or_init(|| std::io::stdout().is_terminal()),
+        ColorMode::Always => true,
+    }
+}
+
+/// A text style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Cyan,
+    Dim,
+    Bold,
+}
+
+impl Style {
+    fn code(&self) -> &'static str {
+        match self {
+            Style::Red => "31",
+            Style::Green => "32",
+            Style::Yellow => "33",
+            Style::Blue => "34",
+            Style::Cyan => "36",
+            Style::Dim => "2",
+            Style::Bold => "1",
+        }
+    }
+
+    /// Display `inner` in this style, if styling is enabled.
+    pub const fn paint<D: fmt::Display>(self, inner: D) -> Styled<D> {
+        Styled { style: self, inner }
+    }
+}
+
+/// Display the inner value wrapped in ANSI escape codes, if styling is enabled.
+#[must_use]
+pub struct Styled<D: fmt::Display> {
+    style: Style,
+    inner: D,
+}
+
+impl<D: fmt::Display> fmt::Display for Styled<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if enabled() {
+            write!(f, "\x1b[{}m{}\x1b[0m", self.style.code(), self.inner)
+        } else {
+            write!(f, "{}", self.inner)
+        }
+    }
+}
+
+impl<D: fmt::Display> fmt::Debug for Styled<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::DisplayOptionExt;
+    use crate::DisplayResultExt;
+    use crate::DisplayUnixTimeStampExt;
+
+    #[test]
+    fn test_styled() {
+        assert_eq!("x", Style::Red.paint("x").to_string());
+
+        with_color_mode(ColorMode::Always, || {
+            assert_eq!("\x1b[31mx\x1b[0m", Style::Red.paint("x").to_string());
+            assert_eq!("\x1b[2mx\x1b[0m", Style::Dim.paint("x").to_string());
+
+            with_color_mode(ColorMode::Never, || {
+                assert_eq!("x", Style::Red.paint("x").to_string());
+            });
+            assert_eq!(ColorMode::Always, color_mode());
+        });
+
+        assert_eq!(ColorMode::Never, color_mode());
+    }
+
+    #[test]
+    fn test_styled_wrappers() {
+        with_color_mode(ColorMode::Always, || {
+            assert_eq!("\x1b[2mNone\x1b[0m", None::<u64>.display().to_string());
+            assert_eq!("1", Some(1).display().to_string());
+
+            assert_eq!("Ok(1)", Ok::<u64, u64>(1).display().to_string());
+            assert_eq!(
+                "\x1b[31mErr(2)\x1b[0m",
+                Err::<u64, u64>(2).display().to_string()
+            );
+
+            let t = Duration::from_millis(1723102819023);
+            assert_eq!(
+                "\x1b[36m2024-08-08T07:40:19.023\x1b[0m",
+                t.display_unix_timestamp_short().to_string()
+            );
+        });
+    }
+
+    #[test]
+    fn test_machine_readable_output_is_unstyled() {
+        use crate::AsJsonExt;
+        use crate::DisplaySliceExt;
+        use crate::LogLine;
+        use crate::Unstyled;
+
+        with_color_mode(ColorMode::Always, || {
+            let none = None::<u64>;
+            assert_eq!("None", Unstyled::new(none.display()).to_string());
+
+            let line = LogLine::new().field("leader", none.display());
+            assert_eq!("leader=None", line.logfmt().to_string());
+            assert_eq!(r#"{"leader":"None"}"#, line.json().to_string());
+
+            let a = [None::<u64>; 10];
+            let opts = a.iter().map(|o| o.display()).collect::<Vec<_>>();
+            assert_eq!("[..,None]", opts.display().fit_width(12).to_string());
+            assert_eq!("[None,..,None]", opts.display().fit_width(14).to_string());
+            assert_eq!(
+                r#"["None","None"]"#,
+                opts[..2].display().as_json().to_string()
+            );
+
+            // Styling is restored after formatting unstyled.
+            assert_eq!("\x1b[2mNone\x1b[0m", none.display().to_string());
+        });
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize_is_unstyled() {
+        use crate::DisplaySliceExt;
+
+        with_color_mode(ColorMode::Always, || {
+            let t = Duration::from_secs(1);
+            assert_eq!(
+                r#""1970-01-01T00:00:01.000000Z+0000""#,
+                serde_json::to_string(&t.display_unix_timestamp()).unwrap()
+            );
+
+            let a = [None::<u64>];
+            let opts = a.iter().map(|o| o.display()).collect::<Vec<_>>();
+            assert_eq!(
+                r#"["None"]"#,
+                serde_json::to_string(&opts.display()).unwrap()
+            );
+        });
+    }
+}
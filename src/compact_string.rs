@@ -0,0 +1,68 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+use core::fmt::Write;
+
+use compact_str::CompactString;
+
+/// Format a value into a `CompactString` that stores up to 24 bytes inline (12 on 32-bit
+/// targets), and only allocates on the heap for a longer output.
+///
+/// Unlike [`ToSmallStringExt`](crate::ToSmallStringExt), the inline capacity is fixed and the
+/// result is as large as a `String`, which suits values stored in structs.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use display_more::DisplayUnixTimeStampExt;
+/// use display_more::ToCompactStringExt;
+///
+/// let t = Duration::from_millis(1723102819023).display_unix_timestamp_short();
+/// let s = t.to_compact_string();
+/// assert_eq!(s, "2024-08-08T07:40:19.023");
+/// assert!(!s.is_heap_allocated());
+/// ```
+pub trait ToCompactStringExt: fmt::Display {
+    fn to_compact_string(&self) -> CompactString {
+        let mut s = CompactString::default();
+        write!(s, "{}", self).expect("a Display implementation returned an error unexpectedly");
+        s
+    }
+}
+
+impl<T: fmt::Display + ?Sized> ToCompactStringExt for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DisplayOptionExt;
+    use crate::DisplaySliceExt;
+
+    #[test]
+    fn test_to_compact_string() {
+        let s = [1, 2, 3].display().to_compact_string();
+        assert_eq!("[1,2,3]", s);
+        assert!(!s.is_heap_allocated());
+
+        let s = Some(1).display().to_compact_string();
+        assert_eq!("1", s);
+
+        let s = "0123456789012345678901234567890123456789".to_compact_string();
+        assert_eq!("0123456789012345678901234567890123456789", s);
+        assert!(s.is_heap_allocated());
+    }
+}
@@ -0,0 +1,237 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+use core::fmt::Write;
+
+/// Implement `Display` for any `T` that is `Debug`, by outputting its single-line `{:?}` form.
+///
+/// This lets types that only derive `Debug` be used where a `Display` value is expected,
+/// e.g., as the element of a [`DisplaySlice`](crate::display_slice::DisplaySlice).
+#[must_use]
+pub struct DebugAsDisplay<'a, T: fmt::Debug + ?Sized>(pub &'a T);
+
+impl<T: fmt::Debug + ?Sized> fmt::Display for DebugAsDisplay<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for DebugAsDisplay<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a `Debug` value with its pretty `{:#?}` form collapsed to a single line.
+///
+/// Newlines and runs of whitespace are replaced with a single space, or removed inside `[..]` and
+/// `(..)`, and the trailing comma that `{:#?}` adds before a closing bracket is removed, so that
+/// the output looks like `{:?}`. Whitespace in quoted strings and chars is kept as is.
+#[must_use]
+pub struct DebugOneLine<'a, T: fmt::Debug + ?Sized>(pub &'a T);
+
+impl<T: fmt::Debug + ?Sized> fmt::Display for DebugOneLine<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut w = OneLineWriter {
+            f,
+            quote: None,
+            escaped: false,
+            last: '\0',
+            pending_comma: false,
+            pending_space: false,
+            pending_newline: false,
+        };
+        write!(w, "{:#?}", self.0)?;
+        if w.pending_comma {
+            w.f.write_char(',')?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for DebugOneLine<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// A `fmt::Write` that collapses whitespace outside of quoted strings and chars.
+struct OneLineWriter<'a, 'f> {
+    f: &'a mut fmt::Formatter<'f>,
+    /// The quote char of the string or char being written.
+    quote: Option<char>,
+    /// Whether the previous char in a quoted string is an unescaped `\`.
+    escaped: bool,
+    /// The last char written outside of quoted strings.
+    last: char,
+    /// A `,` that is written unless a newline and a closing bracket follow.
+    pending_comma: bool,
+    pending_space: bool,
+    pending_newline: bool,
+}
+
+impl Write for OneLineWriter<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if let Some(q) = self.quote {
+                self.f.write_char(c)?;
+                if self.escaped {
+                    self.escaped = false;
+                } else if c == '\\' {
+                    self.escaped = true;
+                } else if c == q {
+                    self.quote = None;
+                }
+                continue;
+            }
+
+            if c.is_whitespace() {
+                self.pending_space = true;
+                self.pending_newline |= c == '\n';
+                continue;
+            }
+
+            let closing = self.pending_newline && matches!(c, '}' | ']' | ')');
+            if self.pending_comma {
+                if !closing {
+                    self.f.write_char(',')?;
+                }
+                self.pending_comma = false;
+            }
+            if self.pending_space {
+                let tight = matches!(self.last, '[' | '(') || (closing && c != '}');
+                if !tight {
+                    self.f.write_char(' ')?;
+                }
+                self.pending_space = false;
+            }
+            self.pending_newline = false;
+            self.last = c;
+
+            match c {
+                ',' => self.pending_comma = true,
+                '"' | '\'' => {
+                    self.quote = Some(c);
+                    self.f.write_char(c)?;
+                }
+                _ => self.f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Display any `Debug` value with its `Debug` output.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DebugAsDisplayExt;
+///
+/// #[derive(Debug)]
+/// struct Foo {
+///     a: u64,
+/// }
+///
+/// assert_eq!(Foo { a: 1 }.debug_display().to_string(), "Foo { a: 1 }");
+/// assert_eq!(Foo { a: 1 }.debug_one_line().to_string(), "Foo { a: 1 }");
+/// ```
+pub trait DebugAsDisplayExt: fmt::Debug {
+    fn debug_display(&self) -> DebugAsDisplay<'_, Self>;
+
+    /// Display the pretty `{:#?}` form collapsed to a single line.
+    fn debug_one_line(&self) -> DebugOneLine<'_, Self>;
+}
+
+impl<T> DebugAsDisplayExt for T
+where T: fmt::Debug + ?Sized
+{
+    fn debug_display(&self) -> DebugAsDisplay<'_, Self> {
+        DebugAsDisplay(self)
+    }
+
+    fn debug_one_line(&self) -> DebugOneLine<'_, Self> {
+        DebugOneLine(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DisplaySliceExt;
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum State {
+        Leader { term: u64 },
+        Follower,
+    }
+
+    #[test]
+    fn test_debug_display() {
+        assert_eq!("\"a\"", "a".debug_display().to_string());
+        assert_eq!("[1, 2]", vec![1, 2].debug_display().to_string());
+        assert_eq!(
+            "Leader { term: 3 }",
+            State::Leader { term: 3 }.debug_display().to_string()
+        );
+    }
+
+    #[test]
+    fn test_debug_display_in_slice() {
+        let states = [State::Follower, State::Leader { term: 2 }];
+        let items = states.iter().map(|s| s.debug_display()).collect::<Vec<_>>();
+        assert_eq!(
+            "[Follower, Leader { term: 2 }]",
+            items.display().sep(", ").to_string()
+        );
+    }
+
+    struct Pretty;
+
+    impl fmt::Debug for Pretty {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            if f.alternate() {
+                write!(f, "Pretty {{\n\t  x\r\n}}")
+            } else {
+                write!(f, "Plain")
+            }
+        }
+    }
+
+    #[test]
+    fn test_debug_one_line() {
+        assert_eq!(
+            "Leader { term: 3 }",
+            State::Leader { term: 3 }.debug_one_line().to_string()
+        );
+        assert_eq!("[1, 2]", vec![1, 2].debug_one_line().to_string());
+        assert_eq!(
+            "[[1], [2, 3]]",
+            vec![vec![1], vec![2, 3]].debug_one_line().to_string()
+        );
+        assert_eq!("Pretty { x }", Pretty.debug_one_line().to_string());
+        assert_eq!("[]", Vec::<u8>::new().debug_one_line().to_string());
+    }
+
+    #[test]
+    fn test_debug_one_line_quoted() {
+        let v = (["a  b,\n"], ' ', '"', "\"  )");
+        assert_eq!(
+            r#"(["a  b,\n"], ' ', '"', "\"  )")"#,
+            v.debug_one_line().to_string()
+        );
+    }
+}
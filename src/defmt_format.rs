@@ -0,0 +1,44 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `defmt::Format` implementations for the wrappers, enabled by the `defmt` feature.
+//!
+//! A wrapper is formatted on the target with its `core::fmt::Display` implementation, thus the
+//! output, such as the truncated slice, is the same as with `{}`.
+//!
+//! ```rust,ignore
+//! use display_more::DisplaySliceExt;
+//!
+//! let buf = [1u8, 2, 3, 4, 5, 6, 7];
+//! defmt::info!("rx: {}", buf.display());
+//! ```
+
+use defmt::Display2Format;
+use defmt::Format;
+use defmt::Formatter;
+
+/// Implement `defmt::Format` with `Display2Format`, for `[generics] Type where [bounds]`.
+macro_rules! impl_defmt_format {
+    ($([$($g:tt)*] $ty:ty $(where [$($w:tt)*])?;)*) => {
+        $(
+            impl<$($g)*> Format for $ty $(where $($w)*)? {
+                fn format(&self, f: Formatter<'_>) {
+                    defmt::write!(f, "{}", Display2Format(self))
+                }
+            }
+        )*
+    };
+}
+
+for_each_wrapper!(impl_defmt_format);
@@ -0,0 +1,376 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Display a compact line-based diff of two values, such as "expected vs actual" or a config
+//! change.
+//!
+//! Removed lines start with `-`, added lines with `+` and unchanged lines with a space. At most
+//! `context` unchanged lines are kept around each change; a skipped run of unchanged lines is
+//! displayed as `..`. Nothing is displayed if the values are equal.
+//!
+//! # Example
+//!
+//! ```rust
+//! use display_more::diff::diff;
+//!
+//! let old = "a\nb\nc\nd\ne\nf";
+//! let new = "a\nb\nC\nd\ne\nf";
+//!
+//! assert_eq!(
+//!     diff(old, new).context(1).to_string(),
+//!     ["..", " b", "-c", "+C", " d", ".."].join("\n")
+//! );
+//! assert_eq!(diff(1, 2).to_string(), "-1\n+2");
+//! ```
+
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The max number of cells of the LCS table, about 8 MiB on a 64-bit target.
+///
+/// Beyond it, the changed lines are displayed as all removed and then all added, rather than
+/// allocating a table quadratic in the number of lines.
+const MAX_LCS_CELLS: usize = 1 << 20;
+
+/// A diff of the rendered text of two values. See the [module docs](self).
+#[must_use]
+pub struct DisplayDiff {
+    old: String,
+    new: String,
+    context: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Keep,
+    Remove,
+    Add,
+}
+
+impl DisplayDiff {
+    pub fn new(old: String, new: String) -> Self {
+        Self {
+            old,
+            new,
+            context: 3,
+        }
+    }
+
+    /// Set the number of unchanged lines kept around each change. By default it is 3.
+    pub const fn context(mut self, context: usize) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Whether the two values differ.
+    pub fn has_changes(&self) -> bool {
+        self.old != self.new
+    }
+
+    /// Compute the edit script: the common prefix and suffix are kept, and the lines between
+    /// are diffed with the longest common subsequence.
+    fn ops<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Op, &'a str)> {
+        let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let mut ops = Vec::with_capacity(old.len() + new.len());
+        ops.extend(old[..prefix].iter().map(|l| (Op::Keep, *l)));
+        Self::lcs_ops(
+            &old[prefix..old.len() - suffix],
+            &new[prefix..new.len() - suffix],
+            &mut ops,
+        );
+        ops.extend(old[old.len() - suffix..].iter().map(|l| (Op::Keep, *l)));
+        ops
+    }
+
+    /// Append the edit script of `old` to `new` by the longest common subsequence of lines.
+    ///
+    /// If the table would exceed [`MAX_LCS_CELLS`], all of `old` is removed and all of `new`
+    /// is added instead.
+    fn lcs_ops<'a>(old: &[&'a str], new: &[&'a str], ops: &mut Vec<(Op, &'a str)>) {
+        let (n, m) = (old.len(), new.len());
+
+        let cells = (n + 1).saturating_mul(m + 1);
+        if cells > MAX_LCS_CELLS {
+            ops.extend(old.iter().map(|l| (Op::Remove, *l)));
+            ops.extend(new.iter().map(|l| (Op::Add, *l)));
+            return;
+        }
+
+        // lcs[i * w + j]: the LCS length of old[i..] and new[j..]
+        let w = m + 1;
+        let mut lcs = vec![0usize; cells];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i * w + j] = if old[i] == new[j] {
+                    lcs[(i + 1) * w + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * w + j].max(lcs[i * w + j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < n || j < m {
+            if i < n && j < m && old[i] == new[j] {
+                ops.push((Op::Keep, old[i]));
+                i += 1;
+                j += 1;
+            } else if j == m || (i < n && lcs[(i + 1) * w + j] >= lcs[i * w + j + 1]) {
+                ops.push((Op::Remove, old[i]));
+                i += 1;
+            } else {
+                ops.push((Op::Add, new[j]));
+                j += 1;
+            }
+        }
+    }
+}
+
+impl fmt::Display for DisplayDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.has_changes() {
+            return Ok(());
+        }
+
+        let old = self.old.split('\n').collect::<Vec<_>>();
+        let new = self.new.split('\n').collect::<Vec<_>>();
+        let ops = Self::ops(&old, &new);
+
+        // Keep an unchanged line only if it is within `context` lines of a change.
+        // The distance to the nearest change is computed with a forward and a backward pass.
+        let mut distance = vec![usize::MAX; ops.len()];
+        let mut last_change = None;
+        for (k, (op, _)) in ops.iter().enumerate() {
+            if *op != Op::Keep {
+                last_change = Some(k);
+            }
+            if let Some(c) = last_change {
+                distance[k] = k - c;
+            }
+        }
+        last_change = None;
+        for (k, (op, _)) in ops.iter().enumerate().rev() {
+            if *op != Op::Keep {
+                last_change = Some(k);
+            }
+            if let Some(c) = last_change {
+                distance[k] = distance[k].min(c - k);
+            }
+        }
+        let near_change = |k: usize| distance[k] <= self.context;
+
+        let mut first = true;
+        let mut skipping = false;
+
+        for (k, (op, line)) in ops.iter().enumerate() {
+            let line = match op {
+                Op::Keep if !near_change(k) => {
+                    skipping = true;
+                    continue;
+                }
+                Op::Keep => format!(" {}", line),
+                Op::Remove => format!("-{}", line),
+                Op::Add => format!("+{}", line),
+            };
+
+            for l in skipping.then_some("..").into_iter().chain([line.as_str()]) {
+                if !first {
+                    writeln!(f)?;
+                }
+                first = false;
+                f.write_str(l)?;
+            }
+            skipping = false;
+        }
+
+        if skipping {
+            write!(f, "\n..")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for DisplayDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Diff the `Display` output of two values.
+pub fn diff(old: impl fmt::Display, new: impl fmt::Display) -> DisplayDiff {
+    DisplayDiff::new(old.to_string(), new.to_string())
+}
+
+/// Diff the pretty `Debug` output, `{:#?}`, of two values, one field per line.
+///
+/// The values may be of different types, such as an expected value built from literals and the
+/// actual value. Only the differing lines and their context are displayed, which keeps the
+/// "expected vs got" output of large structs short.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::diff_debug;
+///
+/// #[derive(Debug)]
+/// struct Config {
+///     port: u16,
+///     host: &'static str,
+/// }
+///
+/// let old = Config {
+///     port: 80,
+///     host: "a",
+/// };
+/// let new = Config {
+///     port: 8080,
+///     host: "a",
+/// };
+///
+/// assert_eq!(
+///     diff_debug(&old, &new).to_string(),
+///     [
+///         " Config {",
+///         "-    port: 80,",
+///         "+    port: 8080,",
+///         "     host: \"a\",",
+///         " }"
+///     ]
+///     .join("\n")
+/// );
+///
+/// let expected = ["a", "b", "c", "d", "e", "f"];
+/// let got = vec!["a", "b", "c", "d", "e", "F"];
+///
+/// assert_eq!(
+///     diff_debug(&expected, &got).context(1).to_string(),
+///     ["..", "     \"e\",", "-    \"f\",", "+    \"F\",", " ]"].join("\n")
+/// );
+/// ```
+pub fn diff_debug<A, B>(old: &A, new: &B) -> DisplayDiff
+where
+    A: fmt::Debug + ?Sized,
+    B: fmt::Debug + ?Sized,
+{
+    DisplayDiff::new(format!("{:#?}", old), format!("{:#?}", new))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_equal() {
+        let d = diff("a\nb", "a\nb");
+        assert!(!d.has_changes());
+        assert_eq!("", d.to_string());
+    }
+
+    #[test]
+    fn test_diff_single_line() {
+        let d = diff("expected", "actual");
+        assert!(d.has_changes());
+        assert_eq!("-expected\n+actual", d.to_string());
+    }
+
+    #[test]
+    fn test_diff_add_remove() {
+        assert_eq!(" a\n+b\n c", diff("a\nc", "a\nb\nc").to_string());
+        assert_eq!(" a\n-b\n c", diff("a\nb\nc", "a\nc").to_string());
+        assert_eq!("-a\n-b\n+x", diff("a\nb", "x").to_string());
+    }
+
+    #[test]
+    fn test_diff_context() {
+        let old = (1..=10)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let new = old.replace("2\n", "two\n").replace("9\n", "nine\n");
+
+        assert_eq!(
+            ["..", "-2", "+two", "..", "-9", "+nine", ".."].join("\n"),
+            diff(&old, &new).context(0).to_string()
+        );
+        assert_eq!(
+            [" 1", "-2", "+two", " 3", "..", " 8", "-9", "+nine", " 10"].join("\n"),
+            diff(&old, &new).context(1).to_string()
+        );
+        assert_eq!(
+            [" 1", "-2", "+two", " 3", " 4", " 5", " 6", " 7", " 8", "-9", "+nine", " 10"]
+                .join("\n"),
+            diff(&old, &new).to_string()
+        );
+        assert_eq!(
+            diff(&old, &new).to_string(),
+            diff(&old, &new).context(usize::MAX).to_string()
+        );
+    }
+
+    #[test]
+    fn test_diff_large_common_prefix_and_suffix() {
+        let old = (0..100_000)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let new = old.replace("\n50000\n", "\nfifty\n");
+
+        assert_eq!(
+            ["..", " 49999", "-50000", "+fifty", " 50001", ".."].join("\n"),
+            diff(&old, &new).context(1).to_string()
+        );
+    }
+
+    #[test]
+    fn test_diff_too_large_to_align() {
+        let old = (0..2000).map(|i| format!("o{i}")).collect::<Vec<_>>();
+        let new = (0..2000).map(|i| format!("n{i}")).collect::<Vec<_>>();
+
+        let d = diff(
+            ["head", &old.join("\n"), "tail"].join("\n"),
+            ["head", &new.join("\n"), "tail"].join("\n"),
+        )
+        .context(0)
+        .to_string();
+
+        let want = ["..".to_string()]
+            .into_iter()
+            .chain(old.iter().map(|l| format!("-{l}")))
+            .chain(new.iter().map(|l| format!("+{l}")))
+            .chain(["..".to_string()])
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(want, d);
+    }
+
+    #[test]
+    fn test_diff_debug() {
+        let old: &[u8] = &[1, 2];
+        let d = diff_debug(old, &vec![1u8, 3]);
+        assert_eq!(" [\n     1,\n-    2,\n+    3,\n ]", d.to_string());
+
+        assert!(!diff_debug(&Some(1), &Some(1)).has_changes());
+    }
+}
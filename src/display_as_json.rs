@@ -0,0 +1,334 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! JSON output for the wrappers: [`AsJsonExt::as_json`].
+
+use alloc::collections::BTreeMap;
+use core::fmt;
+use core::fmt::Write;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::display_once;
+use crate::display_option::DisplayOption;
+use crate::display_result::DisplayResult;
+use crate::display_slice::DisplaySlice;
+use crate::display_unix_epoch::DisplayUnixTimeStamp;
+use crate::DisplayBTreeSet;
+use crate::DisplayBTreeSetOwned;
+use crate::DisplayIntoIter;
+use crate::DisplayOptionOwned;
+use crate::DisplayResultOwned;
+use crate::DisplaySliceOwned;
+use crate::Unstyled;
+
+/// Output a value as JSON instead of its plain-text display.
+///
+/// - `None` outputs `null` and `Some` the displayed value as a string;
+/// - `Result` outputs `{"Ok":"..."}` or `{"Err":"..."}`;
+/// - slices and sets output an array of the displayed elements, with the ellipsis as an element
+///   in place of the omitted ones;
+/// - maps output an object of the displayed keys and values;
+/// - timestamps output an RFC 3339 string in UTC, or `null` if there is no time.
+///
+/// Strings are escaped as JSON strings, so the output is always valid JSON.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::AsJsonExt;
+/// use display_more::DisplayOptionExt;
+/// use display_more::DisplayResultExt;
+/// use display_more::DisplaySliceExt;
+///
+/// let none: Option<u64> = None;
+/// assert_eq!(none.display().as_json().to_string(), "null");
+///
+/// let r: Result<u64, &str> = Err("say \"no\"");
+/// assert_eq!(r.display().as_json().to_string(), r#"{"Err":"say \"no\""}"#);
+///
+/// let a = [1, 2, 3, 4, 5, 6, 7];
+/// assert_eq!(
+///     a.display().as_json().to_string(),
+///     r#"["1","2","3","4","..","7"]"#
+/// );
+/// ```
+pub trait AsJsonExt {
+    /// Write `self` as JSON.
+    fn fmt_json(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    /// Return a `Display` that outputs `self` as JSON.
+    fn as_json(&self) -> AsJson<'_, Self> {
+        AsJson { inner: self }
+    }
+}
+
+/// Display a value as JSON, created by [`AsJsonExt::as_json`].
+#[must_use]
+pub struct AsJson<'a, T: ?Sized> {
+    inner: &'a T,
+}
+
+impl<T: AsJsonExt + ?Sized> fmt::Display for AsJson<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = display_once(|f| self.inner.fmt_json(f));
+        write!(f, "{}", Unstyled::new(inner))
+    }
+}
+
+impl<T: AsJsonExt + ?Sized> fmt::Debug for AsJson<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl<T> AsJsonExt for DisplayOption<'_, T> {
+    fn fmt_json(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.inner {
+            None => f.write_str("null"),
+            Some(_) => write_json_string(f, self),
+        }
+    }
+}
+
+impl<T> AsJsonExt for DisplayOptionOwned<T> {
+    fn fmt_json(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.inner {
+            None => f.write_str("null"),
+            Some(_) => write_json_string(f, self),
+        }
+    }
+}
+
+impl<T: fmt::Display, E: fmt::Display> AsJsonExt for DisplayResult<'_, T, E> {
+    fn fmt_json(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_result_json(f, self.0)
+    }
+}
+
+impl<T: fmt::Display, E: fmt::Display> AsJsonExt for DisplayResultOwned<T, E> {
+    fn fmt_json(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_result_json(f, &self.0)
+    }
+}
+
+fn fmt_result_json<T: fmt::Display, E: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    res: &Result<T, E>,
+) -> fmt::Result {
+    match res {
+        Ok(x) => {
+            f.write_str("{\"Ok\":")?;
+            write_json_string(f, x)?;
+        }
+        Err(e) => {
+            f.write_str("{\"Err\":")?;
+            write_json_string(f, e)?;
+        }
+    }
+    f.write_char('}')
+}
+
+impl<'a, T, S> AsJsonExt for DisplayIntoIter<'a, T, S>
+where
+    T: fmt::Display + 'a,
+    S: Clone + IntoIterator<Item = &'a T>,
+    S::IntoIter: DoubleEndedIterator + ExactSizeIterator,
+{
+    fn fmt_json(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('[')?;
+        for (i, elem) in self
+            .options
+            .layout(self.items.clone().into_iter())
+            .enumerate()
+        {
+            if i > 0 {
+                f.write_char(',')?;
+            }
+            write_json_string(f, elem)?;
+        }
+        f.write_char(']')
+    }
+}
+
+impl<T: fmt::Display> AsJsonExt for DisplaySlice<'_, T> {
+    fn fmt_json(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt_json(f)
+    }
+}
+
+impl<T: fmt::Display> AsJsonExt for DisplaySliceOwned<T> {
+    fn fmt_json(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        DisplayIntoIter::with_options(&self.items, self.options).fmt_json(f)
+    }
+}
+
+impl<T: fmt::Display> AsJsonExt for DisplayBTreeSet<'_, T> {
+    fn fmt_json(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt_json(f)
+    }
+}
+
+impl<T: fmt::Display> AsJsonExt for DisplayBTreeSetOwned<T> {
+    fn fmt_json(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        DisplayIntoIter::with_options(&self.items, self.options).fmt_json(f)
+    }
+}
+
+impl AsJsonExt for DisplayUnixTimeStamp {
+    fn fmt_json(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(datetime) = self.datetime() else {
+            return f.write_str("null");
+        };
+        f.write_char('"')?;
+        self.fmt_datetime(f, &datetime)?;
+        f.write_str("Z\"")
+    }
+}
+
+impl<K: fmt::Display, V: fmt::Display> AsJsonExt for BTreeMap<K, V> {
+    fn fmt_json(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_map_json(f, self.iter())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: fmt::Display, V: fmt::Display, H> AsJsonExt for HashMap<K, V, H> {
+    fn fmt_json(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_map_json(f, self.iter())
+    }
+}
+
+fn fmt_map_json<'a, K, V>(
+    f: &mut fmt::Formatter<'_>,
+    entries: impl Iterator<Item = (&'a K, &'a V)>,
+) -> fmt::Result
+where
+    K: fmt::Display + 'a,
+    V: fmt::Display + 'a,
+{
+    f.write_char('{')?;
+    for (i, (k, v)) in entries.enumerate() {
+        if i > 0 {
+            f.write_char(',')?;
+        }
+        write_json_string(f, k)?;
+        f.write_char(':')?;
+        write_json_string(f, v)?;
+    }
+    f.write_char('}')
+}
+
+/// Write the display of `d` as a quoted and escaped JSON string.
+pub(crate) fn write_json_string(f: &mut fmt::Formatter<'_>, d: impl fmt::Display) -> fmt::Result {
+    f.write_char('"')?;
+    write!(JsonEscape(f), "{}", d)?;
+    f.write_char('"')
+}
+
+/// A `fmt::Write` that escapes `"`, `\` and control chars as in a JSON string.
+pub(crate) struct JsonEscape<'a, 'f>(pub(crate) &'a mut fmt::Formatter<'f>);
+
+impl Write for JsonEscape<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        write_json_escaped(self.0, s)
+    }
+}
+
+/// Whether [`write_json_escaped`] writes `c` as an escape sequence.
+pub(crate) fn is_json_escaped(c: char) -> bool {
+    matches!(c, '"' | '\\') || c.is_control()
+}
+
+/// Write `s` escaped as the content of a JSON string, without the quotes.
+pub(crate) fn write_json_escaped(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+    use core::time::Duration;
+
+    use super::*;
+    use crate::DisplayOptionExt;
+    use crate::DisplayResultExt;
+    use crate::DisplaySliceExt;
+    use crate::DisplayUnixTimeStampExt;
+
+    #[test]
+    fn test_as_json_option() {
+        let none: Option<u64> = None;
+        assert_eq!("null", none.display().as_json().to_string());
+        assert_eq!(r#""a\nb""#, Some("a\nb").display().as_json().to_string());
+        assert_eq!(
+            r#""\"x\"""#,
+            DisplayOptionOwned::new_debug(Some("x"))
+                .as_json()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_as_json_result() {
+        let ok: Result<u64, &str> = Ok(3);
+        assert_eq!(r#"{"Ok":"3"}"#, ok.display().as_json().to_string());
+        let err: Result<u64, &str> = Err("a\\b");
+        assert_eq!(r#"{"Err":"a\\b"}"#, err.display().as_json().to_string());
+    }
+
+    #[test]
+    fn test_as_json_slice() {
+        let a = ["a\"", "b", "c"];
+        assert_eq!(r#"["a\"","b","c"]"#, a.display().as_json().to_string());
+        assert_eq!(r#"["a\"","..","c"]"#, a.display_n(2).as_json().to_string());
+        assert_eq!(r#"[".."]"#, a.display_n(0).as_json().to_string());
+        assert_eq!(r#"[]"#, Vec::<u8>::new().display().as_json().to_string());
+    }
+
+    #[test]
+    fn test_as_json_map() {
+        let m = BTreeMap::from([("a", 1), ("b\t", 2)]);
+        assert_eq!(r#"{"a":"1","b\t":"2"}"#, m.as_json().to_string());
+        assert_eq!("{}", BTreeMap::<u8, u8>::new().as_json().to_string());
+    }
+
+    #[test]
+    fn test_as_json_timestamp() {
+        let t = Duration::from_millis(1723102819023);
+        assert_eq!(
+            r#""2024-08-08T07:40:19.023Z""#,
+            t.display_unix_timestamp_short().as_json().to_string()
+        );
+        assert_eq!(
+            "null",
+            None::<Duration>
+                .display_unix_timestamp()
+                .as_json()
+                .to_string()
+        );
+    }
+}
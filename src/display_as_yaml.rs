@@ -0,0 +1,522 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! YAML output for maps, sequences and the slice wrappers: [`AsYamlExt::as_yaml`].
+
+use alloc::collections::BTreeMap;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Write;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::display_as_json::write_json_escaped;
+use crate::display_once;
+use crate::display_slice::DisplaySlice;
+use crate::DisplayBTreeSet;
+use crate::DisplayBTreeSetOwned;
+use crate::DisplayIntoIter;
+use crate::DisplaySliceOwned;
+use crate::Unstyled;
+
+/// Output a value as an indented YAML block of `key: value` and `- item` lines.
+///
+/// It is implemented for maps, sequences, sets, `Option` and scalars, which nest to any depth,
+/// and for the slice and set wrappers, whose elements are output as their displayed strings and
+/// whose omitted elements are replaced with the ellipsis element.
+///
+/// A string is quoted if it would otherwise not be read back as the same string, e.g., if it is
+/// empty, contains `: ` or a newline, or looks like a number or `null`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::BTreeMap;
+///
+/// use display_more::AsYamlExt;
+///
+/// let mut m = BTreeMap::new();
+/// m.insert("name", vec!["a: b", "c"]);
+/// m.insert("empty", vec![]);
+///
+/// assert_eq!(
+///     m.as_yaml().to_string(),
+///     "empty: []\nname:\n  - \"a: b\"\n  - c"
+/// );
+///
+/// let nested = vec![vec![vec![1, 2]]];
+/// assert_eq!(nested.as_yaml().max_depth(2).to_string(), "- - [..]");
+/// ```
+pub trait AsYamlExt {
+    /// Write `self` as YAML.
+    ///
+    /// A block, i.e., a non-empty sequence or map, writes its first line at the current position
+    /// and indents the following lines by `indent` spaces. `depth` is the number of block levels
+    /// that may still be written: a block at depth 0 writes `[..]` or `{..}` instead.
+    fn fmt_yaml(&self, f: &mut fmt::Formatter<'_>, indent: usize, depth: usize) -> fmt::Result;
+
+    /// Return `true` if `self` is written as a block of lines rather than a single value.
+    fn is_yaml_block(&self) -> bool {
+        false
+    }
+
+    /// Return a `Display` that outputs `self` as YAML.
+    fn as_yaml(&self) -> AsYaml<'_, Self> {
+        AsYaml {
+            inner: self,
+            max_depth: 3,
+        }
+    }
+}
+
+/// Display a value as YAML, created by [`AsYamlExt::as_yaml`].
+#[must_use]
+pub struct AsYaml<'a, T: ?Sized> {
+    inner: &'a T,
+    max_depth: usize,
+}
+
+impl<T: ?Sized> AsYaml<'_, T> {
+    /// Set the max nesting depth of sequences and maps; the top level is depth 1. Deeper ones
+    /// are displayed as `[..]` or `{..}`. By default it is 3.
+    pub const fn max_depth(self, max_depth: usize) -> Self {
+        Self { max_depth, ..self }
+    }
+}
+
+impl<T: AsYamlExt + ?Sized> fmt::Display for AsYaml<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = display_once(|f| self.inner.fmt_yaml(f, 0, self.max_depth));
+        write!(f, "{}", Unstyled::new(inner))
+    }
+}
+
+impl<T: AsYamlExt + ?Sized> fmt::Debug for AsYaml<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl<T: AsYamlExt + ?Sized> AsYamlExt for &T {
+    fn fmt_yaml(&self, f: &mut fmt::Formatter<'_>, indent: usize, depth: usize) -> fmt::Result {
+        (**self).fmt_yaml(f, indent, depth)
+    }
+
+    fn is_yaml_block(&self) -> bool {
+        (**self).is_yaml_block()
+    }
+}
+
+impl<T: AsYamlExt> AsYamlExt for Option<T> {
+    fn fmt_yaml(&self, f: &mut fmt::Formatter<'_>, indent: usize, depth: usize) -> fmt::Result {
+        match self {
+            None => f.write_str("null"),
+            Some(x) => x.fmt_yaml(f, indent, depth),
+        }
+    }
+
+    fn is_yaml_block(&self) -> bool {
+        self.as_ref().is_some_and(|x| x.is_yaml_block())
+    }
+}
+
+/// Implement `AsYamlExt` for types whose `Display` output is a valid plain YAML scalar.
+macro_rules! impl_as_yaml_plain {
+    ($($ty:ty),*) => {
+        $(
+            impl AsYamlExt for $ty {
+                fn fmt_yaml(&self, f: &mut fmt::Formatter<'_>, _: usize, _: usize) -> fmt::Result {
+                    write!(f, "{}", self)
+                }
+            }
+        )*
+    };
+}
+
+impl_as_yaml_plain!(bool, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl AsYamlExt for f32 {
+    fn fmt_yaml(&self, f: &mut fmt::Formatter<'_>, _: usize, _: usize) -> fmt::Result {
+        fmt_float(f, *self as f64)
+    }
+}
+
+impl AsYamlExt for f64 {
+    fn fmt_yaml(&self, f: &mut fmt::Formatter<'_>, _: usize, _: usize) -> fmt::Result {
+        fmt_float(f, *self)
+    }
+}
+
+fn fmt_float(f: &mut fmt::Formatter<'_>, x: f64) -> fmt::Result {
+    if x.is_nan() {
+        f.write_str(".nan")
+    } else if x.is_infinite() {
+        f.write_str(if x > 0.0 { ".inf" } else { "-.inf" })
+    } else {
+        write!(f, "{}", x)
+    }
+}
+
+impl AsYamlExt for char {
+    fn fmt_yaml(&self, f: &mut fmt::Formatter<'_>, _: usize, _: usize) -> fmt::Result {
+        fmt_str(f, self.encode_utf8(&mut [0; 4]), true)
+    }
+}
+
+impl AsYamlExt for str {
+    fn fmt_yaml(&self, f: &mut fmt::Formatter<'_>, _: usize, _: usize) -> fmt::Result {
+        fmt_str(f, self, true)
+    }
+}
+
+impl AsYamlExt for String {
+    fn fmt_yaml(&self, f: &mut fmt::Formatter<'_>, _: usize, _: usize) -> fmt::Result {
+        fmt_str(f, self, true)
+    }
+}
+
+/// A `Display` value output as a YAML string.
+struct Scalar<D>(D);
+
+impl<D: fmt::Display> AsYamlExt for Scalar<D> {
+    fn fmt_yaml(&self, f: &mut fmt::Formatter<'_>, _: usize, _: usize) -> fmt::Result {
+        let mut buf = String::new();
+        write!(buf, "{}", self.0)?;
+        fmt_str(f, &buf, false)
+    }
+}
+
+impl<T: AsYamlExt> AsYamlExt for [T] {
+    fn fmt_yaml(&self, f: &mut fmt::Formatter<'_>, indent: usize, depth: usize) -> fmt::Result {
+        fmt_seq(f, indent, depth, self.iter())
+    }
+
+    fn is_yaml_block(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+impl<T: AsYamlExt> AsYamlExt for Vec<T> {
+    fn fmt_yaml(&self, f: &mut fmt::Formatter<'_>, indent: usize, depth: usize) -> fmt::Result {
+        self.as_slice().fmt_yaml(f, indent, depth)
+    }
+
+    fn is_yaml_block(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+impl<T: AsYamlExt> AsYamlExt for BTreeSet<T> {
+    fn fmt_yaml(&self, f: &mut fmt::Formatter<'_>, indent: usize, depth: usize) -> fmt::Result {
+        fmt_seq(f, indent, depth, self.iter())
+    }
+
+    fn is_yaml_block(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+impl<K: fmt::Display, V: AsYamlExt> AsYamlExt for BTreeMap<K, V> {
+    fn fmt_yaml(&self, f: &mut fmt::Formatter<'_>, indent: usize, depth: usize) -> fmt::Result {
+        fmt_map(f, indent, depth, self.iter())
+    }
+
+    fn is_yaml_block(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: fmt::Display, V: AsYamlExt, H> AsYamlExt for HashMap<K, V, H> {
+    fn fmt_yaml(&self, f: &mut fmt::Formatter<'_>, indent: usize, depth: usize) -> fmt::Result {
+        fmt_map(f, indent, depth, self.iter())
+    }
+
+    fn is_yaml_block(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+impl<'a, T, S> AsYamlExt for DisplayIntoIter<'a, T, S>
+where
+    T: fmt::Display + 'a,
+    S: Clone + IntoIterator<Item = &'a T>,
+    S::IntoIter: DoubleEndedIterator + ExactSizeIterator,
+{
+    fn fmt_yaml(&self, f: &mut fmt::Formatter<'_>, indent: usize, depth: usize) -> fmt::Result {
+        let layout = self.options.layout(self.items.clone().into_iter());
+        fmt_seq(f, indent, depth, layout.map(Scalar))
+    }
+
+    fn is_yaml_block(&self) -> bool {
+        self.items.clone().into_iter().len() > 0
+    }
+}
+
+impl<T: fmt::Display> AsYamlExt for DisplaySlice<'_, T> {
+    fn fmt_yaml(&self, f: &mut fmt::Formatter<'_>, indent: usize, depth: usize) -> fmt::Result {
+        self.inner.fmt_yaml(f, indent, depth)
+    }
+
+    fn is_yaml_block(&self) -> bool {
+        self.inner.is_yaml_block()
+    }
+}
+
+impl<T: fmt::Display> AsYamlExt for DisplaySliceOwned<T> {
+    fn fmt_yaml(&self, f: &mut fmt::Formatter<'_>, indent: usize, depth: usize) -> fmt::Result {
+        DisplayIntoIter::with_options(&self.items, self.options).fmt_yaml(f, indent, depth)
+    }
+
+    fn is_yaml_block(&self) -> bool {
+        !self.items.is_empty()
+    }
+}
+
+impl<T: fmt::Display> AsYamlExt for DisplayBTreeSet<'_, T> {
+    fn fmt_yaml(&self, f: &mut fmt::Formatter<'_>, indent: usize, depth: usize) -> fmt::Result {
+        self.inner.fmt_yaml(f, indent, depth)
+    }
+
+    fn is_yaml_block(&self) -> bool {
+        self.inner.is_yaml_block()
+    }
+}
+
+impl<T: fmt::Display> AsYamlExt for DisplayBTreeSetOwned<T> {
+    fn fmt_yaml(&self, f: &mut fmt::Formatter<'_>, indent: usize, depth: usize) -> fmt::Result {
+        DisplayIntoIter::with_options(&self.items, self.options).fmt_yaml(f, indent, depth)
+    }
+
+    fn is_yaml_block(&self) -> bool {
+        !self.items.is_empty()
+    }
+}
+
+/// Write a sequence as `- item` lines, or `[]` if it is empty.
+fn fmt_seq<T: AsYamlExt>(
+    f: &mut fmt::Formatter<'_>,
+    indent: usize,
+    depth: usize,
+    items: impl Iterator<Item = T>,
+) -> fmt::Result {
+    let mut items = items.peekable();
+    if items.peek().is_none() {
+        return f.write_str("[]");
+    }
+    if depth == 0 {
+        return f.write_str("[..]");
+    }
+
+    for (i, item) in items.enumerate() {
+        if i > 0 {
+            write!(f, "\n{:indent$}", "")?;
+        }
+        // A nested block starts on the same line: `- - a` or `- k: v`.
+        f.write_str("- ")?;
+        item.fmt_yaml(f, indent + 2, depth - 1)?;
+    }
+    Ok(())
+}
+
+/// Write a map as `key: value` lines, or `{}` if it is empty.
+fn fmt_map<'a, K, V>(
+    f: &mut fmt::Formatter<'_>,
+    indent: usize,
+    depth: usize,
+    entries: impl Iterator<Item = (&'a K, &'a V)>,
+) -> fmt::Result
+where
+    K: fmt::Display + 'a,
+    V: AsYamlExt + 'a,
+{
+    let mut entries = entries.peekable();
+    if entries.peek().is_none() {
+        return f.write_str("{}");
+    }
+    if depth == 0 {
+        return f.write_str("{..}");
+    }
+
+    for (i, (k, v)) in entries.enumerate() {
+        if i > 0 {
+            write!(f, "\n{:indent$}", "")?;
+        }
+        Scalar(k).fmt_yaml(f, indent, depth)?;
+        f.write_char(':')?;
+
+        let nested = indent + 2;
+        if v.is_yaml_block() && depth > 1 {
+            write!(f, "\n{:nested$}", "")?;
+        } else {
+            f.write_char(' ')?;
+        }
+        v.fmt_yaml(f, nested, depth - 1)?;
+    }
+    Ok(())
+}
+
+/// Write a string as a plain YAML scalar, or double-quoted if it would be read differently.
+///
+/// If `typed` is true, a string that looks like a number, bool or null is quoted too.
+fn fmt_str(f: &mut fmt::Formatter<'_>, s: &str, typed: bool) -> fmt::Result {
+    if !needs_quote(s, typed) {
+        return f.write_str(s);
+    }
+    f.write_char('"')?;
+    write_json_escaped(f, s)?;
+    f.write_char('"')
+}
+
+fn needs_quote(s: &str, typed: bool) -> bool {
+    let Some(first) = s.chars().next() else {
+        return true;
+    };
+
+    if "-?:,[]{}#&*!|>'\"%@`".contains(first)
+        || s.starts_with(' ')
+        || s.ends_with([' ', ':'])
+        || s.contains(": ")
+        || s.contains(" #")
+        || s.contains(char::is_control)
+    {
+        return true;
+    }
+
+    typed && (is_yaml_keyword(s) || is_yaml_number(s))
+}
+
+/// Whether a YAML 1.1 or 1.2 parser reads `s` as a null or bool.
+fn is_yaml_keyword(s: &str) -> bool {
+    matches!(s, "null" | "Null" | "NULL" | "~")
+        || matches!(s, "true" | "True" | "TRUE" | "false" | "False" | "FALSE")
+        || matches!(
+            s,
+            "y" | "Y" | "yes" | "Yes" | "YES" | "n" | "N" | "no" | "No" | "NO"
+        )
+        || matches!(s, "on" | "On" | "ON" | "off" | "Off" | "OFF")
+}
+
+/// Whether a YAML parser may read `s` as a number, such as `1_000`, `0x1F`, `0o17` or `.NaN`.
+fn is_yaml_number(s: &str) -> bool {
+    if s.parse::<f64>().is_ok() {
+        return true;
+    }
+
+    let unsigned = s.strip_prefix(['+', '-']).unwrap_or(s);
+    if unsigned.eq_ignore_ascii_case(".inf") || unsigned.eq_ignore_ascii_case(".nan") {
+        return true;
+    }
+
+    let (digits, radix) = if let Some(d) = unsigned.strip_prefix("0x") {
+        (d, 16)
+    } else if let Some(d) = unsigned.strip_prefix("0o") {
+        (d, 8)
+    } else if let Some(d) = unsigned.strip_prefix("0b") {
+        (d, 2)
+    } else {
+        (unsigned, 10)
+    };
+    digits.starts_with(|c: char| c.is_digit(radix))
+        && digits.chars().all(|c| c == '_' || c.is_digit(radix))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    use super::*;
+    use crate::DisplaySliceExt;
+
+    #[test]
+    fn test_as_yaml_scalar() {
+        assert_eq!("1", 1u8.as_yaml().to_string());
+        assert_eq!("true", true.as_yaml().to_string());
+        assert_eq!(".nan", f64::NAN.as_yaml().to_string());
+        assert_eq!("null", None::<u8>.as_yaml().to_string());
+        assert_eq!("a b", "a b".as_yaml().to_string());
+        assert_eq!(r#""""#, "".as_yaml().to_string());
+        assert_eq!(r#""1.5""#, "1.5".as_yaml().to_string());
+        assert_eq!(r#""null""#, "null".as_yaml().to_string());
+        assert_eq!(r#""- a""#, "- a".as_yaml().to_string());
+        assert_eq!(r#""a\nb""#, "a\nb".as_yaml().to_string());
+        assert_eq!(r#""k: v""#, "k: v".as_yaml().to_string());
+        assert_eq!("k:v", "k:v".as_yaml().to_string());
+    }
+
+    #[test]
+    fn test_as_yaml_quote_typed_scalars() {
+        for s in [
+            "0x1F", "0o17", "0b101", "1_000", "+12", ".inf", "+.Inf", ".NaN", ".NAN", "y", "N",
+            "yes", "No", "on", "OFF",
+        ] {
+            assert_eq!(format!("\"{}\"", s), s.as_yaml().to_string(), "{}", s);
+        }
+
+        for s in ["0x", "0xg", "_1", "1_a", ".info", "yess", "onn"] {
+            assert_eq!(s, s.as_yaml().to_string());
+        }
+    }
+
+    #[test]
+    fn test_as_yaml_nested() {
+        let mut inner = BTreeMap::new();
+        inner.insert("x", vec![1, 2]);
+        inner.insert("y", vec![]);
+
+        let mut m = BTreeMap::new();
+        m.insert("a", vec![inner.clone()]);
+        m.insert("b", vec![]);
+
+        assert_eq!(
+            "a:\n  - x:\n      - 1\n      - 2\n    y: []\nb: []",
+            m.as_yaml().max_depth(4).to_string()
+        );
+        assert_eq!("a:\n  - x: [..]\n    y: []\nb: []", m.as_yaml().to_string());
+        assert_eq!("a:\n  - {..}\nb: []", m.as_yaml().max_depth(2).to_string());
+        assert_eq!("a: [..]\nb: []", m.as_yaml().max_depth(1).to_string());
+        assert_eq!("{..}", m.as_yaml().max_depth(0).to_string());
+        assert_eq!("{}", BTreeMap::<u8, u8>::new().as_yaml().to_string());
+    }
+
+    #[test]
+    fn test_as_yaml_seq() {
+        let v = vec![vec![1, 2], vec![3]];
+        assert_eq!("- - 1\n  - 2\n- - 3", v.as_yaml().to_string());
+        assert_eq!("- [..]\n- [..]", v.as_yaml().max_depth(1).to_string());
+        assert_eq!(
+            "- Some\n- null",
+            vec![Some("Some"), None].as_yaml().to_string()
+        );
+    }
+
+    #[test]
+    fn test_as_yaml_slice_wrapper() {
+        let a = [1, 2, 3, 4, 5, 6, 7];
+        assert_eq!(
+            "- 1\n- 2\n- 3\n- 4\n- 5\n- 6\n- 7",
+            a.display_n(7).as_yaml().to_string()
+        );
+        assert_eq!("- 1\n- ..\n- 7", a.display_n(2).as_yaml().to_string());
+        assert_eq!("- ..", a.display_n(0).as_yaml().to_string());
+        assert_eq!("[]", [0u8; 0].display().as_yaml().to_string());
+
+        let mut m = BTreeMap::new();
+        m.insert("k", a.display_n(2));
+        assert_eq!("k:\n  - 1\n  - ..\n  - 7", m.as_yaml().to_string());
+    }
+}
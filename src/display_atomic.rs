@@ -0,0 +1,168 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+use core::sync::atomic::AtomicBool;
+#[cfg(target_has_atomic = "16")]
+use core::sync::atomic::AtomicI16;
+#[cfg(target_has_atomic = "32")]
+use core::sync::atomic::AtomicI32;
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::AtomicI64;
+use core::sync::atomic::AtomicI8;
+use core::sync::atomic::AtomicIsize;
+#[cfg(target_has_atomic = "16")]
+use core::sync::atomic::AtomicU16;
+#[cfg(target_has_atomic = "32")]
+use core::sync::atomic::AtomicU32;
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::AtomicU64;
+use core::sync::atomic::AtomicU8;
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering;
+
+/// Display the value of an atomic, loaded when formatting.
+///
+/// The value is loaded with `Ordering::Relaxed` by default, see [`ordering`](Self::ordering).
+#[must_use]
+pub struct DisplayAtomic<'a, A: ?Sized> {
+    atomic: &'a A,
+    ordering: Ordering,
+}
+
+impl<'a, A: ?Sized> DisplayAtomic<'a, A> {
+    pub const fn new(atomic: &'a A) -> Self {
+        Self {
+            atomic,
+            ordering: Ordering::Relaxed,
+        }
+    }
+
+    /// Set the ordering to load the value with.
+    ///
+    /// `Release` loads as `Relaxed` and `AcqRel` as `Acquire`, instead of panicking like
+    /// `load()` does.
+    pub const fn ordering(self, ordering: Ordering) -> Self {
+        let ordering = match ordering {
+            Ordering::Release => Ordering::Relaxed,
+            Ordering::AcqRel => Ordering::Acquire,
+            o => o,
+        };
+        Self { ordering, ..self }
+    }
+}
+
+impl<A: DisplayAtomicExt + ?Sized> fmt::Display for DisplayAtomic<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.atomic.load_value(self.ordering), f)
+    }
+}
+
+impl<A: DisplayAtomicExt + ?Sized> fmt::Debug for DisplayAtomic<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a snapshot of an atomic without an explicit `load()`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::atomic::AtomicU64;
+/// use std::sync::atomic::Ordering;
+///
+/// use display_more::DisplayAtomicExt;
+///
+/// let n = AtomicU64::new(3);
+/// assert_eq!(n.display().to_string(), "3");
+/// assert_eq!(n.display().ordering(Ordering::SeqCst).to_string(), "3");
+/// ```
+pub trait DisplayAtomicExt {
+    type Value: fmt::Display;
+
+    /// Load the value with the given ordering, which must be valid for `load()`.
+    fn load_value(&self, ordering: Ordering) -> Self::Value;
+
+    fn display(&self) -> DisplayAtomic<'_, Self> {
+        DisplayAtomic::new(self)
+    }
+}
+
+macro_rules! impl_atomic {
+    ($($(#[$attr:meta])* $atomic:ty => $value:ty;)*) => {
+        $(
+            $(#[$attr])*
+            impl DisplayAtomicExt for $atomic {
+                type Value = $value;
+
+                fn load_value(&self, ordering: Ordering) -> $value {
+                    self.load(ordering)
+                }
+            }
+        )*
+    };
+}
+
+impl_atomic! {
+    AtomicBool => bool;
+    AtomicI8 => i8;
+    #[cfg(target_has_atomic = "16")]
+    AtomicI16 => i16;
+    #[cfg(target_has_atomic = "32")]
+    AtomicI32 => i32;
+    #[cfg(target_has_atomic = "64")]
+    AtomicI64 => i64;
+    AtomicIsize => isize;
+    AtomicU8 => u8;
+    #[cfg(target_has_atomic = "16")]
+    AtomicU16 => u16;
+    #[cfg(target_has_atomic = "32")]
+    AtomicU32 => u32;
+    #[cfg(target_has_atomic = "64")]
+    AtomicU64 => u64;
+    AtomicUsize => usize;
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_display_atomic() {
+        let n = AtomicUsize::new(1);
+        let d = n.display();
+        n.store(2, Ordering::Relaxed);
+        assert_eq!("2", d.to_string());
+
+        assert_eq!("true", AtomicBool::new(true).display().to_string());
+        assert_eq!("-3", AtomicI8::new(-3).display().to_string());
+    }
+
+    #[test]
+    fn test_display_atomic_ordering() {
+        let n = AtomicU8::new(7);
+        for o in [
+            Ordering::Relaxed,
+            Ordering::Acquire,
+            Ordering::SeqCst,
+            Ordering::Release,
+            Ordering::AcqRel,
+        ] {
+            assert_eq!("7", n.display().ordering(o).to_string());
+        }
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::backtrace::Backtrace;
+use std::backtrace::BacktraceStatus;
+use std::fmt;
+use std::vec::Vec;
+
+/// Crates whose frames are dropped from a summary.
+const NOISE_CRATES: &[&str] = &["std::", "core::", "alloc::", "backtrace::"];
+
+/// Display a short summary of a `Backtrace`, one `symbol at file:line:col` line per frame.
+///
+/// Frames of `std`, `core`, `alloc`, runtime start-up and closure shims are dropped. If any
+/// [`prefix`](Self::prefix) is given, only frames whose symbol starts with one of them are
+/// kept. At most [`depth`](Self::depth) frames are displayed, default 5, followed by
+/// `.. (n more)` if some are omitted.
+///
+/// A backtrace that was not captured is displayed as is.
+#[must_use]
+pub struct DisplayBacktraceSummary<'a> {
+    backtrace: &'a Backtrace,
+    prefixes: Vec<&'a str>,
+    depth: usize,
+}
+
+impl<'a> DisplayBacktraceSummary<'a> {
+    pub const fn new(backtrace: &'a Backtrace) -> Self {
+        Self {
+            backtrace,
+            prefixes: Vec::new(),
+            depth: 5,
+        }
+    }
+
+    /// Keep only frames whose symbol starts with `prefix`, such as `my_crate::`.
+    ///
+    /// It can be called more than once to keep frames of several crates.
+    pub fn prefix(mut self, prefix: &'a str) -> Self {
+        self.prefixes.push(prefix);
+        self
+    }
+
+    /// Set the max number of frames to display.
+    pub const fn depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    fn is_kept(&self, symbol: &str) -> bool {
+        let s = symbol.trim_start_matches(['<', '&']);
+        let s = s.strip_prefix("dyn ").unwrap_or(s);
+
+        if !s.contains("::") || s.starts_with("fn(") {
+            return false;
+        }
+        if NOISE_CRATES.iter().any(|c| s.starts_with(c)) {
+            return false;
+        }
+        self.prefixes.is_empty() || self.prefixes.iter().any(|p| s.starts_with(p))
+    }
+
+    /// Write the summary of the text produced by `Backtrace`'s `Display`.
+    fn fmt_summary(&self, text: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut frames: Vec<(&str, Option<&str>)> = Vec::new();
+        let mut keep_current = false;
+
+        for line in text.lines() {
+            let line = line.trim();
+            if let Some(loc) = line.strip_prefix("at ") {
+                if keep_current {
+                    if let Some(frame) = frames.last_mut() {
+                        frame.1 = Some(loc);
+                    }
+                }
+                continue;
+            }
+
+            let Some((index, symbol)) = line.split_once(": ") else {
+                continue;
+            };
+            if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+                continue;
+            }
+            keep_current = self.is_kept(symbol);
+            if keep_current {
+                frames.push((symbol, None));
+            }
+        }
+
+        for (i, (symbol, loc)) in frames.iter().take(self.depth).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", symbol)?;
+            if let Some(loc) = loc {
+                write!(f, " at {}", loc)?;
+            }
+        }
+
+        if frames.len() > self.depth {
+            if self.depth > 0 {
+                writeln!(f)?;
+            }
+            write!(f, ".. ({} more)", frames.len() - self.depth)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DisplayBacktraceSummary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.backtrace.status() != BacktraceStatus::Captured {
+            return fmt::Display::fmt(self.backtrace, f);
+        }
+        self.fmt_summary(&self.backtrace.to_string(), f)
+    }
+}
+
+impl fmt::Debug for DisplayBacktraceSummary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a `Backtrace` as a few lines of the interesting frames, for logging.
+///
+/// # Example
+///
+/// ```rust
+/// use std::backtrace::Backtrace;
+///
+/// use display_more::DisplayBacktraceExt;
+///
+/// let bt = Backtrace::force_capture();
+/// println!(
+///     "{}",
+///     bt.display_backtrace_summary().prefix("my_crate::").depth(3)
+/// );
+/// ```
+pub trait DisplayBacktraceExt {
+    fn display_backtrace_summary(&self) -> DisplayBacktraceSummary<'_>;
+}
+
+impl DisplayBacktraceExt for Backtrace {
+    fn display_backtrace_summary(&self) -> DisplayBacktraceSummary<'_> {
+        DisplayBacktraceSummary::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::string::String;
+    use std::string::ToString;
+
+    use super::*;
+
+    const TRACE: &str = "   0: my_crate::io::read
+             at ./src/io.rs:10:5
+   1: <my_crate::Foo as core::fmt::Display>::fmt
+             at ./src/foo.rs:3:9
+   2: core::fmt::write
+             at /rustc/abc/library/core/src/fmt/mod.rs:1:1
+   3: other::run
+             at ./other/src/lib.rs:7:1
+   4: <fn() as core::ops::function::FnOnce<()>>::call_once
+             at /rustc/abc/library/core/src/ops/function.rs:250:5
+   5: std::rt::lang_start::<()>::{closure#0}
+             at /rustc/abc/library/std/src/rt.rs:206:18
+   6: main
+   7: __libc_start_main
+   8: _start
+";
+
+    fn summary(d: &DisplayBacktraceSummary<'_>) -> String {
+        struct S<'a, 'b>(&'a DisplayBacktraceSummary<'b>);
+        impl fmt::Display for S<'_, '_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt_summary(TRACE, f)
+            }
+        }
+        S(d).to_string()
+    }
+
+    #[test]
+    fn test_summary_drops_noise() {
+        let bt = Backtrace::disabled();
+        let d = bt.display_backtrace_summary();
+        assert_eq!(
+            summary(&d),
+            "my_crate::io::read at ./src/io.rs:10:5\n\
+             <my_crate::Foo as core::fmt::Display>::fmt at ./src/foo.rs:3:9\n\
+             other::run at ./other/src/lib.rs:7:1"
+        );
+    }
+
+    #[test]
+    fn test_summary_prefix_and_depth() {
+        let bt = Backtrace::disabled();
+
+        let d = bt.display_backtrace_summary().prefix("other::");
+        assert_eq!(summary(&d), "other::run at ./other/src/lib.rs:7:1");
+
+        let d = bt.display_backtrace_summary().prefix("my_crate::").depth(1);
+        assert_eq!(
+            summary(&d),
+            "my_crate::io::read at ./src/io.rs:10:5\n.. (1 more)"
+        );
+
+        let d = bt.display_backtrace_summary().depth(0);
+        assert_eq!(summary(&d), ".. (3 more)");
+    }
+
+    #[test]
+    fn test_summary_captured() {
+        let bt = Backtrace::force_capture();
+        let s = bt
+            .display_backtrace_summary()
+            .prefix("display_more::")
+            .depth(1)
+            .to_string();
+        assert!(s.starts_with("display_more::"), "{}", s);
+
+        let bt = Backtrace::disabled();
+        assert_eq!(bt.display_backtrace_summary().to_string(), bt.to_string());
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::string::ToString;
+use core::fmt;
+
+/// Display a section banner such as `==== compaction report ====`.
+///
+/// The title is centered and padded with the fill char to `width` chars. There are at least 4
+/// fill chars on each side, even if this exceeds `width`.
+#[must_use]
+pub struct Banner<D: fmt::Display> {
+    title: D,
+    width: usize,
+    fill: char,
+}
+
+impl<D: fmt::Display> Banner<D> {
+    /// The minimal number of fill chars on each side of the title.
+    const MIN_FILL: usize = 4;
+
+    pub const fn new(title: D) -> Self {
+        Self {
+            title,
+            width: 0,
+            fill: '=',
+        }
+    }
+
+    /// Set the total width of the banner. By default it is 0, i.e., use the minimal width.
+    pub const fn width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the fill char. By default it is `'='`.
+    pub const fn fill(mut self, fill: char) -> Self {
+        self.fill = fill;
+        self
+    }
+}
+
+impl<D: fmt::Display> fmt::Display for Banner<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let title = self.title.to_string();
+        let title_width = title.chars().count() + 2;
+
+        let total_fill = self.width.saturating_sub(title_width);
+        let left = (total_fill / 2).max(Self::MIN_FILL);
+        let right = (total_fill - total_fill / 2).max(Self::MIN_FILL);
+
+        for _ in 0..left {
+            write!(f, "{}", self.fill)?;
+        }
+        write!(f, " {} ", title)?;
+        for _ in 0..right {
+            write!(f, "{}", self.fill)?;
+        }
+        Ok(())
+    }
+}
+
+impl<D: fmt::Display> fmt::Debug for Banner<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Create a section [`Banner`] with the given title.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::banner;
+///
+/// assert_eq!(
+///     banner("compaction report").to_string(),
+///     "==== compaction report ===="
+/// );
+/// assert_eq!(banner("ok").width(12).fill('-').to_string(), "---- ok ----");
+/// ```
+pub const fn banner<D: fmt::Display>(title: D) -> Banner<D> {
+    Banner::new(title)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_banner() {
+        assert_eq!("==== a ====", banner("a").to_string());
+        assert_eq!("==== 42 ====", banner(42).to_string());
+        assert_eq!("====  ====", banner("").to_string());
+    }
+
+    #[test]
+    fn test_banner_width() {
+        assert_eq!(
+            "======== report ========",
+            banner("report").width(24).to_string()
+        );
+        assert_eq!(24, banner("report").width(24).to_string().len());
+
+        // Odd padding puts the extra fill char on the right
+        assert_eq!(
+            "======= report ========",
+            banner("report").width(23).to_string()
+        );
+
+        // Too narrow: fall back to the minimal fill
+        assert_eq!("==== report ====", banner("report").width(3).to_string());
+
+        // Width is counted in chars
+        assert_eq!("──── é ─────", banner("é").width(12).fill('─').to_string());
+    }
+}
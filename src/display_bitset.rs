@@ -0,0 +1,146 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+
+/// Display the positions of the set bits in a bitmap of `u64` words, with consecutive
+/// positions compressed into ranges, followed by the number of set bits, such as
+/// `{0-14, 63, 128-255} (144 set)`.
+///
+/// Bit `i` is bit `i % 64` of word `i / 64`, counting from the least significant bit.
+#[must_use]
+pub struct DisplayBitset<'a> {
+    words: &'a [u64],
+}
+
+impl<'a> DisplayBitset<'a> {
+    pub const fn new(words: &'a [u64]) -> Self {
+        Self { words }
+    }
+
+    /// Iterate over the positions of the set bits in ascending order.
+    fn positions(&self) -> impl Iterator<Item = usize> + 'a {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut w = word;
+            core::iter::from_fn(move || {
+                if w == 0 {
+                    return None;
+                }
+                let bit = w.trailing_zeros() as usize;
+                w &= w - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+}
+
+impl fmt::Display for DisplayBitset<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_range(
+            f: &mut fmt::Formatter<'_>,
+            first: bool,
+            start: usize,
+            end: usize,
+        ) -> fmt::Result {
+            if !first {
+                write!(f, ", ")?;
+            }
+            if start == end {
+                write!(f, "{}", start)
+            } else {
+                write!(f, "{}-{}", start, end)
+            }
+        }
+
+        write!(f, "{{")?;
+
+        let mut first = true;
+        let mut run: Option<(usize, usize)> = None;
+
+        for pos in self.positions() {
+            match run {
+                Some((start, end)) if end + 1 == pos => run = Some((start, pos)),
+                Some((start, end)) => {
+                    write_range(f, first, start, end)?;
+                    first = false;
+                    run = Some((pos, pos));
+                }
+                None => run = Some((pos, pos)),
+            }
+        }
+        if let Some((start, end)) = run {
+            write_range(f, first, start, end)?;
+        }
+
+        let count: u32 = self.words.iter().map(|w| w.count_ones()).sum();
+        write!(f, "}} ({} set)", count)
+    }
+}
+
+impl fmt::Debug for DisplayBitset<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a bitmap of `u64` words as the compressed positions of its set bits.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayBitsetExt;
+///
+/// let words = [0x7fff | (1 << 63), 0, u64::MAX];
+/// assert_eq!(
+///     words.display_bits().to_string(),
+///     "{0-14, 63, 128-191} (80 set)"
+/// );
+/// ```
+pub trait DisplayBitsetExt {
+    fn display_bits(&self) -> DisplayBitset<'_>;
+}
+
+impl DisplayBitsetExt for [u64] {
+    fn display_bits(&self) -> DisplayBitset<'_> {
+        DisplayBitset::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_display_bitset() {
+        assert_eq!("{} (0 set)", [0u64; 2].display_bits().to_string());
+        assert_eq!("{} (0 set)", [].display_bits().to_string());
+        assert_eq!("{0} (1 set)", [1u64].display_bits().to_string());
+        assert_eq!(
+            "{1, 3, 5} (3 set)",
+            [0b101010u64].display_bits().to_string()
+        );
+        assert_eq!(
+            "{0-127} (128 set)",
+            [u64::MAX, u64::MAX].display_bits().to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_bitset_across_words() {
+        let words = [1u64 << 63, 1, 1 << 1];
+        assert_eq!("{63-64, 129} (3 set)", words.display_bits().to_string());
+    }
+}
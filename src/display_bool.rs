@@ -0,0 +1,125 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+
+/// The words used to display a `bool`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoolStyle {
+    /// `true` / `false`
+    TrueFalse,
+    /// `yes` / `no`
+    YesNo,
+    /// `on` / `off`
+    OnOff,
+    /// `enabled` / `disabled`
+    EnabledDisabled,
+    /// A custom pair of words for `true` and `false`.
+    Custom(&'static str, &'static str),
+}
+
+impl BoolStyle {
+    pub(crate) const fn words(&self) -> (&'static str, &'static str) {
+        match *self {
+            BoolStyle::TrueFalse => ("true", "false"),
+            BoolStyle::YesNo => ("yes", "no"),
+            BoolStyle::OnOff => ("on", "off"),
+            BoolStyle::EnabledDisabled => ("enabled", "disabled"),
+            BoolStyle::Custom(t, f) => (t, f),
+        }
+    }
+}
+
+/// Display a `bool` with the words of a [`BoolStyle`].
+#[must_use]
+pub struct DisplayBool {
+    pub(crate) value: bool,
+    pub(crate) style: BoolStyle,
+}
+
+impl DisplayBool {
+    pub const fn new(value: bool, style: BoolStyle) -> Self {
+        Self { value, style }
+    }
+}
+
+impl fmt::Display for DisplayBool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (t, fa) = self.style.words();
+        f.write_str(if self.value { t } else { fa })
+    }
+}
+
+impl fmt::Debug for DisplayBool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a `bool` as `yes`/`no`, `on`/`off`, `enabled`/`disabled` or a custom pair of words.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::BoolStyle;
+/// use display_more::DisplayBoolExt;
+///
+/// assert_eq!(
+///     true.display_bool(BoolStyle::EnabledDisabled).to_string(),
+///     "enabled"
+/// );
+/// assert_eq!(false.display_bool(BoolStyle::YesNo).to_string(), "no");
+/// ```
+pub trait DisplayBoolExt {
+    fn display_bool(&self, style: BoolStyle) -> DisplayBool;
+}
+
+impl DisplayBoolExt for bool {
+    fn display_bool(&self, style: BoolStyle) -> DisplayBool {
+        DisplayBool::new(*self, style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_bool() {
+        assert_eq!("true", true.display_bool(BoolStyle::TrueFalse).to_string());
+        assert_eq!(
+            "false",
+            false.display_bool(BoolStyle::TrueFalse).to_string()
+        );
+        assert_eq!("yes", true.display_bool(BoolStyle::YesNo).to_string());
+        assert_eq!("no", false.display_bool(BoolStyle::YesNo).to_string());
+        assert_eq!("on", true.display_bool(BoolStyle::OnOff).to_string());
+        assert_eq!("off", false.display_bool(BoolStyle::OnOff).to_string());
+        assert_eq!(
+            "enabled",
+            true.display_bool(BoolStyle::EnabledDisabled).to_string()
+        );
+        assert_eq!(
+            "disabled",
+            false.display_bool(BoolStyle::EnabledDisabled).to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_bool_custom() {
+        let style = BoolStyle::Custom("✓", "✗");
+        assert_eq!("✓", true.display_bool(style).to_string());
+        assert_eq!("✗", false.display_bool(style).to_string());
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use core::fmt;
+
+/// A `Display` value that can be sent to another thread, e.g., an async logging thread.
+pub type BoxedDisplay = Box<dyn fmt::Display + Send + Sync + 'static>;
+
+/// Convert a `Display` value into a [`BoxedDisplay`] without lifetimes.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplaySliceExt;
+/// use display_more::ToBoxedDisplay;
+///
+/// let nodes = vec![1, 2, 3];
+/// let d = nodes.display().to_boxed_display();
+/// drop(nodes);
+///
+/// let s = std::thread::spawn(move || d.to_string()).join().unwrap();
+/// assert_eq!(s, "[1,2,3]");
+/// ```
+pub trait ToBoxedDisplay: fmt::Display {
+    /// Format this value into an owned `String` and box it.
+    ///
+    /// This works for values that borrow, such as the wrappers in this crate.
+    fn to_boxed_display(&self) -> BoxedDisplay {
+        Box::new(self.to_string())
+    }
+
+    /// Box this value as is; it is formatted lazily when the box is displayed.
+    fn into_boxed_display(self) -> BoxedDisplay
+    where Self: Sized + Send + Sync + 'static {
+        Box::new(self)
+    }
+}
+
+impl<T> ToBoxedDisplay for T where T: fmt::Display + ?Sized {}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+    use crate::DisplayOptionExt;
+    use crate::DisplaySliceOwned;
+
+    #[test]
+    fn test_to_boxed_display() {
+        let d = {
+            let opt = Some(String::from("foo"));
+            opt.display().to_boxed_display()
+        };
+        let s = thread::spawn(move || d.to_string()).join().unwrap();
+        assert_eq!("foo", s);
+    }
+
+    #[test]
+    fn test_into_boxed_display() {
+        let d = DisplaySliceOwned::new(vec![1, 2, 3, 4, 5, 6])
+            .at_most(Some(2))
+            .into_boxed_display();
+        let s = thread::spawn(move || format!("nodes: {}", d))
+            .join()
+            .unwrap();
+        assert_eq!("nodes: [1,..,6]", s);
+    }
+}
@@ -12,17 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeSet;
-use std::fmt;
+use alloc::collections::BTreeSet;
+use core::fmt;
 
+use crate::display_iterator_options::DisplayIteratorOptions;
 use crate::DisplayIntoIter;
 
 /// Implement `Display` for `BTreeSet<T>` if `T` is `Display`.
 ///
 /// It outputs at most `limit` elements, excluding those from the 5th to the second-to-last one:
 /// - `DisplayBTreeSet{ set: ... }` outputs: `"[1,2,3,4,..,6]"`.
+#[must_use]
 pub struct DisplayBTreeSet<'a, T: fmt::Display> {
-    inner: DisplayIntoIter<'a, T, &'a BTreeSet<T>>,
+    pub(crate) inner: DisplayIntoIter<'a, T, &'a BTreeSet<T>>,
 }
 
 impl<'a, T: fmt::Display> DisplayBTreeSet<'a, T> {
@@ -32,44 +34,50 @@ impl<'a, T: fmt::Display> DisplayBTreeSet<'a, T> {
         }
     }
 
-    pub fn at_most(mut self, limit: Option<usize>) -> Self {
-        self.inner = self.inner.at_most(limit);
-        self
-    }
+    iterator_options_builders!('a, inner.options);
+}
 
-    pub fn sep(mut self, separator: &'a str) -> Self {
-        self.inner = self.inner.sep(separator);
-        self
+impl<T: fmt::Display> fmt::Display for DisplayBTreeSet<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
     }
+}
 
-    pub fn braces(mut self, left: &'a str, right: &'a str) -> Self {
-        self.inner = self.inner.braces(left, right);
-        self
+impl<T: fmt::Display> fmt::Debug for DisplayBTreeSet<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
     }
+}
 
-    pub fn ellipsis(mut self, s: &'a str) -> Self {
-        self.inner = self.inner.ellipsis(s);
-        self
-    }
+/// Owned counterpart of [`DisplayBTreeSet`] that can be stored in a struct or moved to another thread.
+///
+/// Separators, braces and other strings must be `'static`.
+#[must_use]
+pub struct DisplayBTreeSetOwned<T: fmt::Display> {
+    pub(crate) items: BTreeSet<T>,
+    pub(crate) options: DisplayIteratorOptions<'static>,
+}
 
-    pub fn elem(mut self, prefix: &'a str, suffix: &'a str) -> Self {
-        self.inner = self.inner.elem(prefix, suffix);
-        self
+impl<T: fmt::Display> DisplayBTreeSetOwned<T> {
+    pub const fn new(items: BTreeSet<T>) -> Self {
+        Self {
+            items,
+            options: DisplayIteratorOptions::new(),
+        }
     }
 
-    pub fn show_count(mut self) -> Self {
-        self.inner = self.inner.show_count();
-        self
-    }
+    iterator_options_builders!('static, options);
+}
 
-    pub fn limit(&self) -> usize {
-        self.inner.limit()
+impl<T: fmt::Display> fmt::Display for DisplayBTreeSetOwned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        DisplayIntoIter::with_options(&self.items, self.options).fmt(f)
     }
 }
 
-impl<T: fmt::Display> fmt::Display for DisplayBTreeSet<'_, T> {
+impl<T: fmt::Display> fmt::Debug for DisplayBTreeSetOwned<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.inner.fmt(f)
+        fmt::Display::fmt(self, f)
     }
 }
 
@@ -110,6 +118,7 @@ mod tests {
     use std::collections::BTreeSet;
 
     use super::DisplayBTreeSet;
+    use super::DisplayBTreeSetOwned;
     use crate::DisplayBTreeSetExt;
 
     #[test]
@@ -216,4 +225,12 @@ mod tests {
             DisplayBTreeSet::new(&set).show_count().to_string()
         );
     }
+
+    #[test]
+    fn test_display_btreeset_owned() {
+        let set = (1..=7).collect::<BTreeSet<_>>();
+        let d = DisplayBTreeSetOwned::new(set).at_most(Some(3)).sep("|");
+        let s = std::thread::spawn(move || d.to_string()).join().unwrap();
+        assert_eq!("[1|2|..|7]", s);
+    }
 }
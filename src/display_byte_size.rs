@@ -0,0 +1,153 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+
+/// Display a byte count with IEC units, such as `512 B`, `1.50 MiB` or `3 GiB`.
+///
+/// Exact multiples of a unit are displayed without decimals, otherwise two decimals are shown.
+///
+/// Call [`si`](Self::si) to use SI units based on 1000, such as `1.50 MB`, instead.
+#[must_use]
+pub struct DisplayByteSize {
+    bytes: u64,
+    si: bool,
+}
+
+impl DisplayByteSize {
+    const IEC_UNITS: [&'static str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+    const SI_UNITS: [&'static str; 7] = ["B", "kB", "MB", "GB", "TB", "PB", "EB"];
+
+    pub const fn new(bytes: u64) -> Self {
+        Self { bytes, si: false }
+    }
+
+    /// Use SI units: `kB`, `MB`, ... in multiples of 1000.
+    pub const fn si(self) -> Self {
+        Self { si: true, ..self }
+    }
+
+    /// Use IEC units: `KiB`, `MiB`, ... in multiples of 1024. This is the default.
+    pub const fn iec(self) -> Self {
+        Self { si: false, ..self }
+    }
+}
+
+impl fmt::Display for DisplayByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (base, units) = if self.si {
+            (1000u64, &Self::SI_UNITS)
+        } else {
+            (1024u64, &Self::IEC_UNITS)
+        };
+
+        let mut exp = 0;
+        let mut unit_size = 1u64;
+        while exp < units.len() - 1 && self.bytes / unit_size >= base {
+            exp += 1;
+            unit_size *= base;
+        }
+
+        let (quotient, remainder) = (self.bytes / unit_size, self.bytes % unit_size);
+        if remainder == 0 {
+            return write!(f, "{} {}", quotient, units[exp]);
+        }
+
+        let mut value = self.bytes as f64 / unit_size as f64;
+
+        // Rounding may carry into the next unit, e.g., 1023.999 KiB -> 1024.00 KiB -> 1.00 MiB
+        if (value * 100.0).round() / 100.0 >= base as f64 && exp < units.len() - 1 {
+            exp += 1;
+            value /= base as f64;
+        }
+
+        write!(f, "{:.2} {}", value, units[exp])
+    }
+}
+
+impl fmt::Debug for DisplayByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a byte count in a human-readable unit.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayByteSizeExt;
+///
+/// assert_eq!(1_572_864u64.display_byte_size().to_string(), "1.50 MiB");
+/// assert_eq!((3u64 << 30).display_byte_size().to_string(), "3 GiB");
+/// assert_eq!(1_500_000u64.display_byte_size().si().to_string(), "1.50 MB");
+/// ```
+pub trait DisplayByteSizeExt {
+    fn display_byte_size(&self) -> DisplayByteSize;
+}
+
+impl DisplayByteSizeExt for u64 {
+    fn display_byte_size(&self) -> DisplayByteSize {
+        DisplayByteSize::new(*self)
+    }
+}
+
+impl DisplayByteSizeExt for usize {
+    fn display_byte_size(&self) -> DisplayByteSize {
+        DisplayByteSize::new(*self as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_byte_size() {
+        assert_eq!("0 B", 0u64.display_byte_size().to_string());
+        assert_eq!("1023 B", 1023u64.display_byte_size().to_string());
+        assert_eq!("1 KiB", 1024u64.display_byte_size().to_string());
+        assert_eq!("1.50 KiB", 1536u64.display_byte_size().to_string());
+        assert_eq!("1.50 MiB", 1_572_864u64.display_byte_size().to_string());
+        assert_eq!("3 GiB", (3u64 << 30).display_byte_size().to_string());
+        assert_eq!("1 TiB", (1usize << 40).display_byte_size().to_string());
+        assert_eq!("16.00 EiB", u64::MAX.display_byte_size().to_string());
+    }
+
+    #[test]
+    fn test_display_byte_size_rounding_carry() {
+        assert_eq!(
+            "1.00 MiB",
+            ((1u64 << 20) - 1).display_byte_size().to_string()
+        );
+        assert_eq!("1.01 KiB", 1030u64.display_byte_size().to_string());
+    }
+
+    #[test]
+    fn test_display_byte_size_si() {
+        assert_eq!("999 B", 999u64.display_byte_size().si().to_string());
+        assert_eq!("1 kB", 1000u64.display_byte_size().si().to_string());
+        assert_eq!("1.02 kB", 1024u64.display_byte_size().si().to_string());
+        assert_eq!("1.50 MB", 1_500_000u64.display_byte_size().si().to_string());
+        assert_eq!(
+            "3 GB",
+            3_000_000_000u64.display_byte_size().si().to_string()
+        );
+        assert_eq!("18.45 EB", u64::MAX.display_byte_size().si().to_string());
+        assert_eq!("1.00 MB", 999_999u64.display_byte_size().si().to_string());
+
+        // Toggle back
+        assert_eq!("1 KiB", 1024u64.display_byte_size().si().iec().to_string());
+    }
+}
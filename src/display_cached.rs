@@ -0,0 +1,107 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::string::String;
+use alloc::string::ToString;
+use core::fmt;
+use std::sync::OnceLock;
+
+/// Format the inner `Display` value once and replay the stored string on subsequent writes.
+///
+/// Useful for values that are formatted many times, such as a node descriptor attached to every
+/// log line. The inner value is formatted with default options, i.e., as `to_string()` would.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::Cached;
+/// use display_more::DisplaySliceExt;
+///
+/// let nodes = [1, 2, 3];
+/// let c = Cached::new(nodes.display());
+/// assert_eq!(c.to_string(), "[1,2,3]");
+/// assert_eq!(c.to_string(), "[1,2,3]");
+/// ```
+#[must_use]
+pub struct Cached<D> {
+    inner: D,
+    cache: OnceLock<String>,
+}
+
+impl<D: fmt::Display> Cached<D> {
+    pub const fn new(inner: D) -> Self {
+        Self {
+            inner,
+            cache: OnceLock::new(),
+        }
+    }
+
+    /// Return the formatted string, formatting the inner value if it has not been done yet.
+    pub fn as_str(&self) -> &str {
+        self.cache.get_or_init(|| self.inner.to_string())
+    }
+
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+
+impl<D: fmt::Display> fmt::Display for Cached<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<D: fmt::Display> fmt::Debug for Cached<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::fmt;
+
+    use super::Cached;
+
+    struct Counting<'a>(&'a Cell<usize>);
+
+    impl fmt::Display for Counting<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.set(self.0.get() + 1);
+            write!(f, "node-{}", 3)
+        }
+    }
+
+    #[test]
+    fn test_cached() {
+        let count = Cell::new(0);
+        let c = Cached::new(Counting(&count));
+        assert_eq!(0, count.get());
+
+        assert_eq!("node-3", c.to_string());
+        assert_eq!("node-3", c.to_string());
+        assert_eq!("[node-3]", format!("[{}]", c));
+        assert_eq!("node-3", c.as_str());
+        assert_eq!(1, count.get());
+    }
+
+    #[test]
+    fn test_cached_into_inner() {
+        let c = Cached::new(5);
+        assert_eq!("5", c.to_string());
+        assert_eq!(5, c.into_inner());
+    }
+}
@@ -0,0 +1,254 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::cell::Cell;
+use core::cell::OnceCell;
+use core::cell::RefCell;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+/// Display the value in a `RefCell` if it is not mutably borrowed, otherwise `<borrowed>`.
+#[must_use]
+pub struct DisplayRefCell<'a, T: ?Sized> {
+    inner: &'a RefCell<T>,
+}
+
+impl<'a, T: ?Sized> DisplayRefCell<'a, T> {
+    pub const fn new(inner: &'a RefCell<T>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T: fmt::Display + ?Sized> fmt::Display for DisplayRefCell<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.inner.try_borrow() {
+            Ok(v) => write!(f, "{}", &*v),
+            Err(_) => write!(f, "<borrowed>"),
+        }
+    }
+}
+
+impl<T: fmt::Display + ?Sized> fmt::Debug for DisplayRefCell<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a copy of the value in a `Cell`, taken when formatting.
+#[must_use]
+pub struct DisplayCell<'a, T> {
+    inner: &'a Cell<T>,
+}
+
+impl<'a, T> DisplayCell<'a, T> {
+    pub const fn new(inner: &'a Cell<T>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T: fmt::Display + Copy> fmt::Display for DisplayCell<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.inner.get())
+    }
+}
+
+impl<T: fmt::Display + Copy> fmt::Debug for DisplayCell<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display the value in a `OnceCell` or `OnceLock` if it is initialized when formatting,
+/// otherwise `<uninit>`.
+///
+/// `LazyLock` and `LazyCell` are not supported: stable Rust provides no way to inspect them
+/// without forcing the initialization. Use a `OnceLock` with `get_or_init()` instead if the
+/// value needs to be displayed.
+#[must_use]
+pub struct DisplayOnceCell<'a, T> {
+    inner: Once<'a, T>,
+}
+
+enum Once<'a, T> {
+    Cell(&'a OnceCell<T>),
+    #[cfg(feature = "std")]
+    Lock(&'a OnceLock<T>),
+}
+
+impl<'a, T> DisplayOnceCell<'a, T> {
+    pub const fn new(inner: &'a OnceCell<T>) -> Self {
+        Self {
+            inner: Once::Cell(inner),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub const fn new_lock(inner: &'a OnceLock<T>) -> Self {
+        Self {
+            inner: Once::Lock(inner),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for DisplayOnceCell<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self.inner {
+            Once::Cell(c) => c.get(),
+            #[cfg(feature = "std")]
+            Once::Lock(l) => l.get(),
+        };
+        match value {
+            Some(v) => write!(f, "{}", v),
+            None => write!(f, "<uninit>"),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Debug for DisplayOnceCell<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display the value in a `RefCell` without panicking if it is mutably borrowed.
+///
+/// # Example
+///
+/// ```rust
+/// use std::cell::RefCell;
+///
+/// use display_more::DisplayRefCellExt;
+///
+/// let c = RefCell::new(5);
+/// assert_eq!(c.display().to_string(), "5");
+///
+/// let _b = c.borrow_mut();
+/// assert_eq!(c.display().to_string(), "<borrowed>");
+/// ```
+pub trait DisplayRefCellExt<T: ?Sized> {
+    fn display(&self) -> DisplayRefCell<'_, T>;
+}
+
+impl<T: ?Sized> DisplayRefCellExt<T> for RefCell<T> {
+    fn display(&self) -> DisplayRefCell<'_, T> {
+        DisplayRefCell::new(self)
+    }
+}
+
+/// Display the value in a `Cell`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::cell::Cell;
+///
+/// use display_more::DisplayCellExt;
+///
+/// let c = Cell::new(5);
+/// c.set(6);
+/// assert_eq!(c.display().to_string(), "6");
+/// ```
+pub trait DisplayCellExt<T> {
+    fn display(&self) -> DisplayCell<'_, T>;
+}
+
+impl<T> DisplayCellExt<T> for Cell<T> {
+    fn display(&self) -> DisplayCell<'_, T> {
+        DisplayCell::new(self)
+    }
+}
+
+/// Display the value in a `OnceCell` or `OnceLock`, or `<uninit>` if it is not initialized yet.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::OnceLock;
+///
+/// use display_more::DisplayOnceCellExt;
+///
+/// let c = OnceLock::new();
+/// assert_eq!(c.display().to_string(), "<uninit>");
+///
+/// c.set(5).unwrap();
+/// assert_eq!(c.display().to_string(), "5");
+/// ```
+pub trait DisplayOnceCellExt<T> {
+    fn display(&self) -> DisplayOnceCell<'_, T>;
+}
+
+impl<T> DisplayOnceCellExt<T> for OnceCell<T> {
+    fn display(&self) -> DisplayOnceCell<'_, T> {
+        DisplayOnceCell::new(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> DisplayOnceCellExt<T> for OnceLock<T> {
+    fn display(&self) -> DisplayOnceCell<'_, T> {
+        DisplayOnceCell::new_lock(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_display_ref_cell() {
+        let c = RefCell::new("a");
+        assert_eq!("a", c.display().to_string());
+
+        {
+            let _r = c.borrow();
+            assert_eq!("a", c.display().to_string());
+        }
+        {
+            let _w = c.borrow_mut();
+            assert_eq!("<borrowed>", c.display().to_string());
+        }
+    }
+
+    #[test]
+    fn test_display_cell() {
+        let c = Cell::new(1);
+        let d = c.display();
+        c.set(2);
+        assert_eq!("2", d.to_string());
+    }
+
+    #[test]
+    fn test_display_once_cell() {
+        let c = OnceCell::new();
+        let d = c.display();
+        assert_eq!("<uninit>", d.to_string());
+        c.set("a").unwrap();
+        assert_eq!("a", d.to_string());
+        assert_eq!("a", c.display().to_string());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_display_once_lock() {
+        static LOCK: OnceLock<u64> = OnceLock::new();
+        let d = LOCK.display();
+        assert_eq!("<uninit>", d.to_string());
+        LOCK.get_or_init(|| 3);
+        assert_eq!("3", d.to_string());
+        assert_eq!("3", LOCK.display().to_string());
+    }
+}
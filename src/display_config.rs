@@ -0,0 +1,262 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "std")]
+use core::cell::Cell;
+#[cfg(feature = "std")]
+use core::marker::PhantomData;
+#[cfg(feature = "std")]
+use core::ptr;
+#[cfg(feature = "std")]
+use core::sync::atomic::AtomicPtr;
+#[cfg(feature = "std")]
+use core::sync::atomic::Ordering;
+
+/// House-style defaults used by the wrappers when a call site does not specify them.
+///
+/// The process-wide config is set with [`DisplayConfig::set_global`]. A thread can override it
+/// for a scope with [`DisplayConfig::scoped`].
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayConfig;
+/// use display_more::DisplayOptionExt;
+/// use display_more::DisplaySliceExt;
+///
+/// let a = [1, 2, 3, 4, 5, 6, 7];
+/// assert_eq!(a.display().to_string(), "[1,2,3,4,..,7]");
+///
+/// {
+///     let _guard = DisplayConfig::current()
+///         .slice_limit(3)
+///         .separator(", ")
+///         .none("-")
+///         .scoped();
+///
+///     assert_eq!(a.display().to_string(), "[1, 2, .., 7]");
+///     assert_eq!(None::<u64>.display().to_string(), "-");
+/// }
+///
+/// assert_eq!(a.display().to_string(), "[1,2,3,4,..,7]");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayConfig {
+    pub(crate) slice_limit: usize,
+    pub(crate) separator: &'static str,
+    pub(crate) none: &'static str,
+    pub(crate) timestamp_precision: usize,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[cfg(feature = "std")]
+static DEFAULT: DisplayConfig = DisplayConfig::DEFAULT;
+
+/// The process-wide config, read with a single atomic load and never freed once set.
+#[cfg(feature = "std")]
+static GLOBAL: AtomicPtr<DisplayConfig> = AtomicPtr::new(ptr::addr_of!(DEFAULT).cast_mut());
+
+#[cfg(feature = "std")]
+thread_local! {
+    static SCOPED: Cell<Option<DisplayConfig>> = const { Cell::new(None) };
+}
+
+impl DisplayConfig {
+    /// The built-in defaults.
+    pub const DEFAULT: Self = Self {
+        slice_limit: 5,
+        separator: ",",
+        none: "None",
+        timestamp_precision: 6,
+    };
+
+    /// Set the number of elements a slice or set displays by default. The built-in default is 5.
+    pub const fn slice_limit(self, slice_limit: usize) -> Self {
+        Self {
+            slice_limit,
+            ..self
+        }
+    }
+
+    /// Set the default separator between the elements of a slice or set. The built-in default
+    /// is `","`.
+    pub const fn separator(self, separator: &'static str) -> Self {
+        Self { separator, ..self }
+    }
+
+    /// Set the placeholder displayed for `None`. The built-in default is `"None"`.
+    pub const fn none(self, none: &'static str) -> Self {
+        Self { none, ..self }
+    }
+
+    /// Set the number of fractional second digits of a timestamp, at most 9. It does not affect
+    /// timestamps displayed in milliseconds. The built-in default is 6.
+    pub const fn timestamp_precision(self, timestamp_precision: usize) -> Self {
+        Self {
+            timestamp_precision: if timestamp_precision > 9 {
+                9
+            } else {
+                timestamp_precision
+            },
+            ..self
+        }
+    }
+
+    /// Return the config in effect for the current thread.
+    ///
+    /// Without the `std` feature it is always [`DisplayConfig::DEFAULT`].
+    #[cfg(not(feature = "std"))]
+    pub fn current() -> Self {
+        Self::DEFAULT
+    }
+
+    /// Return the config in effect for the current thread.
+    ///
+    /// It takes no lock: a thread-local lookup and at most one atomic load.
+    #[cfg(feature = "std")]
+    pub fn current() -> Self {
+        SCOPED.with(|c| c.get()).unwrap_or_else(Self::global)
+    }
+
+    /// Set the process-wide config.
+    ///
+    /// It is meant to be called at start-up: every config set stays allocated for the rest of
+    /// the process, so that [`DisplayConfig::current`] can read it without a lock.
+    #[cfg(feature = "std")]
+    pub fn set_global(self) {
+        if self == Self::global() {
+            return;
+        }
+        let config: &'static Self = Box::leak(Box::new(self));
+        GLOBAL.store(ptr::from_ref(config).cast_mut(), Ordering::Release);
+    }
+
+    #[cfg(feature = "std")]
+    fn global() -> Self {
+        // SAFETY: `GLOBAL` only points to `DEFAULT` or to a config leaked by `set_global()`, both
+        // of which live for the rest of the process and are never mutated.
+        unsafe { *GLOBAL.load(Ordering::Acquire) }
+    }
+
+    /// Use this config on the current thread until the returned guard is dropped.
+    #[cfg(feature = "std")]
+    pub fn scoped(self) -> ConfigGuard {
+        let prev = SCOPED.with(|c| c.replace(Some(self)));
+        ConfigGuard {
+            prev,
+            _not_send: PhantomData,
+        }
+    }
+}
+
+/// Restores the previous config of the thread when dropped. See [`DisplayConfig::scoped`].
+#[cfg(feature = "std")]
+#[must_use = "the config is restored as soon as the guard is dropped"]
+pub struct ConfigGuard {
+    prev: Option<DisplayConfig>,
+    /// The guard restores a thread-local, thus it must be dropped on the same thread.
+    _not_send: PhantomData<*const ()>,
+}
+
+#[cfg(feature = "std")]
+impl Drop for ConfigGuard {
+    fn drop(&mut self) {
+        SCOPED.with(|c| c.set(self.prev));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::DisplayBTreeSetExt;
+    use crate::DisplayOptionExt;
+    use crate::DisplaySliceExt;
+    use crate::DisplayUnixTimeStampExt;
+
+    #[test]
+    fn test_config_scoped() {
+        let a = [1, 2, 3, 4, 5, 6, 7];
+
+        {
+            let _g = DisplayConfig::DEFAULT.slice_limit(2).scoped();
+            assert_eq!("[1,..,7]", a.display().to_string());
+            assert_eq!(
+                "[1,..,7]",
+                a.iter()
+                    .copied()
+                    .collect::<std::collections::BTreeSet<_>>()
+                    .display()
+                    .to_string()
+            );
+
+            {
+                let _g = DisplayConfig::current().separator("|").scoped();
+                assert_eq!("[1|..|7]", a.display().to_string());
+            }
+
+            assert_eq!("[1,..,7]", a.display().to_string());
+
+            // Explicit options take precedence
+            assert_eq!("[1;2;3;..;7]", a.display_n(4).sep(";").to_string());
+
+            let _g = DisplayConfig::current().separator("|").scoped();
+            assert_eq!("[1|..|7]", a.display().fit_width(8).to_string());
+            assert_eq!("[..|7]", a.display().fit_width(7).to_string());
+        }
+
+        assert_eq!("[1,2,3,4,..,7]", a.display().to_string());
+        assert_eq!(DisplayConfig::DEFAULT, DisplayConfig::default());
+    }
+
+    #[test]
+    fn test_config_none_and_timestamp() {
+        let t = Duration::from_nanos(1_723_102_819_023_456_789);
+
+        let _g = DisplayConfig::DEFAULT
+            .none("<null>")
+            .timestamp_precision(9)
+            .scoped();
+
+        assert_eq!("<null>", None::<u64>.display().to_string());
+        assert_eq!(
+            "<null>",
+            None::<Duration>.display_unix_timestamp().to_string()
+        );
+        assert_eq!(
+            "2024-08-08T07:40:19.023456789Z+0000",
+            t.display_unix_timestamp().to_string()
+        );
+        assert_eq!(
+            "2024-08-08T07:40:19.023",
+            t.display_unix_timestamp_short().to_string()
+        );
+
+        let _g = DisplayConfig::current().timestamp_precision(0).scoped();
+        assert_eq!(
+            "2024-08-08T07:40:19Z+0000",
+            t.display_unix_timestamp().to_string()
+        );
+        assert_eq!(
+            "2024-08-08T07:40:19",
+            t.display_unix_timestamp().with_timezone(false).to_string()
+        );
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+
+/// Display a count with the singular or plural form of a noun, such as `1 entry` or `42 entries`.
+///
+/// If no plural form is given, it is the singular form followed by `s`.
+#[must_use]
+pub struct DisplayCount<'a, N: fmt::Display> {
+    count: N,
+    is_one: bool,
+    singular: &'a str,
+    plural: Option<&'a str>,
+}
+
+impl<'a, N: fmt::Display> DisplayCount<'a, N> {
+    pub const fn new(count: N, is_one: bool, singular: &'a str, plural: Option<&'a str>) -> Self {
+        Self {
+            count,
+            is_one,
+            singular,
+            plural,
+        }
+    }
+}
+
+impl<N: fmt::Display> fmt::Display for DisplayCount<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_one {
+            return write!(f, "{} {}", self.count, self.singular);
+        }
+
+        match self.plural {
+            Some(plural) => write!(f, "{} {}", self.count, plural),
+            None => write!(f, "{} {}s", self.count, self.singular),
+        }
+    }
+}
+
+impl<N: fmt::Display> fmt::Debug for DisplayCount<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a count followed by a correctly pluralized noun.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayCountExt;
+///
+/// assert_eq!(1.display_count("entry", "entries").to_string(), "1 entry");
+/// assert_eq!(
+///     42.display_count("entry", "entries").to_string(),
+///     "42 entries"
+/// );
+/// assert_eq!(3.display_count_s("file").to_string(), "3 files");
+/// ```
+pub trait DisplayCountExt: fmt::Display + Sized {
+    /// Display with `singular` if the count is 1, otherwise with `plural`.
+    fn display_count<'a>(&self, singular: &'a str, plural: &'a str) -> DisplayCount<'a, Self>;
+
+    /// Display with `singular` if the count is 1, otherwise with `singular` followed by `s`.
+    fn display_count_s<'a>(&self, singular: &'a str) -> DisplayCount<'a, Self>;
+}
+
+macro_rules! impl_count {
+    ($($t:ty),*) => {
+        $(
+            impl DisplayCountExt for $t {
+                fn display_count<'a>(&self, singular: &'a str, plural: &'a str) -> DisplayCount<'a, Self> {
+                    DisplayCount::new(*self, *self == 1, singular, Some(plural))
+                }
+
+                fn display_count_s<'a>(&self, singular: &'a str) -> DisplayCount<'a, Self> {
+                    DisplayCount::new(*self, *self == 1, singular, None)
+                }
+            }
+        )*
+    };
+}
+
+impl_count!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_count() {
+        assert_eq!("0 entries", 0.display_count("entry", "entries").to_string());
+        assert_eq!(
+            "1 entry",
+            1u64.display_count("entry", "entries").to_string()
+        );
+        assert_eq!(
+            "2 entries",
+            2usize.display_count("entry", "entries").to_string()
+        );
+        assert_eq!(
+            "-1 entries",
+            (-1).display_count("entry", "entries").to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_count_s() {
+        assert_eq!("0 files", 0.display_count_s("file").to_string());
+        assert_eq!("1 file", 1.display_count_s("file").to_string());
+        assert_eq!("5 files", 5u8.display_count_s("file").to_string());
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+
+/// Display a change with an explicit sign: `+5`, `-3` or `±0`.
+#[must_use]
+pub struct DisplayDelta<N: fmt::Display> {
+    sign: Option<char>,
+    abs: N,
+}
+
+impl<N: fmt::Display> DisplayDelta<N> {
+    /// Create a delta from its sign char and absolute value.
+    ///
+    /// `sign` is `None` for values without a sign, such as `NaN`.
+    pub const fn new(sign: Option<char>, abs: N) -> Self {
+        Self { sign, abs }
+    }
+}
+
+impl<N: fmt::Display> fmt::Display for DisplayDelta<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(sign) = self.sign {
+            write!(f, "{}", sign)?;
+        }
+        write!(f, "{}", self.abs)
+    }
+}
+
+impl<N: fmt::Display> fmt::Debug for DisplayDelta<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+fn sign_char(positive: bool, negative: bool) -> Option<char> {
+    if positive {
+        Some('+')
+    } else if negative {
+        Some('-')
+    } else {
+        Some('±')
+    }
+}
+
+/// Display a number that represents a change, always with a sign.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayDeltaExt;
+///
+/// assert_eq!(5.display_delta().to_string(), "+5");
+/// assert_eq!((-3).display_delta().to_string(), "-3");
+/// assert_eq!(0.display_delta().to_string(), "±0");
+/// assert_eq!(1.5.display_delta().to_string(), "+1.5");
+/// ```
+pub trait DisplayDeltaExt {
+    type Abs: fmt::Display;
+
+    fn display_delta(&self) -> DisplayDelta<Self::Abs>;
+}
+
+macro_rules! impl_delta_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl DisplayDeltaExt for $t {
+                type Abs = $t;
+
+                fn display_delta(&self) -> DisplayDelta<$t> {
+                    DisplayDelta::new(sign_char(*self > 0, false), *self)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_delta_signed {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl DisplayDeltaExt for $t {
+                type Abs = $u;
+
+                fn display_delta(&self) -> DisplayDelta<$u> {
+                    DisplayDelta::new(sign_char(*self > 0, *self < 0), self.unsigned_abs())
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_delta_float {
+    ($($t:ty),*) => {
+        $(
+            impl DisplayDeltaExt for $t {
+                type Abs = $t;
+
+                fn display_delta(&self) -> DisplayDelta<$t> {
+                    let sign = if self.is_nan() {
+                        None
+                    } else {
+                        sign_char(*self > 0.0, *self < 0.0)
+                    };
+                    DisplayDelta::new(sign, self.abs())
+                }
+            }
+        )*
+    };
+}
+
+impl_delta_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_delta_signed!(
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize
+);
+impl_delta_float!(f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_delta_int() {
+        assert_eq!("+5", 5.display_delta().to_string());
+        assert_eq!("-3", (-3i64).display_delta().to_string());
+        assert_eq!("±0", 0i32.display_delta().to_string());
+        assert_eq!("+7", 7u64.display_delta().to_string());
+        assert_eq!("±0", 0u8.display_delta().to_string());
+        assert_eq!("-128", i8::MIN.display_delta().to_string());
+    }
+
+    #[test]
+    fn test_display_delta_float() {
+        assert_eq!("+1.5", 1.5.display_delta().to_string());
+        assert_eq!("-0.25", (-0.25f32).display_delta().to_string());
+        assert_eq!("±0", 0.0.display_delta().to_string());
+        assert_eq!("±0", (-0.0).display_delta().to_string());
+        assert_eq!("+inf", f64::INFINITY.display_delta().to_string());
+        assert_eq!("NaN", f64::NAN.display_delta().to_string());
+    }
+}
@@ -0,0 +1,409 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::time::Duration;
+
+use crate::NonFinite;
+
+/// Write `nanos` nanoseconds in the largest unit in which the value is at least 1, with at most
+/// one decimal, such as `1.2ms`, `4ms` or `1.3s`.
+pub(crate) fn fmt_human_nanos(f: &mut fmt::Formatter<'_>, nanos: u128) -> fmt::Result {
+    const UNITS: [(u128, &str); 4] = [
+        (1_000_000_000, "s"),
+        (1_000_000, "ms"),
+        (1_000, "µs"),
+        (1, "ns"),
+    ];
+
+    for (div, unit) in UNITS {
+        // The value in tenths of the unit, rounded half up.
+        let tenths = nanos.saturating_mul(10).saturating_add(div / 2) / div;
+        if tenths >= 10 || div == 1 {
+            let (int, frac) = (tenths / 10, tenths % 10);
+            write!(f, "{int}")?;
+            if frac > 0 {
+                write!(f, ".{frac}")?;
+            }
+            return f.write_str(unit);
+        }
+    }
+    unreachable!("the last unit accepts any value")
+}
+
+/// Write a `Duration` in human units, see [`fmt_human_nanos`].
+pub(crate) fn fmt_human(f: &mut fmt::Formatter<'_>, duration: Duration) -> fmt::Result {
+    fmt_human_nanos(f, duration.as_nanos())
+}
+
+/// Display a `Duration` in the ISO 8601 duration format, such as `PT1H2M3.5S`.
+///
+/// Hours are not carried into days, since a day is not always 24 hours in ISO 8601. Zero
+/// components are omitted, and a zero duration is displayed as `PT0S`. Fractional seconds are
+/// displayed with up to 9 digits, without trailing zeros.
+#[must_use]
+pub struct DisplayIso8601Duration {
+    duration: Duration,
+}
+
+impl DisplayIso8601Duration {
+    pub const fn new(duration: Duration) -> Self {
+        Self { duration }
+    }
+}
+
+impl fmt::Display for DisplayIso8601Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.duration.as_secs();
+        let nanos = self.duration.subsec_nanos();
+        let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+
+        f.write_str("PT")?;
+        if h > 0 {
+            write!(f, "{h}H")?;
+        }
+        if m > 0 {
+            write!(f, "{m}M")?;
+        }
+
+        if s > 0 || nanos > 0 || secs == 0 {
+            write!(f, "{s}")?;
+            if nanos > 0 {
+                let mut buf = [b'0'; 9];
+                let mut n = nanos;
+                for b in buf.iter_mut().rev() {
+                    *b = b'0' + (n % 10) as u8;
+                    n /= 10;
+                }
+                let digits = core::str::from_utf8(&buf).unwrap().trim_end_matches('0');
+                write!(f, ".{digits}")?;
+            }
+            f.write_str("S")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for DisplayIso8601Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a `Duration` in a standard machine-parseable format.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use display_more::DisplayDurationExt;
+///
+/// assert_eq!(
+///     Duration::from_millis(3_723_500)
+///         .display_iso8601()
+///         .to_string(),
+///     "PT1H2M3.5S"
+/// );
+/// assert_eq!(Duration::ZERO.display_iso8601().to_string(), "PT0S");
+/// ```
+pub trait DisplayDurationExt {
+    fn display_iso8601(&self) -> DisplayIso8601Duration;
+}
+
+impl DisplayDurationExt for Duration {
+    fn display_iso8601(&self) -> DisplayIso8601Duration {
+        DisplayIso8601Duration::new(*self)
+    }
+}
+
+/// Display the latency summary of a set of durations, such as
+/// `n=500 min=1.2ms p50=4ms p99=80ms max=1.3s`.
+///
+/// Percentiles are computed by the nearest-rank method. An empty set is displayed as `n=0`.
+#[must_use]
+pub struct DisplayDurationStats {
+    /// The durations, sorted.
+    sorted: Vec<Duration>,
+}
+
+impl DisplayDurationStats {
+    pub fn new(durations: &[Duration]) -> Self {
+        let mut sorted = durations.to_vec();
+        sorted.sort_unstable();
+        Self { sorted }
+    }
+
+    /// Return the smallest duration that is greater than or equal to `p` percent of the durations.
+    fn percentile(&self, p: usize) -> Duration {
+        let n = self.sorted.len();
+        let rank = (p * n).div_ceil(100).max(1);
+        self.sorted[rank - 1]
+    }
+}
+
+impl fmt::Display for DisplayDurationStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = self.sorted.len();
+        write!(f, "n={n}")?;
+
+        let (Some(min), Some(max)) = (self.sorted.first(), self.sorted.last()) else {
+            return Ok(());
+        };
+
+        f.write_str(" min=")?;
+        fmt_human(f, *min)?;
+        f.write_str(" p50=")?;
+        fmt_human(f, self.percentile(50))?;
+        f.write_str(" p99=")?;
+        fmt_human(f, self.percentile(99))?;
+        f.write_str(" max=")?;
+        fmt_human(f, *max)
+    }
+}
+
+impl fmt::Debug for DisplayDurationStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display the latency summary of a slice of durations.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use display_more::DisplayDurationStatsExt;
+///
+/// let latencies = [4, 2, 80, 3, 1300].map(Duration::from_millis);
+/// assert_eq!(
+///     latencies.display_duration_stats().to_string(),
+///     "n=5 min=2ms p50=4ms p99=1.3s max=1.3s"
+/// );
+/// ```
+pub trait DisplayDurationStatsExt {
+    fn display_duration_stats(&self) -> DisplayDurationStats;
+}
+
+impl DisplayDurationStatsExt for [Duration] {
+    fn display_duration_stats(&self) -> DisplayDurationStats {
+        DisplayDurationStats::new(self)
+    }
+}
+
+/// Display a float number of seconds as a duration in human units, such as `1.5s` or `1.5ms`.
+///
+/// The value is rounded to nanoseconds, then displayed in the largest unit in which it is at
+/// least 1, with at most one decimal. A negative value is prefixed with `-`.
+///
+/// A value of `u64::MAX` seconds or more, beyond what a `Duration` holds, is displayed in
+/// seconds in scientific notation, such as `1e40s`.
+#[must_use]
+pub struct DisplaySecs {
+    secs: f64,
+    non_finite: NonFinite,
+}
+
+impl DisplaySecs {
+    pub const fn new(secs: f64) -> Self {
+        Self {
+            secs,
+            non_finite: NonFinite::Std,
+        }
+    }
+
+    /// Set how `NaN` and infinities are displayed.
+    pub const fn non_finite(self, non_finite: NonFinite) -> Self {
+        Self { non_finite, ..self }
+    }
+}
+
+impl fmt::Display for DisplaySecs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.secs.is_finite() {
+            return self.non_finite.write(f, self.secs);
+        }
+
+        let negative = self.secs < 0.0;
+        let abs = if negative { -self.secs } else { self.secs };
+
+        if abs >= u64::MAX as f64 {
+            return write!(f, "{:e}s", self.secs);
+        }
+
+        // Convert the whole seconds exactly, and round the fraction half up to nanoseconds.
+        let whole = abs as u64;
+        let frac_nanos = ((abs - whole as f64) * 1e9 + 0.5) as u128;
+        let nanos = whole as u128 * 1_000_000_000 + frac_nanos;
+        if negative && nanos > 0 {
+            f.write_str("-")?;
+        }
+        fmt_human_nanos(f, nanos)
+    }
+}
+
+impl fmt::Debug for DisplaySecs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a float amount of time, as returned by many metrics APIs, as a duration in human
+/// units.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplaySecsExt;
+///
+/// assert_eq!(1.5f64.display_secs().to_string(), "1.5s");
+/// assert_eq!(0.0015f64.display_secs().to_string(), "1.5ms");
+/// assert_eq!(1500f64.display_millis().to_string(), "1.5s");
+/// assert_eq!(250f64.display_micros().to_string(), "250µs");
+/// ```
+pub trait DisplaySecsExt {
+    /// Display a number of seconds.
+    fn display_secs(&self) -> DisplaySecs;
+
+    /// Display a number of milliseconds.
+    fn display_millis(&self) -> DisplaySecs;
+
+    /// Display a number of microseconds.
+    fn display_micros(&self) -> DisplaySecs;
+}
+
+impl DisplaySecsExt for f64 {
+    fn display_secs(&self) -> DisplaySecs {
+        DisplaySecs::new(*self)
+    }
+
+    fn display_millis(&self) -> DisplaySecs {
+        DisplaySecs::new(*self / 1e3)
+    }
+
+    fn display_micros(&self) -> DisplaySecs {
+        DisplaySecs::new(*self / 1e6)
+    }
+}
+
+impl DisplaySecsExt for f32 {
+    fn display_secs(&self) -> DisplaySecs {
+        (*self as f64).display_secs()
+    }
+
+    fn display_millis(&self) -> DisplaySecs {
+        (*self as f64).display_millis()
+    }
+
+    fn display_micros(&self) -> DisplaySecs {
+        (*self as f64).display_micros()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_display_iso8601() {
+        let d = |secs, nanos| Duration::new(secs, nanos).display_iso8601().to_string();
+        assert_eq!("PT0S", d(0, 0));
+        assert_eq!("PT0.000000001S", d(0, 1));
+        assert_eq!("PT0.25S", d(0, 250_000_000));
+        assert_eq!("PT59S", d(59, 0));
+        assert_eq!("PT1M", d(60, 0));
+        assert_eq!("PT1H", d(3600, 0));
+        assert_eq!("PT1H1S", d(3601, 0));
+        assert_eq!("PT25H0.1S", d(90_000, 100_000_000));
+        assert_eq!("PT1H2M3.5S", d(3723, 500_000_000));
+    }
+
+    struct Human(u128);
+
+    impl fmt::Display for Human {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt_human_nanos(f, self.0)
+        }
+    }
+
+    #[test]
+    fn test_fmt_human_nanos() {
+        let h = |nanos| Human(nanos).to_string();
+        assert_eq!("0ns", h(0));
+        assert_eq!("949ns", h(949));
+        assert_eq!("1µs", h(999));
+        assert_eq!("1µs", h(1_000));
+        assert_eq!("1.5µs", h(1_549));
+        assert_eq!("1.2ms", h(1_234_567));
+        assert_eq!("4ms", h(4_000_000));
+        assert_eq!("182.4ms", h(182_400_000));
+        assert_eq!("1s", h(999_960_000));
+        assert_eq!("1.3s", h(1_300_000_000));
+        assert_eq!("3600s", h(3_600_000_000_000));
+    }
+
+    #[test]
+    fn test_display_duration_stats() {
+        let stats = |ms: &[u64]| {
+            let durations = ms
+                .iter()
+                .map(|x| Duration::from_micros(*x))
+                .collect::<Vec<_>>();
+            durations.display_duration_stats().to_string()
+        };
+
+        assert_eq!("n=0", stats(&[]));
+        assert_eq!("n=1 min=5µs p50=5µs p99=5µs max=5µs", stats(&[5]));
+
+        let mut v = (1..=500).collect::<Vec<_>>();
+        v.reverse();
+        assert_eq!("n=500 min=1µs p50=250µs p99=495µs max=500µs", stats(&v));
+    }
+
+    #[test]
+    fn test_display_secs() {
+        assert_eq!("1.5s", 1.5f64.display_secs().to_string());
+        assert_eq!("0ns", 0f64.display_secs().to_string());
+        assert_eq!("0ns", (-1e-12f64).display_secs().to_string());
+        assert_eq!("-182.4ms", (-0.1824f64).display_secs().to_string());
+        assert_eq!("1ns", 1e-9f64.display_secs().to_string());
+        assert_eq!("1.5ms", 1500f64.display_micros().to_string());
+        assert_eq!("1.5ms", 1.5f64.display_millis().to_string());
+        assert_eq!("2.5s", 2.5f32.display_secs().to_string());
+        assert_eq!("NaN", f64::NAN.display_secs().to_string());
+        assert_eq!(
+            "-",
+            f64::INFINITY
+                .display_secs()
+                .non_finite(NonFinite::Dash)
+                .to_string()
+        );
+        assert_eq!("10000000000000000000s", 1e19f64.display_secs().to_string());
+        assert_eq!("1s", 0.9999999999f64.display_secs().to_string());
+
+        // Beyond the range of `Duration`
+        assert_eq!("1e40s", 1e40f64.display_secs().to_string());
+        assert_eq!("-1.5e20s", (-1.5e20f64).display_secs().to_string());
+        assert_eq!("1e22s", 1e25f64.display_millis().to_string());
+        assert_eq!(
+            "1.7976931348623157e308s",
+            f64::MAX.display_secs().to_string()
+        );
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The default patterns of the names of variables whose values are masked.
+pub const DEFAULT_SECRET_PATTERNS: &[&str] = &[
+    "*TOKEN*",
+    "*SECRET*",
+    "*PASSWORD*",
+    "*PASSWD*",
+    "*CREDENTIAL*",
+    "*PRIVATE_KEY*",
+    "*API_KEY*",
+];
+
+/// Return whether `text` matches `pattern`, where `*` matches any sequence of chars.
+///
+/// ASCII letters are compared case-insensitively.
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let p = pattern.as_bytes();
+    let t = text.as_bytes();
+
+    let (mut pi, mut ti) = (0, 0);
+    // The position of the last `*` in the pattern and the text position it is tried at.
+    let mut star: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        if pi < p.len() && p[pi] == b'*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if pi < p.len() && p[pi].eq_ignore_ascii_case(&t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if let Some((sp, st)) = star {
+            pi = sp + 1;
+            ti = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+
+    p[pi..].iter().all(|b| *b == b'*')
+}
+
+/// Display environment variables as `NAME=value` lines sorted by name, with the values of
+/// secret-looking variables masked as `***`.
+///
+/// A value is masked if the name matches one of the patterns, case-insensitively, where `*`
+/// matches any sequence of chars. The default patterns are [`DEFAULT_SECRET_PATTERNS`].
+#[must_use]
+pub struct DisplayEnv<'a, K, V> {
+    vars: Vec<(K, V)>,
+    patterns: Vec<&'a str>,
+}
+
+impl<'a, K: AsRef<str>, V: AsRef<str>> DisplayEnv<'a, K, V> {
+    pub fn new(vars: impl IntoIterator<Item = (K, V)>) -> Self {
+        let mut vars: Vec<_> = vars.into_iter().collect();
+        vars.sort_by(|a, b| a.0.as_ref().cmp(b.0.as_ref()));
+        Self {
+            vars,
+            patterns: DEFAULT_SECRET_PATTERNS.to_vec(),
+        }
+    }
+
+    /// Mask also the values of the variables whose names match `pattern`, such as `*_DSN`.
+    pub fn mask(mut self, pattern: &'a str) -> Self {
+        self.patterns.push(pattern);
+        self
+    }
+
+    /// Replace the patterns of the names of the variables to mask.
+    pub fn patterns(self, patterns: &[&'a str]) -> Self {
+        Self {
+            patterns: patterns.to_vec(),
+            ..self
+        }
+    }
+
+    fn is_secret(&self, name: &str) -> bool {
+        self.patterns.iter().any(|p| glob_match(p, name))
+    }
+}
+
+impl<K: AsRef<str>, V: AsRef<str>> fmt::Display for DisplayEnv<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, value)) in self.vars.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let name = name.as_ref();
+            if self.is_secret(name) {
+                write!(f, "{}=***", name)?;
+            } else {
+                write!(f, "{}={}", name, value.as_ref())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<K: AsRef<str>, V: AsRef<str>> fmt::Debug for DisplayEnv<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display environment variables with secret values masked, so that a startup dump of the
+/// environment is safe to log.
+///
+/// It accepts any iterator of name-value pairs, such as `std::env::vars()` or `&HashMap<String,
+/// String>`.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::display_env;
+///
+/// let vars = [
+///     ("HOME", "/root"),
+///     ("GITHUB_TOKEN", "ghp_x"),
+///     ("DB_DSN", "mysql://u:p@h"),
+/// ];
+/// assert_eq!(
+///     display_env(vars).to_string(),
+///     "DB_DSN=mysql://u:p@h\nGITHUB_TOKEN=***\nHOME=/root"
+/// );
+/// assert_eq!(
+///     display_env(vars).mask("*_DSN").to_string(),
+///     "DB_DSN=***\nGITHUB_TOKEN=***\nHOME=/root"
+/// );
+/// ```
+pub fn display_env<'a, K: AsRef<str>, V: AsRef<str>>(
+    vars: impl IntoIterator<Item = (K, V)>,
+) -> DisplayEnv<'a, K, V> {
+    DisplayEnv::new(vars)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*TOKEN*", "TOKEN"));
+        assert!(glob_match("*TOKEN*", "gh_token_x"));
+        assert!(glob_match("*_DSN", "DB_DSN"));
+        assert!(glob_match("A*B*C", "AxxBxxBxC"));
+        assert!(glob_match("", ""));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("*_DSN", "DB_DSN_X"));
+        assert!(!glob_match("A*B", "AC"));
+        assert!(!glob_match("", "a"));
+    }
+
+    #[test]
+    fn test_display_env() {
+        let vars = [
+            ("B", "2"),
+            ("my_secret", "s"),
+            ("A", "1"),
+            ("PASSWORD", "p"),
+        ];
+        assert_eq!(
+            "A=1\nB=2\nPASSWORD=***\nmy_secret=***",
+            display_env(vars).to_string()
+        );
+        assert_eq!(
+            "A=***\nB=2\nPASSWORD=p\nmy_secret=s",
+            display_env(vars).patterns(&["a"]).to_string()
+        );
+        assert_eq!("", display_env(Vec::<(&str, &str)>::new()).to_string());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_display_env_map() {
+        use std::collections::HashMap;
+        use std::string::String;
+
+        let m: HashMap<String, String> = [("K".to_string(), "v".to_string())].into();
+        assert_eq!("K=v", display_env(&m).to_string());
+    }
+}
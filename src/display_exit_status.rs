@@ -0,0 +1,144 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::process::ExitStatus;
+
+/// Display an `ExitStatus` as `exit code 1`, or as `killed by SIGKILL (9)` on unix.
+///
+/// Only the signals numbered the same on all unix platforms are named; others are displayed as
+/// `killed by signal 10`.
+#[must_use]
+pub struct DisplayExitStatus {
+    status: ExitStatus,
+}
+
+impl DisplayExitStatus {
+    pub const fn new(status: ExitStatus) -> Self {
+        Self { status }
+    }
+}
+
+/// Return the name of a signal that has the same number on all unix platforms.
+#[cfg(unix)]
+fn signal_name(signal: i32) -> Option<&'static str> {
+    let name = match signal {
+        1 => "SIGHUP",
+        2 => "SIGINT",
+        3 => "SIGQUIT",
+        4 => "SIGILL",
+        5 => "SIGTRAP",
+        6 => "SIGABRT",
+        8 => "SIGFPE",
+        9 => "SIGKILL",
+        11 => "SIGSEGV",
+        13 => "SIGPIPE",
+        14 => "SIGALRM",
+        15 => "SIGTERM",
+        _ => return None,
+    };
+    Some(name)
+}
+
+impl fmt::Display for DisplayExitStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(code) = self.status.code() {
+            return write!(f, "exit code {}", code);
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+
+            if let Some(signal) = self.status.signal() {
+                return match signal_name(signal) {
+                    Some(name) => write!(f, "killed by {} ({})", name, signal),
+                    None => write!(f, "killed by signal {}", signal),
+                };
+            }
+        }
+
+        write!(f, "{}", self.status)
+    }
+}
+
+impl fmt::Debug for DisplayExitStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a process `ExitStatus` the same way on all platforms.
+///
+/// # Example
+///
+/// ```rust
+/// use std::process::Command;
+///
+/// use display_more::DisplayExitStatusExt;
+///
+/// # fn main() -> std::io::Result<()> {
+/// # if cfg!(unix) {
+/// let status = Command::new("sh").args(["-c", "exit 3"]).status()?;
+/// assert_eq!(status.display_exit().to_string(), "exit code 3");
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+pub trait DisplayExitStatusExt {
+    fn display_exit(&self) -> DisplayExitStatus;
+}
+
+impl DisplayExitStatusExt for ExitStatus {
+    fn display_exit(&self) -> DisplayExitStatus {
+        DisplayExitStatus::new(*self)
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use std::os::unix::process::ExitStatusExt;
+    use std::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_display_exit_code() {
+        assert_eq!(
+            "exit code 0",
+            ExitStatus::from_raw(0).display_exit().to_string()
+        );
+        assert_eq!(
+            "exit code 1",
+            ExitStatus::from_raw(1 << 8).display_exit().to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_exit_signal() {
+        assert_eq!(
+            "killed by SIGKILL (9)",
+            ExitStatus::from_raw(9).display_exit().to_string()
+        );
+        assert_eq!(
+            "killed by SIGTERM (15)",
+            ExitStatus::from_raw(15).display_exit().to_string()
+        );
+        assert_eq!(
+            "killed by signal 10",
+            ExitStatus::from_raw(10).display_exit().to_string()
+        );
+    }
+}
@@ -0,0 +1,220 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+
+/// Write the names of the flags set in `value`, joined with `|`, and the unnamed bits in hex.
+///
+/// A flag is written if all of its bits are set and some of them are not covered by a flag
+/// written before it. Flags with no bits or an empty name are ignored.
+fn fmt_flags<'a>(
+    f: &mut fmt::Formatter<'_>,
+    value: u64,
+    names: impl IntoIterator<Item = (u64, &'a str)>,
+) -> fmt::Result {
+    if value == 0 {
+        return write!(f, "0");
+    }
+
+    let mut remaining = value;
+    let mut first = true;
+
+    for (mask, name) in names {
+        if mask == 0 || name.is_empty() || value & mask != mask || remaining & mask == 0 {
+            continue;
+        }
+        if !first {
+            write!(f, "|")?;
+        }
+        write!(f, "{}", name)?;
+        first = false;
+        remaining &= !mask;
+    }
+
+    if remaining != 0 {
+        if !first {
+            write!(f, "|")?;
+        }
+        write!(f, "{:#x}", remaining)?;
+    }
+    Ok(())
+}
+
+/// Display a bit mask by the names of the flags set in it, such as `READ|WRITE|0x40`.
+///
+/// Bits without a name are displayed in hex after the names. A value of `0` is displayed as `0`.
+#[must_use]
+pub struct DisplayFlags<'a, T> {
+    value: T,
+    names: &'a [(T, &'a str)],
+}
+
+impl<'a, T> DisplayFlags<'a, T> {
+    pub const fn new(value: T, names: &'a [(T, &'a str)]) -> Self {
+        Self { value, names }
+    }
+}
+
+impl<T: Into<u64> + Copy> fmt::Display for DisplayFlags<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = self
+            .names
+            .iter()
+            .map(|(mask, name)| ((*mask).into(), *name));
+        fmt_flags(f, self.value.into(), names)
+    }
+}
+
+impl<T: Into<u64> + Copy> fmt::Debug for DisplayFlags<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a bit mask by the names of its flags, for permission and option masks in logs.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::display_flags;
+///
+/// const FLAGS: &[(u32, &str)] = &[(1, "READ"), (2, "WRITE"), (4, "EXEC")];
+///
+/// assert_eq!(display_flags(0x43u32, FLAGS).to_string(), "READ|WRITE|0x40");
+/// assert_eq!(display_flags(0u32, FLAGS).to_string(), "0");
+/// ```
+pub const fn display_flags<'a, T>(value: T, names: &'a [(T, &'a str)]) -> DisplayFlags<'a, T> {
+    DisplayFlags::new(value, names)
+}
+
+#[cfg(feature = "bitflags")]
+pub use bitflags_impl::DisplayBitflags;
+#[cfg(feature = "bitflags")]
+pub use bitflags_impl::DisplayBitflagsExt;
+
+#[cfg(feature = "bitflags")]
+mod bitflags_impl {
+    use core::fmt;
+
+    use bitflags::Flags;
+
+    use super::fmt_flags;
+
+    /// Display a `bitflags` value by the names of the flags set in it, such as `READ|WRITE|0x40`.
+    #[must_use]
+    pub struct DisplayBitflags<'a, F> {
+        flags: &'a F,
+    }
+
+    impl<'a, F> DisplayBitflags<'a, F> {
+        pub const fn new(flags: &'a F) -> Self {
+            Self { flags }
+        }
+    }
+
+    impl<F> fmt::Display for DisplayBitflags<'_, F>
+    where
+        F: Flags,
+        F::Bits: Into<u64>,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let names = F::FLAGS
+                .iter()
+                .map(|flag| (flag.value().bits().into(), flag.name()));
+            fmt_flags(f, self.flags.bits().into(), names)
+        }
+    }
+
+    impl<F> fmt::Debug for DisplayBitflags<'_, F>
+    where
+        F: Flags,
+        F::Bits: Into<u64>,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Display::fmt(self, f)
+        }
+    }
+
+    /// Display a `bitflags` value by its flag names, the same way as [`display_flags`].
+    ///
+    /// [`display_flags`]: crate::display_flags
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use display_more::DisplayBitflagsExt;
+    ///
+    /// bitflags::bitflags! {
+    ///     struct Perm: u8 {
+    ///         const READ = 1;
+    ///         const WRITE = 2;
+    ///     }
+    /// }
+    ///
+    /// let p = Perm::from_bits_retain(0x43);
+    /// assert_eq!(p.display_flags().to_string(), "READ|WRITE|0x40");
+    /// ```
+    pub trait DisplayBitflagsExt: Flags {
+        fn display_flags(&self) -> DisplayBitflags<'_, Self> {
+            DisplayBitflags::new(self)
+        }
+    }
+
+    impl<F: Flags> DisplayBitflagsExt for F {}
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    const FLAGS: &[(u8, &str)] = &[(1, "READ"), (2, "WRITE"), (3, "RW"), (4, "EXEC")];
+
+    #[test]
+    fn test_display_flags() {
+        assert_eq!("0", display_flags(0, FLAGS).to_string());
+        assert_eq!("READ", display_flags(1, FLAGS).to_string());
+        assert_eq!("READ|WRITE|0x40", display_flags(0x43, FLAGS).to_string());
+        assert_eq!("0x80", display_flags(0x80, FLAGS).to_string());
+        assert_eq!("READ|WRITE|EXEC", display_flags(7, FLAGS).to_string());
+    }
+
+    #[test]
+    fn test_display_flags_composite_first() {
+        let names: &[(u8, &str)] = &[(3, "RW"), (1, "READ"), (0, "NONE"), (2, ""), (4, "EXEC")];
+        assert_eq!("RW|EXEC", display_flags(7, names).to_string());
+        assert_eq!("READ", display_flags(1, names).to_string());
+        assert_eq!("0x2", display_flags(2, names).to_string());
+    }
+
+    #[cfg(feature = "bitflags")]
+    #[test]
+    fn test_display_bitflags() {
+        bitflags::bitflags! {
+            struct Perm: u16 {
+                const READ = 1;
+                const WRITE = 2;
+                const ALL = 3;
+            }
+        }
+
+        assert_eq!("0", Perm::empty().display_flags().to_string());
+        assert_eq!("READ|WRITE", Perm::ALL.display_flags().to_string());
+        assert_eq!(
+            "WRITE|0x100",
+            Perm::from_bits_retain(0x102).display_flags().to_string()
+        );
+    }
+}
@@ -0,0 +1,850 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::display_iterator_options::DisplayIteratorOptions;
+use crate::DisplayIntoIter;
+
+/// How float wrappers display `NaN` and infinities.
+///
+/// JSON has no representation for them, so JSON-bound output may use [`NonFinite::Null`],
+/// while human-readable output may prefer [`NonFinite::Dash`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NonFinite {
+    /// `NaN`, `inf` and `-inf`, as std does.
+    #[default]
+    Std,
+    /// `null` for all non-finite values.
+    Null,
+    /// `-` for all non-finite values.
+    Dash,
+    /// Custom strings for `NaN`, `inf` and `-inf`.
+    Custom {
+        nan: &'static str,
+        inf: &'static str,
+        neg_inf: &'static str,
+    },
+}
+
+impl NonFinite {
+    /// Write a non-finite `value` in this style.
+    pub(crate) fn write(&self, f: &mut fmt::Formatter<'_>, value: f64) -> fmt::Result {
+        let s = match *self {
+            NonFinite::Std => return write!(f, "{}", value),
+            NonFinite::Null => "null",
+            NonFinite::Dash => "-",
+            NonFinite::Custom { nan, inf, neg_inf } => {
+                if value.is_nan() {
+                    nan
+                } else if value > 0.0 {
+                    inf
+                } else {
+                    neg_inf
+                }
+            }
+        };
+        f.write_str(s)
+    }
+}
+
+/// A float of its original width, so that `f32` values are formatted with their own shortest
+/// digits instead of those of the widened `f64`.
+#[derive(Debug, Clone, Copy)]
+enum Float {
+    F32(f32),
+    F64(f64),
+}
+
+impl Float {
+    fn as_f64(self) -> f64 {
+        match self {
+            Float::F32(v) => v as f64,
+            Float::F64(v) => v,
+        }
+    }
+}
+
+impl fmt::Display for Float {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Float::F32(v) => fmt::Display::fmt(v, f),
+            Float::F64(v) => fmt::Display::fmt(v, f),
+        }
+    }
+}
+
+impl fmt::LowerExp for Float {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Float::F32(v) => fmt::LowerExp::fmt(v, f),
+            Float::F64(v) => fmt::LowerExp::fmt(v, f),
+        }
+    }
+}
+
+/// Write `digits` with the decimal exponent `exp` of the first digit in positional notation,
+/// such as `0.00123` or `123000`.
+fn write_positional(
+    f: &mut fmt::Formatter<'_>,
+    negative: bool,
+    digits: &str,
+    exp: i32,
+) -> fmt::Result {
+    if negative {
+        write!(f, "-")?;
+    }
+
+    if exp < 0 {
+        write!(f, "0.")?;
+        for _ in 0..(-exp - 1) {
+            write!(f, "0")?;
+        }
+        write!(f, "{}", digits)
+    } else {
+        let int_len = exp as usize + 1;
+        if int_len >= digits.len() {
+            write!(f, "{}", digits)?;
+            for _ in digits.len()..int_len {
+                write!(f, "0")?;
+            }
+            Ok(())
+        } else {
+            write!(f, "{}.{}", &digits[..int_len], &digits[int_len..])
+        }
+    }
+}
+
+/// Display a float with exactly `n` significant digits in positional notation, such as
+/// `0.0001235` or `123000`.
+#[must_use]
+pub struct DisplaySigFigs {
+    value: f64,
+    sig_figs: usize,
+    non_finite: NonFinite,
+}
+
+impl DisplaySigFigs {
+    pub fn new(value: f64, sig_figs: usize) -> Self {
+        Self {
+            value,
+            sig_figs: sig_figs.max(1),
+            non_finite: NonFinite::Std,
+        }
+    }
+
+    /// Set how `NaN` and infinities are displayed.
+    pub const fn non_finite(self, non_finite: NonFinite) -> Self {
+        Self { non_finite, ..self }
+    }
+}
+
+impl fmt::Display for DisplaySigFigs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.value.is_finite() {
+            return self.non_finite.write(f, self.value);
+        }
+
+        if self.value == 0.0 {
+            return write!(f, "0");
+        }
+
+        // Let std do the rounding, then lay out the digits positionally.
+        let (negative, digits, exp) = sci_parts(Float::F64(self.value), Some(self.sig_figs - 1));
+        write_positional(f, negative, &digits, exp)
+    }
+}
+
+impl fmt::Debug for DisplaySigFigs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a float in scientific notation, such as `1.2345e-4`, or in engineering notation, in
+/// which the exponent is a multiple of 3, such as `123.45e-6`.
+///
+/// Without a precision, the shortest digits that round-trip are displayed.
+#[must_use]
+pub struct DisplaySci {
+    value: Float,
+    precision: Option<usize>,
+    engineering: bool,
+    non_finite: NonFinite,
+}
+
+impl DisplaySci {
+    pub const fn new(value: f64) -> Self {
+        Self::of(Float::F64(value))
+    }
+
+    /// Display an `f32` with its own shortest digits.
+    pub const fn new_f32(value: f32) -> Self {
+        Self::of(Float::F32(value))
+    }
+
+    const fn of(value: Float) -> Self {
+        Self {
+            value,
+            precision: None,
+            engineering: false,
+            non_finite: NonFinite::Std,
+        }
+    }
+
+    /// Set how `NaN` and infinities are displayed.
+    pub const fn non_finite(self, non_finite: NonFinite) -> Self {
+        Self { non_finite, ..self }
+    }
+
+    /// Use engineering notation, i.e., an exponent that is a multiple of 3.
+    pub const fn engineering(self) -> Self {
+        Self {
+            engineering: true,
+            ..self
+        }
+    }
+
+    /// Set the number of digits after the decimal point of the mantissa.
+    pub const fn precision(self, precision: usize) -> Self {
+        Self {
+            precision: Some(precision),
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for DisplaySci {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.value.as_f64().is_finite() {
+            return self.non_finite.write(f, self.value.as_f64());
+        }
+
+        if !self.engineering {
+            return match self.precision {
+                Some(p) => write!(f, "{:.*e}", p, self.value),
+                None => write!(f, "{:e}", self.value),
+            };
+        }
+
+        let (negative, mut digits, mut exp) = sci_parts(self.value, self.precision);
+
+        // With a precision, the number of significant digits depends on the shift of the
+        // decimal point, which may change if rounding carries into the next power of 10.
+        if let Some(p) = self.precision {
+            for _ in 0..2 {
+                let shift = exp.rem_euclid(3) as usize;
+                let (_, d, e) = sci_parts(self.value, Some(shift + p));
+                digits = d;
+                if e == exp {
+                    break;
+                }
+                exp = e;
+            }
+        }
+
+        let shift = exp.rem_euclid(3) as usize;
+        let int_len = shift + 1;
+
+        if negative {
+            write!(f, "-")?;
+        }
+
+        if digits.len() <= int_len {
+            write!(f, "{}", digits)?;
+            for _ in digits.len()..int_len {
+                write!(f, "0")?;
+            }
+        } else {
+            write!(f, "{}.{}", &digits[..int_len], &digits[int_len..])?;
+        }
+
+        write!(f, "e{}", exp - shift as i32)
+    }
+}
+
+impl fmt::Debug for DisplaySci {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Split a finite float into sign, significant digits and decimal exponent, using std's
+/// scientific formatting with `decimals` digits after the point, or the shortest round-trip
+/// digits if it is `None`.
+fn sci_parts(value: Float, decimals: Option<usize>) -> (bool, String, i32) {
+    let sci = match decimals {
+        Some(d) => format!("{:.*e}", d, value),
+        None => format!("{:e}", value),
+    };
+
+    let (mantissa, exp) = sci.split_once('e').unwrap();
+    let exp: i32 = exp.parse().unwrap();
+
+    let (negative, mantissa) = match mantissa.strip_prefix('-') {
+        Some(m) => (true, m),
+        None => (false, mantissa),
+    };
+
+    (negative, mantissa.replace('.', ""), exp)
+}
+
+/// Display a float with the shortest digits that round-trip, such as `0.1`, in positional
+/// notation as std `Display` does, e.g., `1` and `10000000000000000`.
+///
+/// With the `fast-float` feature the digits are computed by [ryu](https://docs.rs/ryu), which is
+/// faster than std formatting. The output is the same with either backend.
+#[must_use]
+pub struct DisplayShortest {
+    value: Float,
+    non_finite: NonFinite,
+}
+
+impl DisplayShortest {
+    pub const fn new(value: f64) -> Self {
+        Self::of(Float::F64(value))
+    }
+
+    /// Display an `f32` with its own shortest digits, e.g., `0.1` rather than
+    /// `0.10000000149011612`.
+    pub const fn new_f32(value: f32) -> Self {
+        Self::of(Float::F32(value))
+    }
+
+    const fn of(value: Float) -> Self {
+        Self {
+            value,
+            non_finite: NonFinite::Std,
+        }
+    }
+
+    /// Set how `NaN` and infinities are displayed.
+    pub const fn non_finite(self, non_finite: NonFinite) -> Self {
+        Self { non_finite, ..self }
+    }
+}
+
+impl fmt::Display for DisplayShortest {
+    #[cfg(feature = "fast-float")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.value.as_f64().is_finite() {
+            return self.non_finite.write(f, self.value.as_f64());
+        }
+
+        let mut buf = ryu::Buffer::new();
+        let s = match self.value {
+            Float::F32(v) => buf.format_finite(v),
+            Float::F64(v) => buf.format_finite(v),
+        };
+
+        // ryu outputs `1.0`, `1e16` or `1.5e-7`: convert to the positional notation of std.
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(abs) => (true, abs),
+            None => (false, s),
+        };
+        let (mantissa, exp) = match s.split_once('e') {
+            Some((m, e)) => (m, e.parse::<i32>().unwrap()),
+            None => (s, 0),
+        };
+        let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        let frac = frac.trim_end_matches('0');
+
+        let int = int.trim_start_matches('0');
+        if int.is_empty() {
+            // `0.00123`: the first significant digit is in the fraction.
+            let digits = frac.trim_start_matches('0');
+            if digits.is_empty() {
+                return write!(f, "{}0", if negative { "-" } else { "" });
+            }
+            let lead = (frac.len() - digits.len()) as i32;
+            write_positional(f, negative, digits, exp - lead - 1)
+        } else {
+            // At most 17 significant digits: concatenate them without allocating.
+            let mut buf = [0u8; 32];
+            buf[..int.len()].copy_from_slice(int.as_bytes());
+            buf[int.len()..int.len() + frac.len()].copy_from_slice(frac.as_bytes());
+            let digits = core::str::from_utf8(&buf[..int.len() + frac.len()]).unwrap();
+            let digits = digits.trim_end_matches('0');
+            write_positional(f, negative, digits, exp + int.len() as i32 - 1)
+        }
+    }
+
+    #[cfg(not(feature = "fast-float"))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.value.as_f64().is_finite() {
+            return self.non_finite.write(f, self.value.as_f64());
+        }
+
+        write!(f, "{}", self.value)
+    }
+}
+
+impl fmt::Debug for DisplayShortest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a slice of floats with [`DisplayShortest`], such as `[0.1,0.25,1.5]`.
+///
+/// The elements are truncated and separated the same way as
+/// [`DisplaySlice`](crate::display_slice::DisplaySlice).
+#[must_use]
+pub struct DisplayShortestSlice<'a, T> {
+    values: &'a [T],
+    non_finite: NonFinite,
+    options: DisplayIteratorOptions<'a>,
+}
+
+impl<'a, T: DisplayFloatExt> DisplayShortestSlice<'a, T> {
+    pub const fn new(values: &'a [T]) -> Self {
+        Self {
+            values,
+            non_finite: NonFinite::Std,
+            options: DisplayIteratorOptions::new(),
+        }
+    }
+
+    /// Set how `NaN` and infinities are displayed.
+    pub const fn non_finite(self, non_finite: NonFinite) -> Self {
+        Self { non_finite, ..self }
+    }
+
+    iterator_options_builders!('a, options);
+
+    fn shortest(&self, v: &T) -> DisplayShortest {
+        v.display_shortest().non_finite(self.non_finite)
+    }
+}
+
+impl<T: DisplayFloatExt> fmt::Display for DisplayShortestSlice<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let options = self.options.resolved();
+
+        if options.max_width.is_some() {
+            // Fitting the width renders the elements several times: convert them once.
+            let values = self
+                .values
+                .iter()
+                .map(|v| self.shortest(v))
+                .collect::<Vec<_>>();
+            return DisplayIntoIter::with_options(values.iter(), options).fmt(f);
+        }
+
+        options.write_seq(f, self.values.iter().map(|v| self.shortest(v)))
+    }
+}
+
+impl<T: DisplayFloatExt> fmt::Debug for DisplayShortestSlice<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a float with a fixed number of decimals, identically on every platform.
+///
+/// Unlike `{:.N}`, it never outputs a negative zero: `-0.0` and negative values that round to
+/// zero, including subnormals, are displayed as `0.00..`. This makes it suitable for snapshot
+/// tests.
+#[must_use]
+pub struct DisplayFixed {
+    value: f64,
+    precision: usize,
+    non_finite: NonFinite,
+}
+
+impl DisplayFixed {
+    pub const fn new(value: f64, precision: usize) -> Self {
+        Self {
+            value,
+            precision,
+            non_finite: NonFinite::Std,
+        }
+    }
+
+    /// Set how `NaN` and infinities are displayed.
+    pub const fn non_finite(self, non_finite: NonFinite) -> Self {
+        Self { non_finite, ..self }
+    }
+}
+
+impl fmt::Display for DisplayFixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.value.is_finite() {
+            return self.non_finite.write(f, self.value);
+        }
+
+        let s = format!("{:.*}", self.precision, self.value);
+
+        // Strip the sign of a negative zero, e.g., "-0.00"
+        let s = match s.strip_prefix('-') {
+            Some(abs) if abs.bytes().all(|b| b == b'0' || b == b'.') => abs,
+            _ => &s,
+        };
+
+        f.write_str(s)
+    }
+}
+
+impl fmt::Debug for DisplayFixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display floats in various notations.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayFloatExt;
+///
+/// assert_eq!(0.00012346f64.display_sig_figs(4).to_string(), "0.0001235");
+/// assert_eq!(123456.0f64.display_sig_figs(3).to_string(), "123000");
+/// assert_eq!(0.00012345f64.display_sci().to_string(), "1.2345e-4");
+/// assert_eq!(0.00012345f64.display_eng().to_string(), "123.45e-6");
+/// ```
+pub trait DisplayFloatExt {
+    /// Display with exactly `n` significant digits.
+    fn display_sig_figs(&self, n: usize) -> DisplaySigFigs;
+
+    /// Display with `precision` decimals, deterministically and without negative zero.
+    fn display_fixed(&self, precision: usize) -> DisplayFixed;
+
+    /// Display with the shortest digits that round-trip.
+    fn display_shortest(&self) -> DisplayShortest;
+
+    /// Display in scientific notation.
+    fn display_sci(&self) -> DisplaySci;
+
+    /// Display in engineering notation.
+    fn display_eng(&self) -> DisplaySci {
+        self.display_sci().engineering()
+    }
+}
+
+impl DisplayFloatExt for f64 {
+    fn display_sig_figs(&self, n: usize) -> DisplaySigFigs {
+        DisplaySigFigs::new(*self, n)
+    }
+
+    fn display_fixed(&self, precision: usize) -> DisplayFixed {
+        DisplayFixed::new(*self, precision)
+    }
+
+    fn display_shortest(&self) -> DisplayShortest {
+        DisplayShortest::new(*self)
+    }
+
+    fn display_sci(&self) -> DisplaySci {
+        DisplaySci::new(*self)
+    }
+}
+
+impl DisplayFloatExt for f32 {
+    fn display_sig_figs(&self, n: usize) -> DisplaySigFigs {
+        DisplaySigFigs::new(*self as f64, n)
+    }
+
+    fn display_fixed(&self, precision: usize) -> DisplayFixed {
+        DisplayFixed::new(*self as f64, precision)
+    }
+
+    fn display_shortest(&self) -> DisplayShortest {
+        DisplayShortest::new_f32(*self)
+    }
+
+    fn display_sci(&self) -> DisplaySci {
+        DisplaySci::new_f32(*self)
+    }
+}
+
+/// Display a slice of floats, each with its shortest round-trip digits.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayFloatSliceExt;
+///
+/// let a = [0.1f32, 0.25, 1.5];
+/// assert_eq!(a.display_shortest().to_string(), "[0.1,0.25,1.5]");
+/// assert_eq!(
+///     vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6]
+///         .display_shortest()
+///         .to_string(),
+///     "[0.1,0.2,0.3,0.4,..,0.6]"
+/// );
+/// ```
+pub trait DisplayFloatSliceExt<T> {
+    fn display_shortest(&self) -> DisplayShortestSlice<'_, T>;
+}
+
+impl<T: DisplayFloatExt> DisplayFloatSliceExt<T> for [T] {
+    fn display_shortest(&self) -> DisplayShortestSlice<'_, T> {
+        DisplayShortestSlice::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DisplaySliceExt;
+
+    #[test]
+    fn test_display_sig_figs() {
+        assert_eq!("0.0001235", 0.00012346f64.display_sig_figs(4).to_string());
+        assert_eq!("123000", 123456.0.display_sig_figs(3).to_string());
+        assert_eq!("123500", 123456.0.display_sig_figs(4).to_string());
+        assert_eq!("1.23", 1.23456.display_sig_figs(3).to_string());
+        assert_eq!("1.00", 1.0.display_sig_figs(3).to_string());
+        assert_eq!("12.3", 12.345.display_sig_figs(3).to_string());
+        assert_eq!("0.100", 0.1f32.display_sig_figs(3).to_string());
+        assert_eq!("0", 0.0.display_sig_figs(3).to_string());
+    }
+
+    #[test]
+    fn test_display_sig_figs_rounding_carry() {
+        assert_eq!("10.0", 9.9951.display_sig_figs(3).to_string());
+        assert_eq!("100", 99.96.display_sig_figs(2).to_string());
+        assert_eq!("1", 0.96.display_sig_figs(1).to_string());
+    }
+
+    #[test]
+    fn test_display_sig_figs_negative_and_special() {
+        assert_eq!("-1.23", (-1.2345).display_sig_figs(3).to_string());
+        assert_eq!("-0.0012", (-0.00123).display_sig_figs(2).to_string());
+        assert_eq!("2", 1.5.display_sig_figs(0).to_string());
+        assert_eq!("NaN", f64::NAN.display_sig_figs(3).to_string());
+        assert_eq!("inf", f64::INFINITY.display_sig_figs(3).to_string());
+    }
+
+    #[test]
+    fn test_display_sci() {
+        assert_eq!("1.2345e-4", 0.00012345.display_sci().to_string());
+        assert_eq!("1.23e-4", 0.00012345.display_sci().precision(2).to_string());
+        assert_eq!("1e0", 1.0.display_sci().to_string());
+        assert_eq!("-1.5e3", (-1500.0).display_sci().to_string());
+        assert_eq!("0e0", 0.0.display_sci().to_string());
+        assert_eq!("NaN", f64::NAN.display_sci().to_string());
+    }
+
+    #[test]
+    fn test_display_eng() {
+        assert_eq!("123.45e-6", 0.00012345.display_eng().to_string());
+        assert_eq!("1.5e3", 1500.0.display_eng().to_string());
+        assert_eq!("15e3", 15000.0.display_eng().to_string());
+        assert_eq!("150e3", 150000.0.display_eng().to_string());
+        assert_eq!("-10e-3", (-0.01).display_eng().to_string());
+        assert_eq!("1e0", 1.0.display_eng().to_string());
+        assert_eq!("0e0", 0.0.display_eng().to_string());
+        assert_eq!("-inf", f64::NEG_INFINITY.display_eng().to_string());
+    }
+
+    #[test]
+    fn test_display_eng_precision() {
+        assert_eq!(
+            "123.5e-6",
+            0.00012346.display_eng().precision(1).to_string()
+        );
+        assert_eq!("1.50e3", 1500.0.display_eng().precision(2).to_string());
+        assert_eq!("15e3", 15000.0.display_eng().precision(0).to_string());
+
+        // Rounding carries into the next multiple of 3
+        assert_eq!("1.0e3", 999.96.display_eng().precision(1).to_string());
+        assert_eq!("1e3", 999.96.display_eng().precision(0).to_string());
+    }
+
+    #[test]
+    fn test_display_shortest() {
+        assert_eq!("0.1", 0.1.display_shortest().to_string());
+        assert_eq!(
+            "0.30000000000000004",
+            (0.1 + 0.2).display_shortest().to_string()
+        );
+        assert_eq!("-2.5", (-2.5).display_shortest().to_string());
+        assert_eq!("NaN", f64::NAN.display_shortest().to_string());
+        assert_eq!("inf", f64::INFINITY.display_shortest().to_string());
+
+        // In a slice
+        let values = [0.1, 0.25, 1.5];
+        let shortest = values
+            .iter()
+            .map(|v| v.display_shortest())
+            .collect::<Vec<_>>();
+        assert_eq!("[0.1,0.25,1.5]", shortest.display().to_string());
+    }
+
+    /// The output does not depend on whether the `fast-float` feature is enabled.
+    #[test]
+    fn test_display_shortest_positional() {
+        assert_eq!("1", 1.0.display_shortest().to_string());
+        assert_eq!("10000000000000000", 1e16.display_shortest().to_string());
+        assert_eq!("0.00000015", 1.5e-7.display_shortest().to_string());
+        assert_eq!("123456.789", 123456.789.display_shortest().to_string());
+        assert_eq!("0", 0.0.display_shortest().to_string());
+        assert_eq!("-0", (-0.0).display_shortest().to_string());
+        assert_eq!("-120", (-120.0).display_shortest().to_string());
+
+        for v in [
+            0.1,
+            1.0,
+            1e16,
+            1.5e-7,
+            -2.5e-300,
+            1.7976931348623157e308,
+            5e-324,
+            123.456,
+            (0.1 + 0.2),
+        ] {
+            assert_eq!(v.to_string(), v.display_shortest().to_string());
+        }
+    }
+
+    #[test]
+    fn test_display_shortest_f32() {
+        assert_eq!("0.1", 0.1f32.display_shortest().to_string());
+        assert_eq!("16777216", 16777216f32.display_shortest().to_string());
+        assert_eq!("0.3", (0.1f32 + 0.2f32).display_shortest().to_string());
+        assert_eq!("NaN", f32::NAN.display_shortest().to_string());
+
+        for v in [0.1f32, 1.0, 3.4028235e38, 1e-45, -1.5e-7, 123.456] {
+            assert_eq!(v.to_string(), v.display_shortest().to_string());
+        }
+    }
+
+    #[test]
+    fn test_display_shortest_slice() {
+        let a = [0.1, 0.1 + 0.2, -2.5, 1e16];
+        assert_eq!(
+            "[0.1,0.30000000000000004,-2.5,10000000000000000]",
+            a.display_shortest().to_string()
+        );
+
+        // `f32` elements keep their own digits.
+        let a = [0.1f32, 0.2, 0.1 + 0.2];
+        assert_eq!("[0.1,0.2,0.3]", a.display_shortest().to_string());
+        assert_eq!(
+            "[0.1, .., 0.3]",
+            a.display_shortest().at_most(Some(2)).sep(", ").to_string()
+        );
+        assert_eq!(
+            "[0.1,..,0.3]",
+            a.display_shortest().head_tail(1, 1).to_string()
+        );
+
+        let a = [1.5, f64::NAN, f64::INFINITY];
+        assert_eq!("[1.5,NaN,inf]", a.display_shortest().to_string());
+        assert_eq!(
+            "[1.5,null,null]",
+            a.display_shortest().non_finite(NonFinite::Null).to_string()
+        );
+
+        let a = (1..=100).map(|i| i as f64 / 10.0).collect::<Vec<_>>();
+        assert_eq!(
+            "[0.1,..,10]",
+            a.display_shortest().fit_width(11).to_string()
+        );
+        assert_eq!("[]", Vec::<f64>::new().display_shortest().to_string());
+    }
+
+    #[test]
+    fn test_display_sci_f32() {
+        assert_eq!("1e-1", 0.1f32.display_sci().to_string());
+        assert_eq!("1.23e-4", 0.000123f32.display_sci().to_string());
+        assert_eq!("1.00e-1", 0.1f32.display_sci().precision(2).to_string());
+        assert_eq!("100e-3", 0.1f32.display_eng().to_string());
+        assert_eq!("123e-6", 0.000123f32.display_eng().to_string());
+        assert_eq!("-inf", f32::NEG_INFINITY.display_sci().to_string());
+    }
+
+    #[test]
+    fn test_non_finite() {
+        let nan = f64::NAN;
+        let inf = f64::INFINITY;
+        let neg_inf = f64::NEG_INFINITY;
+
+        assert_eq!("NaN", nan.display_sig_figs(3).to_string());
+        assert_eq!(
+            "null",
+            nan.display_sig_figs(3)
+                .non_finite(NonFinite::Null)
+                .to_string()
+        );
+        assert_eq!(
+            "-",
+            inf.display_sci().non_finite(NonFinite::Dash).to_string()
+        );
+        assert_eq!(
+            "null",
+            neg_inf
+                .display_shortest()
+                .non_finite(NonFinite::Null)
+                .to_string()
+        );
+
+        let custom = NonFinite::Custom {
+            nan: "n/a",
+            inf: "+∞",
+            neg_inf: "-∞",
+        };
+        assert_eq!("n/a", nan.display_eng().non_finite(custom).to_string());
+        assert_eq!("+∞", inf.display_shortest().non_finite(custom).to_string());
+        assert_eq!("-∞", neg_inf.display_sci().non_finite(custom).to_string());
+
+        // Finite values are not affected
+        assert_eq!(
+            "1.5",
+            1.5.display_shortest()
+                .non_finite(NonFinite::Null)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_fixed() {
+        assert_eq!("1.50", 1.5.display_fixed(2).to_string());
+        assert_eq!("2", 1.5.display_fixed(0).to_string());
+        assert_eq!("-1.25", (-1.25).display_fixed(2).to_string());
+        assert_eq!("0.100", 0.1f32.display_fixed(3).to_string());
+        assert_eq!("123456789.000", 123456789.0.display_fixed(3).to_string());
+    }
+
+    #[test]
+    fn test_display_fixed_negative_zero_and_subnormal() {
+        assert_eq!("0.00", (-0.0).display_fixed(2).to_string());
+        assert_eq!("0", (-0.0).display_fixed(0).to_string());
+        assert_eq!("0.00", (-0.001).display_fixed(2).to_string());
+        assert_eq!("0.000", f64::MIN_POSITIVE.display_fixed(3).to_string());
+        assert_eq!(
+            "0.000",
+            (-f64::MIN_POSITIVE / 2.0).display_fixed(3).to_string()
+        );
+        assert_eq!("-0.01", (-0.006).display_fixed(2).to_string());
+    }
+
+    #[test]
+    fn test_display_fixed_non_finite() {
+        assert_eq!("NaN", f64::NAN.display_fixed(2).to_string());
+        assert_eq!(
+            "null",
+            f64::INFINITY
+                .display_fixed(2)
+                .non_finite(NonFinite::Null)
+                .to_string()
+        );
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+
+/// Display an integer with its digits grouped by thousands, such as `123,456,789`.
+#[must_use]
+pub struct DisplayGrouped {
+    negative: bool,
+    abs: u128,
+    separator: char,
+}
+
+impl DisplayGrouped {
+    pub const fn new(negative: bool, abs: u128) -> Self {
+        Self {
+            negative,
+            abs,
+            separator: ',',
+        }
+    }
+
+    /// Set the separator between groups. By default it is `','`.
+    pub const fn sep(self, separator: char) -> Self {
+        Self { separator, ..self }
+    }
+}
+
+impl fmt::Display for DisplayGrouped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // u128::MAX has 39 digits
+        let mut buf = [0u8; 39];
+        let mut start = buf.len();
+        let mut n = self.abs;
+        loop {
+            start -= 1;
+            buf[start] = b'0' + (n % 10) as u8;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+        let digits = &buf[start..];
+
+        if self.negative {
+            write!(f, "-")?;
+        }
+
+        for (i, d) in digits.iter().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                write!(f, "{}", self.separator)?;
+            }
+            write!(f, "{}", *d as char)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for DisplayGrouped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display an integer with thousands separators.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayGroupedExt;
+///
+/// assert_eq!(123456789.display_grouped().to_string(), "123,456,789");
+/// assert_eq!((-1234).display_grouped().sep('.').to_string(), "-1.234");
+/// assert_eq!(1234567.display_underscored().to_string(), "1_234_567");
+/// ```
+pub trait DisplayGroupedExt {
+    fn display_grouped(&self) -> DisplayGrouped;
+
+    /// Display with `_` separators, in Rust integer literal style, such as `1_234_567`.
+    fn display_underscored(&self) -> DisplayGrouped {
+        self.display_grouped().sep('_')
+    }
+}
+
+macro_rules! impl_grouped_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl DisplayGroupedExt for $t {
+                fn display_grouped(&self) -> DisplayGrouped {
+                    DisplayGrouped::new(false, *self as u128)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_grouped_signed {
+    ($($t:ty),*) => {
+        $(
+            impl DisplayGroupedExt for $t {
+                fn display_grouped(&self) -> DisplayGrouped {
+                    DisplayGrouped::new(*self < 0, self.unsigned_abs() as u128)
+                }
+            }
+        )*
+    };
+}
+
+impl_grouped_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_grouped_signed!(i8, i16, i32, i64, i128, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_grouped() {
+        assert_eq!("0", 0u8.display_grouped().to_string());
+        assert_eq!("999", 999u32.display_grouped().to_string());
+        assert_eq!("1,000", 1000u32.display_grouped().to_string());
+        assert_eq!("123,456,789", 123456789u64.display_grouped().to_string());
+        assert_eq!("12,345,678", 12345678usize.display_grouped().to_string());
+        assert_eq!(
+            "340,282,366,920,938,463,463,374,607,431,768,211,455",
+            u128::MAX.display_grouped().to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_grouped_signed() {
+        assert_eq!("-1", (-1i32).display_grouped().to_string());
+        assert_eq!("-123,456", (-123456i64).display_grouped().to_string());
+        assert_eq!("-128", i8::MIN.display_grouped().to_string());
+        assert_eq!(
+            "-170,141,183,460,469,231,731,687,303,715,884,105,728",
+            i128::MIN.display_grouped().to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_grouped_sep() {
+        assert_eq!("1 234 567", 1234567.display_grouped().sep(' ').to_string());
+        assert_eq!("1.234", 1234.display_grouped().sep('.').to_string());
+    }
+
+    #[test]
+    fn test_display_underscored() {
+        assert_eq!("1_234_567", 1234567u32.display_underscored().to_string());
+        assert_eq!("-1_000", (-1000i16).display_underscored().to_string());
+        assert_eq!("999", 999.display_underscored().to_string());
+        assert_eq!(
+            1_234_567_890u64,
+            1234567890u64
+                .display_underscored()
+                .to_string()
+                .replace('_', "")
+                .parse::<u64>()
+                .unwrap()
+        );
+    }
+}
@@ -0,0 +1,187 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Write;
+
+use crate::display_env::glob_match;
+use crate::Summary;
+
+/// The default patterns of the names of headers whose values are masked.
+pub const DEFAULT_SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+];
+
+/// Display header-like `(name, value)` pairs as `{name: value, ..}` in their original order,
+/// with names lowercased, sensitive values masked as `***` and long values cut.
+///
+/// A value is masked if the name matches one of the patterns, case-insensitively, where `*`
+/// matches any sequence of chars. The default patterns are [`DEFAULT_SENSITIVE_HEADERS`].
+///
+/// Values longer than [`max_value`](Self::max_value) chars are cut and end with
+/// [`Summary::MARKER`].
+#[must_use]
+pub struct DisplayHeaders<'a, K, V> {
+    headers: Vec<(K, V)>,
+    patterns: Vec<&'a str>,
+    max_value: usize,
+}
+
+impl<'a, K: AsRef<str>, V: AsRef<str>> DisplayHeaders<'a, K, V> {
+    pub fn new(headers: impl IntoIterator<Item = (K, V)>) -> Self {
+        Self {
+            headers: headers.into_iter().collect(),
+            patterns: DEFAULT_SENSITIVE_HEADERS.to_vec(),
+            max_value: 64,
+        }
+    }
+
+    /// Mask also the values of the headers whose names match `pattern`, such as `x-*-token`.
+    pub fn mask(mut self, pattern: &'a str) -> Self {
+        self.patterns.push(pattern);
+        self
+    }
+
+    /// Replace the patterns of the names of the headers to mask.
+    pub fn patterns(self, patterns: &[&'a str]) -> Self {
+        Self {
+            patterns: patterns.to_vec(),
+            ..self
+        }
+    }
+
+    /// Set the max number of chars of a value. By default it is 64.
+    pub const fn max_value(mut self, max_value: usize) -> Self {
+        self.max_value = max_value;
+        self
+    }
+
+    fn is_sensitive(&self, name: &str) -> bool {
+        self.patterns.iter().any(|p| glob_match(p, name))
+    }
+
+    fn fmt_value(&self, f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+        match value.char_indices().nth(self.max_value) {
+            None => f.write_str(value),
+            Some((i, _)) => {
+                f.write_str(&value[..i])?;
+                f.write_char(Summary::MARKER)
+            }
+        }
+    }
+}
+
+impl<K: AsRef<str>, V: AsRef<str>> fmt::Display for DisplayHeaders<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        for (i, (name, value)) in self.headers.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+
+            let name = name.as_ref();
+            for c in name.chars() {
+                f.write_char(c.to_ascii_lowercase())?;
+            }
+            write!(f, ": ")?;
+
+            if self.is_sensitive(name) {
+                write!(f, "***")?;
+            } else {
+                self.fmt_value(f, value.as_ref())?;
+            }
+        }
+        write!(f, "}}")
+    }
+}
+
+impl<K: AsRef<str>, V: AsRef<str>> fmt::Debug for DisplayHeaders<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display RPC or HTTP headers safely for logging, with credentials masked and long values cut.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::display_headers;
+///
+/// let headers = [
+///     ("Content-Type", "text/plain"),
+///     ("Authorization", "Bearer x"),
+/// ];
+/// assert_eq!(
+///     display_headers(headers).to_string(),
+///     "{content-type: text/plain, authorization: ***}"
+/// );
+/// assert_eq!(
+///     display_headers(headers).max_value(4).to_string(),
+///     "{content-type: text…, authorization: ***}"
+/// );
+/// ```
+pub fn display_headers<'a, K: AsRef<str>, V: AsRef<str>>(
+    headers: impl IntoIterator<Item = (K, V)>,
+) -> DisplayHeaders<'a, K, V> {
+    DisplayHeaders::new(headers)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_display_headers() {
+        assert_eq!(
+            "{}",
+            display_headers(Vec::<(&str, &str)>::new()).to_string()
+        );
+
+        let headers = [("X-Id", "7"), ("Cookie", "a=b"), ("SET-COOKIE", "c=d")];
+        assert_eq!(
+            "{x-id: 7, cookie: ***, set-cookie: ***}",
+            display_headers(headers).to_string()
+        );
+        assert_eq!(
+            "{x-id: ***, cookie: a=b, set-cookie: c=d}",
+            display_headers(headers).patterns(&["x-*"]).to_string()
+        );
+        assert_eq!(
+            "{x-id: ***, cookie: ***, set-cookie: ***}",
+            display_headers(headers).mask("X-ID").to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_headers_max_value() {
+        let headers = [("a", "héllo")];
+        assert_eq!(
+            "{a: héllo}",
+            display_headers(headers).max_value(5).to_string()
+        );
+        assert_eq!(
+            "{a: hé…}",
+            display_headers(headers).max_value(2).to_string()
+        );
+        assert_eq!("{a: …}", display_headers(headers).max_value(0).to_string());
+    }
+}
@@ -12,21 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt;
+use core::fmt;
 
 use crate::display_iterator_options::DisplayIteratorOptions;
+use crate::Summarize;
 
 /// Implement `Display` for cloneable iter sources that yield `&T`.
 ///
 /// It outputs at most `limit` elements, excluding those from the 5th to the second-to-last one.
+#[must_use]
 pub struct DisplayIntoIter<'a, T, S>
 where
     T: fmt::Display + 'a,
     S: Clone + IntoIterator<Item = &'a T>,
     S::IntoIter: DoubleEndedIterator + ExactSizeIterator,
 {
-    items: S,
-    options: DisplayIteratorOptions<'a>,
+    pub(crate) items: S,
+    pub(crate) options: DisplayIteratorOptions<'a>,
 }
 
 impl<'a, T, S> DisplayIntoIter<'a, T, S>
@@ -35,47 +37,21 @@ where
     S: Clone + IntoIterator<Item = &'a T>,
     S::IntoIter: DoubleEndedIterator + ExactSizeIterator,
 {
-    pub fn new(items: S) -> Self {
-        Self {
-            items,
-            options: DisplayIteratorOptions::default(),
-        }
-    }
-
-    pub fn at_most(mut self, limit: Option<usize>) -> Self {
-        self.options.limit = limit;
-        self
-    }
-
-    pub fn sep(mut self, separator: &'a str) -> Self {
-        self.options.separator = separator;
-        self
-    }
-
-    pub fn braces(mut self, left: &'a str, right: &'a str) -> Self {
-        self.options.left_brace = left;
-        self.options.right_brace = right;
-        self
-    }
-
-    pub fn ellipsis(mut self, s: &'a str) -> Self {
-        self.options.ellipsis = s;
-        self
+    pub const fn new(items: S) -> Self {
+        Self::with_options(items, DisplayIteratorOptions::new())
     }
 
-    pub fn elem(mut self, prefix: &'a str, suffix: &'a str) -> Self {
-        self.options.elem_prefix = prefix;
-        self.options.elem_suffix = suffix;
-        self
+    pub(crate) const fn with_options(items: S, options: DisplayIteratorOptions<'a>) -> Self {
+        Self { items, options }
     }
 
-    pub fn show_count(mut self) -> Self {
-        self.options.show_count = true;
-        self
-    }
+    iterator_options_builders!('a, options);
 
-    pub fn limit(&self) -> usize {
-        self.options.limit()
+    /// Return a copy that displays at most `limit` elements.
+    pub(crate) fn clone_with_limit(&self, limit: usize) -> Self {
+        let mut options = self.options;
+        options.limit = Some(limit);
+        Self::with_options(self.items.clone(), options)
     }
 }
 
@@ -86,52 +62,27 @@ where
     S::IntoIter: DoubleEndedIterator + ExactSizeIterator,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let limit = self.limit();
-        let len = self.items.clone().into_iter().len();
-        let truncated = len > limit;
-        let options = &self.options;
-
-        let ell;
-        let ellipsis = if options.show_count && truncated {
-            ell = format!("{}({len} total)", options.ellipsis);
-            &ell
-        } else {
-            options.ellipsis
-        };
-
-        if limit == 0 {
-            return write!(f, "{}{ellipsis}{}", options.left_brace, options.right_brace);
-        }
-
-        write!(f, "{}", options.left_brace)?;
+        let options = self.options.resolved();
 
-        let (pre, suf, sep) = (options.elem_prefix, options.elem_suffix, options.separator);
-
-        if truncated {
-            let mut iter = self.items.clone().into_iter();
-
-            for _ in 0..(limit - 1) {
-                let item = iter.next().unwrap();
-                write!(f, "{pre}{item}{suf}{sep}")?;
-            }
-
-            write!(f, "{ellipsis}{sep}")?;
-            write!(
-                f,
-                "{pre}{}{suf}",
-                self.items.clone().into_iter().next_back().unwrap()
-            )?;
-        } else {
-            for (i, item) in self.items.clone().into_iter().enumerate() {
-                if i > 0 {
-                    write!(f, "{sep}")?;
-                }
-
-                write!(f, "{pre}{item}{suf}")?;
-            }
+        if let Some(width) = options.max_width {
+            let mut options = options;
+            options.max_width = None;
+            let s = Self::with_options(self.items.clone(), options).summarize(width);
+            return f.write_str(s.as_str());
         }
 
-        write!(f, "{}", options.right_brace)
+        options.write_seq(f, self.items.clone().into_iter())
+    }
+}
+
+impl<'a, T, S> fmt::Debug for DisplayIntoIter<'a, T, S>
+where
+    T: fmt::Display + 'a,
+    S: Clone + IntoIterator<Item = &'a T>,
+    S::IntoIter: DoubleEndedIterator + ExactSizeIterator,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
     }
 }
 
@@ -212,4 +163,118 @@ mod tests {
                 .to_string()
         );
     }
+
+    #[test]
+    fn test_display_into_iter_head_tail() {
+        let values = [1, 2, 3, 4, 5, 6, 7];
+
+        assert_eq!(
+            "[1,2,..,6,7]",
+            DisplayIntoIter::new(values.iter())
+                .head_tail(2, 2)
+                .to_string()
+        );
+        assert_eq!(
+            "[..(7 total),5,6,7]",
+            DisplayIntoIter::new(values.iter())
+                .head_tail(0, 3)
+                .show_count()
+                .to_string()
+        );
+        assert_eq!(
+            "[1,2,3,..]",
+            DisplayIntoIter::new(values.iter())
+                .head_tail(3, 0)
+                .to_string()
+        );
+        assert_eq!(
+            "[1,2,3,4,5,6,7]",
+            DisplayIntoIter::new(values.iter())
+                .head_tail(4, 3)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_into_iter_head_tail_overflow() {
+        let values = [1, 2, 3];
+
+        assert_eq!(
+            "[1,2,3]",
+            DisplayIntoIter::new(values.iter())
+                .head_tail(usize::MAX, 1)
+                .to_string()
+        );
+        assert_eq!(
+            "[1,2,3]",
+            DisplayIntoIter::new(values.iter())
+                .head_tail(usize::MAX, usize::MAX)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_into_iter_same_layout_in_all_formats() {
+        use crate::AsJsonExt;
+        use crate::AsYamlExt;
+
+        let values = [1, 2, 3, 4, 5, 6, 7];
+        let d = DisplayIntoIter::new(values.iter()).head_tail(2, 2);
+
+        assert_eq!("[1,2,..,6,7]", d.to_string());
+        assert_eq!(r#"["1","2","..","6","7"]"#, d.as_json().to_string());
+        assert_eq!("- 1\n- 2\n- ..\n- 6\n- 7", d.as_yaml().to_string());
+
+        let d = DisplayIntoIter::new(values.iter())
+            .head_tail(0, 1)
+            .show_count();
+
+        assert_eq!("[..(7 total),7]", d.to_string());
+        assert_eq!(r#"["..(7 total)","7"]"#, d.as_json().to_string());
+        assert_eq!("- ..(7 total)\n- 7", d.as_yaml().to_string());
+    }
+
+    /// An iterator that reports more elements than it yields.
+    #[derive(Clone)]
+    struct Overcounted<'a>(std::slice::Iter<'a, i32>);
+
+    impl<'a> Iterator for Overcounted<'a> {
+        type Item = &'a i32;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.next()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let n = self.0.len() + 10;
+            (n, Some(n))
+        }
+    }
+
+    impl DoubleEndedIterator for Overcounted<'_> {
+        fn next_back(&mut self) -> Option<Self::Item> {
+            self.0.next_back()
+        }
+    }
+
+    impl ExactSizeIterator for Overcounted<'_> {}
+
+    #[test]
+    fn test_display_into_iter_wrong_len_does_not_panic() {
+        let empty: [i32; 0] = [];
+        assert_eq!(
+            "[..]",
+            DisplayIntoIter::new(Overcounted(empty.iter()))
+                .at_most(Some(3))
+                .to_string()
+        );
+
+        let values = [1, 2];
+        assert_eq!(
+            "[1,2,..]",
+            DisplayIntoIter::new(Overcounted(values.iter()))
+                .at_most(Some(3))
+                .to_string()
+        );
+    }
 }
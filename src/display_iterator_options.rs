@@ -12,11 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use core::fmt;
+
+use crate::DisplayConfig;
+
+#[derive(Clone, Copy)]
 pub(crate) struct DisplayIteratorOptions<'a> {
-    /// The maximum number of elements to display. by default, it is 5.
+    /// The maximum number of elements to display. by default, it is `DisplayConfig::slice_limit`.
     pub(crate) limit: Option<usize>,
-    /// The separator between elements. by default, it is ",".
-    pub(crate) separator: &'a str,
+    /// The separator between elements. by default, it is `DisplayConfig::separator`.
+    pub(crate) separator: Option<&'a str>,
     /// The left brace. by default, it is "[".
     pub(crate) left_brace: &'a str,
     /// The right brace. by default, it is "]".
@@ -29,25 +34,239 @@ pub(crate) struct DisplayIteratorOptions<'a> {
     pub(crate) elem_suffix: &'a str,
     /// Whether to show the total count when truncated. by default, it is false.
     pub(crate) show_count: bool,
+    /// The max width of the output in chars. by default, it is unlimited.
+    pub(crate) max_width: Option<usize>,
+    /// The number of the displayed elements taken from the end when truncated. by default, it is 1.
+    pub(crate) tail: usize,
 }
 
 impl Default for DisplayIteratorOptions<'_> {
     fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> DisplayIteratorOptions<'a> {
+    pub(crate) const fn new() -> Self {
         Self {
             limit: None,
-            separator: ",",
+            separator: None,
             left_brace: "[",
             right_brace: "]",
             ellipsis: "..",
             elem_prefix: "",
             elem_suffix: "",
             show_count: false,
+            max_width: None,
+            tail: 1,
+        }
+    }
+
+    pub(crate) const fn at_most(self, limit: Option<usize>) -> Self {
+        Self { limit, ..self }
+    }
+
+    pub(crate) const fn sep(self, separator: &'a str) -> Self {
+        Self {
+            separator: Some(separator),
+            ..self
+        }
+    }
+
+    pub(crate) const fn braces(self, left: &'a str, right: &'a str) -> Self {
+        Self {
+            left_brace: left,
+            right_brace: right,
+            ..self
+        }
+    }
+
+    pub(crate) const fn ellipsis(self, ellipsis: &'a str) -> Self {
+        Self { ellipsis, ..self }
+    }
+
+    pub(crate) const fn elem(self, prefix: &'a str, suffix: &'a str) -> Self {
+        Self {
+            elem_prefix: prefix,
+            elem_suffix: suffix,
+            ..self
+        }
+    }
+
+    pub(crate) const fn show_count(self) -> Self {
+        Self {
+            show_count: true,
+            ..self
+        }
+    }
+
+    pub(crate) const fn fit_width(self, width: usize) -> Self {
+        Self {
+            max_width: Some(width),
+            ..self
+        }
+    }
+
+    pub(crate) const fn head_tail(self, head: usize, tail: usize) -> Self {
+        Self {
+            limit: Some(head.saturating_add(tail)),
+            tail,
+            ..self
+        }
+    }
+
+    /// Return a copy with the limit and the separator taken from [`DisplayConfig::current`] if
+    /// they are not set, so that formatting reads the config once rather than per use.
+    pub(crate) fn resolved(self) -> Self {
+        if self.limit.is_some() && self.separator.is_some() {
+            return self;
+        }
+
+        let config = DisplayConfig::current();
+        Self {
+            limit: Some(self.limit.unwrap_or(config.slice_limit)),
+            separator: Some(self.separator.unwrap_or(config.separator)),
+            ..self
         }
     }
-}
 
-impl DisplayIteratorOptions<'_> {
     pub(crate) fn limit(&self) -> usize {
-        self.limit.unwrap_or(5)
+        self.limit
+            .unwrap_or_else(|| DisplayConfig::current().slice_limit)
+    }
+
+    /// Return the number of leading and trailing elements to display, or `None` if all of the
+    /// `len` elements are displayed.
+    pub(crate) fn head_tail_of(&self, len: usize) -> Option<(usize, usize)> {
+        let limit = self.limit();
+        if len <= limit {
+            return None;
+        }
+
+        let tail = self.tail.min(limit);
+        Some((limit - tail, tail))
+    }
+
+    /// Return the elements to output for `iter`: the head elements, then the ellipsis and the
+    /// tail elements if it is truncated.
+    ///
+    /// Every output format, plain text, JSON, YAML or serde, is laid out by this.
+    pub(crate) fn layout<I: ExactSizeIterator>(&self, iter: I) -> Layout<'a, I> {
+        let len = iter.len();
+
+        match self.head_tail_of(len) {
+            None => Layout {
+                iter,
+                head: usize::MAX,
+                skip: 0,
+                ellipsis: None,
+            },
+            Some((head, tail)) => Layout {
+                iter,
+                head,
+                skip: len - head - tail,
+                ellipsis: Some(Ellipsis {
+                    s: self.ellipsis,
+                    total: self.show_count.then_some(len),
+                }),
+            },
+        }
+    }
+
+    /// Write the elements of `iter` in braces, laid out by [`layout`](Self::layout).
+    pub(crate) fn write_seq<I>(&self, f: &mut fmt::Formatter<'_>, iter: I) -> fmt::Result
+    where
+        I: ExactSizeIterator,
+        I::Item: fmt::Display,
+    {
+        let (pre, suf, sep) = (self.elem_prefix, self.elem_suffix, self.separator());
+
+        write!(f, "{}", self.left_brace)?;
+
+        for (i, elem) in self.layout(iter).enumerate() {
+            if i > 0 {
+                write!(f, "{sep}")?;
+            }
+            match elem {
+                Elem::Item(item) => write!(f, "{pre}{item}{suf}")?,
+                Elem::Ellipsis(e) => write!(f, "{e}")?,
+            }
+        }
+
+        write!(f, "{}", self.right_brace)
+    }
+
+    pub(crate) fn separator(&self) -> &'a str {
+        self.separator
+            .unwrap_or_else(|| DisplayConfig::current().separator)
+    }
+}
+
+/// The text in place of the omitted elements, such as `..` or `..(7 total)`.
+#[derive(Clone, Copy)]
+pub(crate) struct Ellipsis<'a> {
+    s: &'a str,
+    total: Option<usize>,
+}
+
+impl fmt::Display for Ellipsis<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.s)?;
+        if let Some(total) = self.total {
+            write!(f, "({total} total)")?;
+        }
+        Ok(())
+    }
+}
+
+/// An element output by [`Layout`].
+pub(crate) enum Elem<'a, T> {
+    Item(T),
+    Ellipsis(Ellipsis<'a>),
+}
+
+impl<T: fmt::Display> fmt::Display for Elem<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Elem::Item(x) => x.fmt(f),
+            Elem::Ellipsis(e) => e.fmt(f),
+        }
+    }
+}
+
+/// An iterator over the elements to output, built by [`DisplayIteratorOptions::layout`].
+///
+/// It reads the source once, and never panics if the source yields fewer elements than its
+/// `len()`.
+pub(crate) struct Layout<'a, I> {
+    iter: I,
+    /// The number of leading elements still to output.
+    head: usize,
+    /// The number of elements to skip after the ellipsis.
+    skip: usize,
+    /// The ellipsis still to output, if truncated.
+    ellipsis: Option<Ellipsis<'a>>,
+}
+
+impl<'a, I: Iterator> Iterator for Layout<'a, I> {
+    type Item = Elem<'a, I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.head > 0 {
+            self.head -= 1;
+            if let Some(x) = self.iter.next() {
+                return Some(Elem::Item(x));
+            }
+            self.head = 0;
+        }
+
+        if let Some(e) = self.ellipsis.take() {
+            if self.skip > 0 {
+                self.iter.nth(self.skip - 1);
+            }
+            return Some(Elem::Ellipsis(e));
+        }
+
+        self.iter.next().map(Elem::Item)
     }
 }
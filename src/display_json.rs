@@ -0,0 +1,240 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+use core::fmt::Write;
+
+use serde_json::Value;
+
+use crate::display_as_json::write_json_escaped;
+use crate::Summary;
+
+/// Display a bounded one-line preview of a JSON value.
+///
+/// Strings longer than `max_str` chars are cut and end with [`Summary::MARKER`], arrays and
+/// objects display at most `max_entries` entries followed by `..`, and arrays and objects
+/// deeper than `max_depth` are displayed as `[..]` or `{..}`.
+#[must_use]
+pub struct DisplayJsonSummary<'a> {
+    value: &'a Value,
+    max_str: usize,
+    max_entries: usize,
+    max_depth: usize,
+}
+
+impl<'a> DisplayJsonSummary<'a> {
+    pub const fn new(value: &'a Value) -> Self {
+        Self {
+            value,
+            max_str: 32,
+            max_entries: 5,
+            max_depth: 3,
+        }
+    }
+
+    /// Set the max number of chars of a string. By default it is 32.
+    pub const fn max_str(self, max_str: usize) -> Self {
+        Self { max_str, ..self }
+    }
+
+    /// Set the max number of entries of an array or object. By default it is 5.
+    pub const fn max_entries(self, max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            ..self
+        }
+    }
+
+    /// Set the max nesting depth of arrays and objects; the top level is depth 1. By default
+    /// it is 3.
+    pub const fn max_depth(self, max_depth: usize) -> Self {
+        Self { max_depth, ..self }
+    }
+
+    fn fmt_str(&self, f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+        f.write_char('"')?;
+        match s.char_indices().nth(self.max_str) {
+            None => write_json_escaped(f, s)?,
+            Some((end, _)) => {
+                write_json_escaped(f, &s[..end])?;
+                f.write_char(Summary::MARKER)?;
+            }
+        }
+        f.write_char('"')
+    }
+
+    fn fmt_value(&self, f: &mut fmt::Formatter<'_>, value: &Value, depth: usize) -> fmt::Result {
+        match value {
+            Value::String(s) => self.fmt_str(f, s),
+            Value::Array(a) => {
+                if a.is_empty() {
+                    return write!(f, "[]");
+                }
+                if depth >= self.max_depth {
+                    return write!(f, "[..]");
+                }
+
+                write!(f, "[")?;
+                for (i, v) in a.iter().take(self.max_entries).enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    self.fmt_value(f, v, depth + 1)?;
+                }
+                self.fmt_more(f, a.len())?;
+                write!(f, "]")
+            }
+            Value::Object(o) => {
+                if o.is_empty() {
+                    return write!(f, "{{}}");
+                }
+                if depth >= self.max_depth {
+                    return write!(f, "{{..}}");
+                }
+
+                write!(f, "{{")?;
+                for (i, (k, v)) in o.iter().take(self.max_entries).enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    f.write_char('"')?;
+                    write_json_escaped(f, k)?;
+                    f.write_str("\":")?;
+                    self.fmt_value(f, v, depth + 1)?;
+                }
+                self.fmt_more(f, o.len())?;
+                write!(f, "}}")
+            }
+            _ => write!(f, "{}", value),
+        }
+    }
+
+    /// Output `..` if some of the `len` entries are omitted.
+    fn fmt_more(&self, f: &mut fmt::Formatter<'_>, len: usize) -> fmt::Result {
+        match len.cmp(&self.max_entries) {
+            core::cmp::Ordering::Greater if self.max_entries == 0 => write!(f, ".."),
+            core::cmp::Ordering::Greater => write!(f, ",.."),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for DisplayJsonSummary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_value(f, self.value, 0)
+    }
+}
+
+impl fmt::Debug for DisplayJsonSummary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a bounded one-line preview of a `serde_json::Value`.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayJsonSummaryExt;
+/// use serde_json::json;
+///
+/// let v = json!({
+///     "id": 1,
+///     "tags": ["a", "b", "c", "d", "e", "f"],
+///     "body": "a very long text that nobody wants to see in a log line",
+/// });
+/// assert_eq!(
+///     v.display_json_summary().to_string(),
+///     r#"{"body":"a very long text that nobody wan…","id":1,"tags":["a","b","c","d","e",..]}"#
+/// );
+/// ```
+pub trait DisplayJsonSummaryExt {
+    fn display_json_summary(&self) -> DisplayJsonSummary<'_>;
+}
+
+impl DisplayJsonSummaryExt for Value {
+    fn display_json_summary(&self) -> DisplayJsonSummary<'_> {
+        DisplayJsonSummary::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_display_json_summary_scalars() {
+        assert_eq!("null", json!(null).display_json_summary().to_string());
+        assert_eq!("true", json!(true).display_json_summary().to_string());
+        assert_eq!("1.5", json!(1.5).display_json_summary().to_string());
+        assert_eq!(
+            r#""a\"b""#,
+            json!("a\"b").display_json_summary().to_string()
+        );
+        assert_eq!(
+            r#""abc…""#,
+            json!("abcdef")
+                .display_json_summary()
+                .max_str(3)
+                .to_string()
+        );
+        assert_eq!(
+            r#""abc""#,
+            json!("abc").display_json_summary().max_str(3).to_string()
+        );
+        assert_eq!(
+            r#"{"k\"\n":"中文\t…"}"#,
+            json!({"k\"\n": "中文\tab"})
+                .display_json_summary()
+                .max_str(3)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_json_summary_entries() {
+        let v = json!([1, 2, 3]);
+        assert_eq!("[1,2,3]", v.display_json_summary().to_string());
+        assert_eq!(
+            "[1,2,..]",
+            v.display_json_summary().max_entries(2).to_string()
+        );
+        assert_eq!("[..]", v.display_json_summary().max_entries(0).to_string());
+        assert_eq!("[]", json!([]).display_json_summary().to_string());
+
+        let v = json!({"a": 1, "b": 2});
+        assert_eq!(
+            r#"{"a":1,..}"#,
+            v.display_json_summary().max_entries(1).to_string()
+        );
+        assert_eq!("{}", json!({}).display_json_summary().to_string());
+    }
+
+    #[test]
+    fn test_display_json_summary_depth() {
+        let v = json!({"a": {"b": {"c": [1]}}, "x": []});
+        assert_eq!(
+            r#"{"a":{"b":{"c":[..]}},"x":[]}"#,
+            v.display_json_summary().to_string()
+        );
+        assert_eq!(
+            r#"{"a":{..},"x":[]}"#,
+            v.display_json_summary().max_depth(1).to_string()
+        );
+        assert_eq!("{..}", v.display_json_summary().max_depth(0).to_string());
+    }
+}
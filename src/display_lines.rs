@@ -0,0 +1,300 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::string::ToString;
+use core::fmt;
+use core::fmt::Write;
+
+/// A `fmt::Write` that calls `on_line_start` before the first char of every line.
+///
+/// No call is made for the empty line after a trailing `'\n'`.
+pub(crate) struct LineWriter<'a, 'f, F>
+where F: FnMut(&mut fmt::Formatter<'f>) -> fmt::Result
+{
+    f: &'a mut fmt::Formatter<'f>,
+    at_line_start: bool,
+    on_line_start: F,
+}
+
+impl<'a, 'f, F> LineWriter<'a, 'f, F>
+where F: FnMut(&mut fmt::Formatter<'f>) -> fmt::Result
+{
+    pub(crate) const fn new(f: &'a mut fmt::Formatter<'f>, on_line_start: F) -> Self {
+        Self {
+            f,
+            at_line_start: true,
+            on_line_start,
+        }
+    }
+}
+
+impl<'f, F> Write for LineWriter<'_, 'f, F>
+where F: FnMut(&mut fmt::Formatter<'f>) -> fmt::Result
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for line in s.split_inclusive('\n') {
+            if self.at_line_start {
+                (self.on_line_start)(self.f)?;
+            }
+            self.f.write_str(line)?;
+            self.at_line_start = line.ends_with('\n');
+        }
+        Ok(())
+    }
+}
+
+/// Prefix every line of the inner `Display` output with a right-aligned line number,
+/// such as `  12 | `.
+#[must_use]
+pub struct DisplayLineNumbers<D: fmt::Display> {
+    inner: D,
+    start: usize,
+}
+
+impl<D: fmt::Display> DisplayLineNumbers<D> {
+    pub const fn new(inner: D) -> Self {
+        Self { inner, start: 1 }
+    }
+
+    /// Set the number of the first line. By default it is 1.
+    pub const fn start_at(mut self, start: usize) -> Self {
+        self.start = start;
+        self
+    }
+}
+
+impl<D: fmt::Display> fmt::Display for DisplayLineNumbers<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut n = self.start;
+        let mut w = LineWriter::new(f, |f| {
+            let res = write!(f, "{:>4} | ", n);
+            n += 1;
+            res
+        });
+        write!(w, "{}", self.inner)
+    }
+}
+
+impl<D: fmt::Display> fmt::Debug for DisplayLineNumbers<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Prepend a string to every line of the inner `Display` output.
+#[must_use]
+pub struct DisplayLinePrefix<'a, D: fmt::Display> {
+    inner: D,
+    prefix: &'a str,
+}
+
+impl<'a, D: fmt::Display> DisplayLinePrefix<'a, D> {
+    pub const fn new(inner: D, prefix: &'a str) -> Self {
+        Self { inner, prefix }
+    }
+}
+
+impl<D: fmt::Display> fmt::Display for DisplayLinePrefix<'_, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut w = LineWriter::new(f, |f| f.write_str(self.prefix));
+        write!(w, "{}", self.inner)
+    }
+}
+
+impl<D: fmt::Display> fmt::Debug for DisplayLinePrefix<'_, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Soft-wrap the inner `Display` output at word boundaries so that lines do not exceed `width`
+/// chars.
+///
+/// Lines that already fit are output unchanged. Words in a wrapped line are separated by a single
+/// space, and a word longer than `width` is put on a line of its own without being broken.
+#[must_use]
+pub struct DisplayWrap<D: fmt::Display> {
+    inner: D,
+    width: usize,
+}
+
+impl<D: fmt::Display> DisplayWrap<D> {
+    pub const fn new(inner: D, width: usize) -> Self {
+        Self { inner, width }
+    }
+}
+
+impl<D: fmt::Display> fmt::Display for DisplayWrap<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = self.inner.to_string();
+
+        for (i, line) in text.split('\n').enumerate() {
+            if i > 0 {
+                f.write_char('\n')?;
+            }
+
+            if line.chars().count() <= self.width {
+                f.write_str(line)?;
+                continue;
+            }
+
+            let mut col = 0;
+            for word in line.split_whitespace() {
+                let len = word.chars().count();
+                if col > 0 {
+                    if col + 1 + len > self.width {
+                        f.write_char('\n')?;
+                        col = 0;
+                    } else {
+                        f.write_char(' ')?;
+                        col += 1;
+                    }
+                }
+                f.write_str(word)?;
+                col += len;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<D: fmt::Display> fmt::Debug for DisplayWrap<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Line-oriented adapters for multi-line `Display` values.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayLinesExt;
+///
+/// let sql = "SELECT *\nFROM t";
+/// assert_eq!(
+///     sql.with_line_numbers().to_string(),
+///     "   1 | SELECT *\n   2 | FROM t"
+/// );
+/// assert_eq!(sql.line_prefix("│ ").to_string(), "│ SELECT *\n│ FROM t");
+/// assert_eq!(sql.wrap_at(6).to_string(), "SELECT\n*\nFROM t");
+/// ```
+pub trait DisplayLinesExt: fmt::Display {
+    /// Prefix every line with its line number.
+    fn with_line_numbers(&self) -> DisplayLineNumbers<&Self> {
+        DisplayLineNumbers::new(self)
+    }
+
+    /// Prepend `prefix` to every line.
+    fn line_prefix<'a>(&'a self, prefix: &'a str) -> DisplayLinePrefix<'a, &'a Self> {
+        DisplayLinePrefix::new(self, prefix)
+    }
+
+    /// Soft-wrap lines longer than `width` chars at word boundaries.
+    fn wrap_at(&self, width: usize) -> DisplayWrap<&Self> {
+        DisplayWrap::new(self, width)
+    }
+}
+
+impl<T> DisplayLinesExt for T where T: fmt::Display + ?Sized {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_line_numbers() {
+        assert_eq!("", "".with_line_numbers().to_string());
+        assert_eq!("   1 | a", "a".with_line_numbers().to_string());
+        assert_eq!(
+            "   1 | a\n   2 | \n   3 | b\n",
+            "a\n\nb\n".with_line_numbers().to_string()
+        );
+        assert_eq!("   1 | \n   2 | x", "\nx".with_line_numbers().to_string());
+    }
+
+    #[test]
+    fn test_with_line_numbers_start_at() {
+        let text = format!("{}\n{}", 1, 2);
+        assert_eq!(
+            "   9 | 1\n  10 | 2",
+            text.with_line_numbers().start_at(9).to_string()
+        );
+        assert_eq!(
+            "1000 | 1\n1001 | 2",
+            text.with_line_numbers().start_at(1000).to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_line_numbers_split_writes() {
+        struct Parts;
+
+        impl fmt::Display for Parts {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "ab")?;
+                writeln!(f, "c")?;
+                write!(f, "\nd")
+            }
+        }
+
+        assert_eq!(
+            "   1 | abc\n   2 | \n   3 | d",
+            Parts.with_line_numbers().to_string()
+        );
+    }
+
+    #[test]
+    fn test_line_prefix() {
+        assert_eq!("", "".line_prefix("> ").to_string());
+        assert_eq!("> a\n> \n> b\n", "a\n\nb\n".line_prefix("> ").to_string());
+
+        // Nested
+        let inner = "x\ny".line_prefix("│ ");
+        assert_eq!(
+            "report:\n  │ x\n  │ y",
+            format!("report:\n{}", inner.line_prefix("  "))
+        );
+
+        // Combined with line numbers
+        assert_eq!(
+            "#   1 | a\n#   2 | b",
+            "a\nb".with_line_numbers().line_prefix("#").to_string()
+        );
+    }
+
+    #[test]
+    fn test_wrap_at() {
+        let text = "the quick brown fox jumps over the lazy dog";
+        assert_eq!(
+            "the quick\nbrown fox\njumps over\nthe lazy\ndog",
+            text.wrap_at(10).to_string()
+        );
+        assert_eq!(text, text.wrap_at(80).to_string());
+
+        // Long words are not broken
+        assert_eq!("a\nabcdefghij\nb", "a abcdefghij b".wrap_at(4).to_string());
+
+        // Existing line breaks and short indented lines are kept
+        assert_eq!(
+            "  short\n\nfoo bar\nbaz",
+            "  short\n\nfoo bar baz".wrap_at(8).to_string()
+        );
+
+        // Width is counted in chars
+        assert_eq!("ééé ééé\nééé", "ééé ééé ééé".wrap_at(7).to_string());
+
+        assert_eq!("", "".wrap_at(0).to_string());
+    }
+}
@@ -0,0 +1,177 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Mutex;
+use std::sync::RwLock;
+use std::sync::TryLockError;
+
+/// Display the value in a `Mutex` if it can be locked without blocking, otherwise `<locked>`.
+///
+/// A poisoned value is displayed followed by ` (poisoned)`.
+#[must_use]
+pub struct DisplayMutex<'a, T: ?Sized> {
+    inner: &'a Mutex<T>,
+}
+
+impl<'a, T: ?Sized> DisplayMutex<'a, T> {
+    pub const fn new(inner: &'a Mutex<T>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T: fmt::Display + ?Sized> fmt::Display for DisplayMutex<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.inner.try_lock() {
+            Ok(guard) => write!(f, "{}", &*guard),
+            Err(TryLockError::Poisoned(e)) => write!(f, "{} (poisoned)", &**e.get_ref()),
+            Err(TryLockError::WouldBlock) => write!(f, "<locked>"),
+        }
+    }
+}
+
+impl<T: fmt::Display + ?Sized> fmt::Debug for DisplayMutex<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display the value in a `RwLock` if it can be read without blocking, otherwise `<locked>`.
+///
+/// A poisoned value is displayed followed by ` (poisoned)`.
+#[must_use]
+pub struct DisplayRwLock<'a, T: ?Sized> {
+    inner: &'a RwLock<T>,
+}
+
+impl<'a, T: ?Sized> DisplayRwLock<'a, T> {
+    pub const fn new(inner: &'a RwLock<T>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T: fmt::Display + ?Sized> fmt::Display for DisplayRwLock<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.inner.try_read() {
+            Ok(guard) => write!(f, "{}", &*guard),
+            Err(TryLockError::Poisoned(e)) => write!(f, "{} (poisoned)", &**e.get_ref()),
+            Err(TryLockError::WouldBlock) => write!(f, "<locked>"),
+        }
+    }
+}
+
+impl<T: fmt::Display + ?Sized> fmt::Debug for DisplayRwLock<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display the value in a `Mutex` without blocking, so that it is safe to use in a formatter
+/// that may run while the lock is held.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::Mutex;
+///
+/// use display_more::DisplayMutexExt;
+///
+/// let m = Mutex::new(5);
+/// assert_eq!(m.display().to_string(), "5");
+///
+/// let _guard = m.lock().unwrap();
+/// assert_eq!(m.display().to_string(), "<locked>");
+/// ```
+pub trait DisplayMutexExt<T: ?Sized> {
+    fn display(&self) -> DisplayMutex<'_, T>;
+}
+
+impl<T: ?Sized> DisplayMutexExt<T> for Mutex<T> {
+    fn display(&self) -> DisplayMutex<'_, T> {
+        DisplayMutex::new(self)
+    }
+}
+
+/// Display the value in a `RwLock` without blocking, so that it is safe to use in a formatter
+/// that may run while the lock is held for writing.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::RwLock;
+///
+/// use display_more::DisplayRwLockExt;
+///
+/// let l = RwLock::new("a");
+/// assert_eq!(l.display().to_string(), "a");
+///
+/// let _guard = l.write().unwrap();
+/// assert_eq!(l.display().to_string(), "<locked>");
+/// ```
+pub trait DisplayRwLockExt<T: ?Sized> {
+    fn display(&self) -> DisplayRwLock<'_, T>;
+}
+
+impl<T: ?Sized> DisplayRwLockExt<T> for RwLock<T> {
+    fn display(&self) -> DisplayRwLock<'_, T> {
+        DisplayRwLock::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::string::ToString;
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[test]
+    fn test_display_mutex() {
+        let m = Mutex::new(1);
+        assert_eq!("1", m.display().to_string());
+
+        {
+            let _g = m.lock().unwrap();
+            assert_eq!("<locked>", m.display().to_string());
+        }
+        assert_eq!("1", m.display().to_string());
+    }
+
+    #[test]
+    fn test_display_mutex_poisoned() {
+        let m = Arc::new(Mutex::new(2));
+        let m2 = m.clone();
+        let _ = std::thread::spawn(move || {
+            let _g = m2.lock().unwrap();
+            panic!("poison");
+        })
+        .join();
+        assert_eq!("2 (poisoned)", m.display().to_string());
+    }
+
+    #[test]
+    fn test_display_rwlock() {
+        let l = RwLock::new("x");
+        assert_eq!("x", l.display().to_string());
+
+        {
+            let _r = l.read().unwrap();
+            assert_eq!("x", l.display().to_string());
+        }
+        {
+            let _w = l.write().unwrap();
+            assert_eq!("<locked>", l.display().to_string());
+        }
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::cell::Cell;
+use core::fmt;
+
+/// Implement `Display` with a `FnOnce` closure, so that values can be moved into it.
+///
+/// The closure is consumed by the first formatting. Any later formatting outputs
+/// [`DisplayOnce::CONSUMED`] instead.
+#[must_use]
+pub struct DisplayOnce<F>
+where F: FnOnce(&mut fmt::Formatter<'_>) -> fmt::Result
+{
+    f: Cell<Option<F>>,
+}
+
+impl<F> DisplayOnce<F>
+where F: FnOnce(&mut fmt::Formatter<'_>) -> fmt::Result
+{
+    /// The marker output when formatted more than once.
+    pub const CONSUMED: &'static str = "<consumed>";
+
+    pub const fn new(f: F) -> Self {
+        Self {
+            f: Cell::new(Some(f)),
+        }
+    }
+}
+
+impl<F> fmt::Display for DisplayOnce<F>
+where F: FnOnce(&mut fmt::Formatter<'_>) -> fmt::Result
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.f.take() {
+            Some(func) => func(f),
+            None => write!(f, "{}", Self::CONSUMED),
+        }
+    }
+}
+
+impl<F> fmt::Debug for DisplayOnce<F>
+where F: FnOnce(&mut fmt::Formatter<'_>) -> fmt::Result
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Create a `Display` value from a `FnOnce` closure.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::display_once;
+///
+/// let name = String::from("foo");
+/// let d = display_once(move |f| write!(f, "name: {}", name));
+/// assert_eq!(d.to_string(), "name: foo");
+/// assert_eq!(d.to_string(), "<consumed>");
+/// ```
+pub const fn display_once<F>(f: F) -> DisplayOnce<F>
+where F: FnOnce(&mut fmt::Formatter<'_>) -> fmt::Result {
+    DisplayOnce::new(f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NotClone(u64);
+
+    #[test]
+    fn test_display_once() {
+        let v = NotClone(3);
+        let d = display_once(move |f| write!(f, "v={}", v.0));
+        assert_eq!("v=3", d.to_string());
+        assert_eq!("<consumed>", d.to_string());
+        assert_eq!("<consumed>", format!("{:?}", d));
+    }
+
+    #[test]
+    fn test_display_once_with_formatter_options() {
+        let d = display_once(|f| write!(f, "{}", f.alternate()));
+        assert_eq!("true", format!("{:#}", d));
+    }
+}
@@ -12,30 +12,92 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt;
+use core::fmt;
+
+use crate::DisplayConfig;
 
 /// Wrapper that implements `Display` for `Option<T>` using either `Display` or `Debug` formatting.
 ///
-/// It outputs a literal string `"None"` if it is None. Otherwise it invokes the stored
-/// formatting function for T.
+/// It outputs the [`DisplayConfig`] placeholder, `"None"` by default, if it is None. Otherwise it
+/// invokes the stored formatting function for T.
+#[must_use]
 pub struct DisplayOption<'a, T> {
-    inner: &'a Option<T>,
+    pub(crate) inner: &'a Option<T>,
     fmt_fn: fn(&T, &mut fmt::Formatter<'_>) -> fmt::Result,
 }
 
 impl<T> fmt::Display for DisplayOption<'_, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self.inner {
-            None => write!(f, "None"),
+            None => write_styled!(f, Dim, "{}", DisplayConfig::current().none),
             Some(x) => (self.fmt_fn)(x, f),
         }
     }
 }
 
+impl<T> fmt::Debug for DisplayOption<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Owned counterpart of [`DisplayOption`] that can be stored in a struct or moved to another
+/// thread.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayOptionOwned;
+///
+/// let d = DisplayOptionOwned::new(Some(1));
+/// let s = std::thread::spawn(move || d.to_string()).join().unwrap();
+/// assert_eq!(s, "1");
+/// ```
+#[must_use]
+pub struct DisplayOptionOwned<T> {
+    pub(crate) inner: Option<T>,
+    fmt_fn: fn(&T, &mut fmt::Formatter<'_>) -> fmt::Result,
+}
+
+impl<T: fmt::Display> DisplayOptionOwned<T> {
+    pub const fn new(inner: Option<T>) -> Self {
+        Self {
+            inner,
+            fmt_fn: <T as fmt::Display>::fmt,
+        }
+    }
+}
+
+impl<T: fmt::Debug> DisplayOptionOwned<T> {
+    /// Create an owned display that formats `T` with `Debug`.
+    pub const fn new_debug(inner: Option<T>) -> Self {
+        Self {
+            inner,
+            fmt_fn: <T as fmt::Debug>::fmt,
+        }
+    }
+}
+
+impl<T> fmt::Display for DisplayOptionOwned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        DisplayOption {
+            inner: &self.inner,
+            fmt_fn: self.fmt_fn,
+        }
+        .fmt(f)
+    }
+}
+
+impl<T> fmt::Debug for DisplayOptionOwned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
 /// Implement `Display` for `Option<T>` if T is `Display`.
 ///
-/// It outputs a literal string `"None"` if it is None. Otherwise it invokes the Display
-/// implementation for T.
+/// It outputs the [`DisplayConfig`] placeholder, `"None"` by default, if it is None. Otherwise it
+/// invokes the Display implementation for T.
 ///
 /// # Example
 ///
@@ -47,12 +109,17 @@ impl<T> fmt::Display for DisplayOption<'_, T> {
 /// ```
 pub trait DisplayOptionExt<'a, T: fmt::Display> {
     fn display(&'a self) -> DisplayOption<'a, T>;
+
+    /// Same as [`display()`](Self::display); use it when another `display()` is in scope.
+    fn display_opt(&'a self) -> DisplayOption<'a, T> {
+        self.display()
+    }
 }
 
 impl<T> DisplayOptionExt<'_, T> for Option<T>
 where T: fmt::Display
 {
-    fn display(&self) -> DisplayOption<T> {
+    fn display(&self) -> DisplayOption<'_, T> {
         DisplayOption {
             inner: self,
             fmt_fn: <T as fmt::Display>::fmt,
@@ -77,7 +144,7 @@ pub trait DisplayDebugOptionExt<'a, T: fmt::Debug> {
 impl<T> DisplayDebugOptionExt<'_, T> for Option<T>
 where T: fmt::Debug
 {
-    fn display_debug(&self) -> DisplayOption<T> {
+    fn display_debug(&self) -> DisplayOption<'_, T> {
         DisplayOption {
             inner: self,
             fmt_fn: <T as fmt::Debug>::fmt,
@@ -93,6 +160,7 @@ mod tests {
     fn test_display_option() {
         let option = Some(1);
         assert_eq!(option.display().to_string(), "1");
+        assert_eq!(option.display_opt().to_string(), "1");
     }
 
     #[test]
@@ -110,4 +178,32 @@ mod tests {
         // Vec has Debug but not Display
         assert_eq!(Some(vec![1, 2, 3]).display_debug().to_string(), "[1, 2, 3]");
     }
+
+    #[test]
+    fn test_display_option_owned() {
+        assert_eq!(DisplayOptionOwned::new(Some(1)).to_string(), "1");
+        assert_eq!(DisplayOptionOwned::new(None::<u64>).to_string(), "None");
+        assert_eq!(
+            DisplayOptionOwned::new_debug(Some("hello")).to_string(),
+            "\"hello\""
+        );
+        assert_eq!(
+            DisplayOptionOwned::new_debug(None::<Vec<u8>>).to_string(),
+            "None"
+        );
+    }
+
+    #[test]
+    fn test_display_option_debug() {
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        struct Foo<'a> {
+            a: DisplayOption<'a, u64>,
+        }
+
+        let a = Some(1);
+        assert_eq!(format!("{:?}", a.display()), "1");
+        assert_eq!(format!("{:?}", Foo { a: a.display() }), "Foo { a: 1 }");
+        assert_eq!(format!("{:?}", None::<u64>.display()), "None");
+    }
 }
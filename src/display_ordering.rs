@@ -0,0 +1,109 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::cmp::Ordering;
+use core::fmt;
+
+/// Display an `Ordering` as `<`, `=` and `>`, or as `less`, `equal` and `greater`.
+#[must_use]
+pub struct DisplayOrdering {
+    pub(crate) ordering: Ordering,
+    pub(crate) words: bool,
+}
+
+impl DisplayOrdering {
+    pub const fn new(ordering: Ordering) -> Self {
+        Self {
+            ordering,
+            words: false,
+        }
+    }
+
+    /// Display `less`, `equal` or `greater`.
+    pub const fn words(self) -> Self {
+        Self {
+            words: true,
+            ..self
+        }
+    }
+
+    /// Display `<`, `=` or `>`. This is the default.
+    pub const fn symbols(self) -> Self {
+        Self {
+            words: false,
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for DisplayOrdering {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match (self.ordering, self.words) {
+            (Ordering::Less, false) => "<",
+            (Ordering::Equal, false) => "=",
+            (Ordering::Greater, false) => ">",
+            (Ordering::Less, true) => "less",
+            (Ordering::Equal, true) => "equal",
+            (Ordering::Greater, true) => "greater",
+        };
+        f.write_str(s)
+    }
+}
+
+impl fmt::Debug for DisplayOrdering {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a comparison result as a symbol or a word instead of `Less` or `Greater`.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayOrderingExt;
+///
+/// assert_eq!(1.cmp(&2).display().to_string(), "<");
+/// assert_eq!(2.cmp(&1).display().words().to_string(), "greater");
+/// ```
+pub trait DisplayOrderingExt {
+    fn display(&self) -> DisplayOrdering;
+}
+
+impl DisplayOrderingExt for Ordering {
+    fn display(&self) -> DisplayOrdering {
+        DisplayOrdering::new(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_display_ordering() {
+        assert_eq!("<", Ordering::Less.display().to_string());
+        assert_eq!("=", Ordering::Equal.display().to_string());
+        assert_eq!(">", Ordering::Greater.display().to_string());
+        assert_eq!("less", Ordering::Less.display().words().to_string());
+        assert_eq!("equal", Ordering::Equal.display().words().to_string());
+        assert_eq!("greater", Ordering::Greater.display().words().to_string());
+        assert_eq!(
+            ">",
+            Ordering::Greater.display().words().symbols().to_string()
+        );
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+
+/// Display two values joined with a separator, such as `7-120034` for a `(term, index)` key.
+///
+/// The default separator is `-`, see [`sep`](Self::sep). It is the owning counterpart of
+/// [`display_with`](crate::DisplayTupleExt::display_with), which borrows a tuple.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayPair;
+///
+/// assert_eq!(DisplayPair::new(7, 120034).to_string(), "7-120034");
+/// assert_eq!(DisplayPair::new("n1", 3).sep(":").to_string(), "n1:3");
+/// ```
+#[must_use]
+pub struct DisplayPair<'a, A, B> {
+    a: A,
+    b: B,
+    sep: &'a str,
+}
+
+impl<'a, A, B> DisplayPair<'a, A, B> {
+    pub const fn new(a: A, b: B) -> Self {
+        Self { a, b, sep: "-" }
+    }
+
+    /// Set the separator between the two values.
+    pub const fn sep(mut self, sep: &'a str) -> Self {
+        self.sep = sep;
+        self
+    }
+}
+
+impl<A: fmt::Display, B: fmt::Display> fmt::Display for DisplayPair<'_, A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.a, self.sep, self.b)
+    }
+}
+
+impl<A: fmt::Display, B: fmt::Display> fmt::Debug for DisplayPair<'_, A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_display_pair() {
+        assert_eq!("1-2", DisplayPair::new(1, 2).to_string());
+        assert_eq!("a.b", DisplayPair::new("a", "b").sep(".").to_string());
+        assert_eq!("ab", DisplayPair::new("a", 'b').sep("").to_string());
+        assert_eq!(
+            "1-2/3",
+            DisplayPair::new(DisplayPair::new(1, 2), 3)
+                .sep("/")
+                .to_string()
+        );
+
+        let sep = String::from("::");
+        assert_eq!("a::b", DisplayPair::new("a", "b").sep(&sep).to_string());
+    }
+}
@@ -0,0 +1,189 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::fmt::Write;
+use std::path::Path;
+use std::path::MAIN_SEPARATOR;
+
+/// Display a path without losing information, unlike `Path::display()`.
+///
+/// - Bytes that are not valid UTF-8 are displayed as `\xNN` instead of being replaced with `�`.
+/// - Control chars are escaped, such as `\n`.
+/// - A path that contains a space is double-quoted, with `"` escaped as `\"`.
+///
+/// Call [`tilde`](Self::tilde) to abbreviate the home directory to `~`.
+#[must_use]
+pub struct DisplayPath<'a> {
+    path: &'a Path,
+    home: Option<Cow<'a, Path>>,
+}
+
+impl<'a> DisplayPath<'a> {
+    pub fn new(path: &'a Path) -> Self {
+        Self { path, home: None }
+    }
+
+    /// Abbreviate the home directory, as found in the `HOME` or `USERPROFILE` environment
+    /// variable, to `~`.
+    pub fn tilde(self) -> Self {
+        let home = std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .filter(|h| !h.is_empty());
+        Self {
+            home: home.map(|h| Cow::Owned(h.into())),
+            ..self
+        }
+    }
+
+    /// Abbreviate the given home directory to `~`.
+    pub fn tilde_from(self, home: &'a Path) -> Self {
+        Self {
+            home: Some(Cow::Borrowed(home)),
+            ..self
+        }
+    }
+
+    /// Return `~` or an empty string, and the rest of the path to display.
+    fn split_home(&self) -> (&'static str, &'a [u8]) {
+        let rest = self
+            .home
+            .as_ref()
+            .and_then(|home| self.path.strip_prefix(home).ok());
+
+        match rest {
+            Some(rest) => ("~", rest.as_os_str().as_encoded_bytes()),
+            None => ("", self.path.as_os_str().as_encoded_bytes()),
+        }
+    }
+
+    fn write_escaped(f: &mut fmt::Formatter<'_>, bytes: &[u8], quoted: bool) -> fmt::Result {
+        for chunk in bytes.utf8_chunks() {
+            for c in chunk.valid().chars() {
+                if c == '"' && quoted {
+                    f.write_str("\\\"")?;
+                } else if c.is_control() {
+                    write!(f, "{}", c.escape_default())?;
+                } else {
+                    f.write_char(c)?;
+                }
+            }
+            for b in chunk.invalid() {
+                write!(f, "\\x{:02X}", b)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DisplayPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (tilde, rest) = self.split_home();
+        let quoted = rest.contains(&b' ');
+
+        if quoted {
+            f.write_char('"')?;
+        }
+        f.write_str(tilde)?;
+        if !tilde.is_empty() && !rest.is_empty() {
+            f.write_char(MAIN_SEPARATOR)?;
+        }
+        Self::write_escaped(f, rest, quoted)?;
+        if quoted {
+            f.write_char('"')?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for DisplayPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a `Path` or `PathBuf` with quoting and visible escaping of non-UTF-8 bytes.
+///
+/// # Example
+///
+/// ```rust
+/// use std::path::Path;
+///
+/// use display_more::DisplayPathExt;
+///
+/// assert_eq!(
+///     Path::new("/tmp/a.txt").display_path().to_string(),
+///     "/tmp/a.txt"
+/// );
+/// assert_eq!(
+///     Path::new("/tmp/my file").display_path().to_string(),
+///     r#""/tmp/my file""#
+/// );
+/// assert_eq!(
+///     Path::new("/home/u/src")
+///         .display_path()
+///         .tilde_from(Path::new("/home/u"))
+///         .to_string(),
+///     "~/src"
+/// );
+/// ```
+pub trait DisplayPathExt {
+    fn display_path(&self) -> DisplayPath<'_>;
+}
+
+impl DisplayPathExt for Path {
+    fn display_path(&self) -> DisplayPath<'_> {
+        DisplayPath::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_display_path() {
+        let p = |s: &str| Path::new(s).display_path().to_string();
+        assert_eq!("a/b", p("a/b"));
+        assert_eq!("", p(""));
+        assert_eq!(r#""a b/\"c\"""#, p("a b/\"c\""));
+        assert_eq!("a\\nb", p("a\nb"));
+        assert_eq!("é", PathBuf::from("é").display_path().to_string());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_display_path_non_utf8() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let p = Path::new(OsStr::from_bytes(b"a\xffb\xe2\x82"));
+        assert_eq!("a\\xFFb\\xE2\\x82", p.display_path().to_string());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_display_path_tilde() {
+        let home = Path::new("/home/u");
+        let p = |s: &str| Path::new(s).display_path().tilde_from(home).to_string();
+        assert_eq!("~", p("/home/u"));
+        assert_eq!("~/a/b", p("/home/u/a/b"));
+        assert_eq!(r#""~/my docs""#, p("/home/u/my docs"));
+        assert_eq!("/home/user", p("/home/user"));
+        assert_eq!("/tmp", p("/tmp"));
+    }
+}
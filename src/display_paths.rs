@@ -0,0 +1,241 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::path::MAIN_SEPARATOR;
+
+use crate::display_iterator_options::DisplayIteratorOptions;
+use crate::DisplayPath;
+
+/// Display a list of paths as their common ancestor followed by the remainders, such as
+/// `/data/db1/: [t1/seg_01,t1/seg_02,..,t9/seg_88]`.
+///
+/// If the paths have no common ancestor they are displayed in full, as `[a,b]`.
+///
+/// The paths are displayed with [`DisplayPath`], and truncated the same way as
+/// [`DisplaySlice`](crate::display_slice::DisplaySlice); by default the limit and the separator
+/// are those of [`DisplayConfig`](crate::DisplayConfig).
+#[must_use]
+pub struct DisplayPaths<'a, P> {
+    paths: &'a [P],
+    options: DisplayIteratorOptions<'a>,
+}
+
+impl<'a, P: AsRef<Path>> DisplayPaths<'a, P> {
+    pub const fn new(paths: &'a [P]) -> Self {
+        Self {
+            paths,
+            options: DisplayIteratorOptions::new(),
+        }
+    }
+
+    /// Set the max number of paths to display.
+    pub const fn limit(self, limit: usize) -> Self {
+        Self {
+            options: self.options.at_most(Some(limit)),
+            ..self
+        }
+    }
+
+    pub const fn sep(self, separator: &'a str) -> Self {
+        Self {
+            options: self.options.sep(separator),
+            ..self
+        }
+    }
+
+    pub const fn ellipsis(self, s: &'a str) -> Self {
+        Self {
+            options: self.options.ellipsis(s),
+            ..self
+        }
+    }
+
+    /// Display the first `head` and the last `tail` paths when truncated.
+    pub const fn head_tail(self, head: usize, tail: usize) -> Self {
+        Self {
+            options: self.options.head_tail(head, tail),
+            ..self
+        }
+    }
+
+    /// Return the longest common ancestor of all of the paths.
+    fn common_ancestor(&self) -> PathBuf {
+        let mut common: Vec<Component<'_>> = Vec::new();
+
+        for (i, p) in self.paths.iter().enumerate() {
+            let parent = p.as_ref().parent().unwrap_or(Path::new(""));
+            if i == 0 {
+                common = parent.components().collect();
+                continue;
+            }
+            let n = common
+                .iter()
+                .zip(parent.components())
+                .take_while(|(a, b)| *a == b)
+                .count();
+            common.truncate(n);
+        }
+        common.iter().collect()
+    }
+}
+
+impl<P: AsRef<Path>> fmt::Display for DisplayPaths<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let options = self.options.resolved();
+
+        let prefix = self.common_ancestor();
+        let prefix_bytes = prefix.as_os_str().as_encoded_bytes();
+        if let Some(&last) = prefix_bytes.last() {
+            write!(f, "{}", DisplayPath::new(&prefix))?;
+            if last != MAIN_SEPARATOR as u8 {
+                write!(f, "{}", MAIN_SEPARATOR)?;
+            }
+            f.write_str(": ")?;
+        }
+
+        let rests = self.paths.iter().map(|p| {
+            let p = p.as_ref();
+            DisplayPath::new(p.strip_prefix(&prefix).unwrap_or(p))
+        });
+
+        options.write_seq(f, rests)
+    }
+}
+
+impl<P: AsRef<Path>> fmt::Debug for DisplayPaths<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a list of paths with their common ancestor factored out, to shorten file lists in
+/// logs.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayPathsExt;
+///
+/// # if cfg!(unix) {
+/// let paths = [
+///     "/data/db1/t1/seg_01",
+///     "/data/db1/t1/seg_02",
+///     "/data/db1/t9/seg_88",
+/// ];
+/// assert_eq!(
+///     paths.display_common_prefix().to_string(),
+///     "/data/db1/: [t1/seg_01,t1/seg_02,t9/seg_88]"
+/// );
+/// assert_eq!(
+///     paths.display_common_prefix().limit(2).sep(", ").to_string(),
+///     "/data/db1/: [t1/seg_01, .., t9/seg_88]"
+/// );
+/// # }
+/// ```
+pub trait DisplayPathsExt<P> {
+    fn display_common_prefix(&self) -> DisplayPaths<'_, P>;
+}
+
+impl<P: AsRef<Path>> DisplayPathsExt<P> for [P] {
+    fn display_common_prefix(&self) -> DisplayPaths<'_, P> {
+        DisplayPaths::new(self)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    use std::string::ToString;
+
+    use super::*;
+    use crate::DisplayConfig;
+
+    #[test]
+    fn test_display_paths() {
+        let paths = ["/a/b/x", "/a/b/y/z"];
+        assert_eq!("/a/b/: [x,y/z]", paths.display_common_prefix().to_string());
+
+        let paths = ["/a/x", "/b/y"];
+        assert_eq!("/: [a/x,b/y]", paths.display_common_prefix().to_string());
+
+        let paths = ["a/x", "b/y"];
+        assert_eq!("[a/x,b/y]", paths.display_common_prefix().to_string());
+
+        let paths = ["/a/b/c"];
+        assert_eq!("/a/b/: [c]", paths.display_common_prefix().to_string());
+
+        let paths: [&str; 0] = [];
+        assert_eq!("[]", paths.display_common_prefix().to_string());
+    }
+
+    #[test]
+    fn test_display_paths_ancestor_of_another() {
+        let paths = [PathBuf::from("/a/b"), PathBuf::from("/a/b/c")];
+        assert_eq!("/a/: [b,b/c]", paths.display_common_prefix().to_string());
+    }
+
+    #[test]
+    fn test_display_paths_limit() {
+        let paths = ["/d/1", "/d/2", "/d/3", "/d/4"];
+        assert_eq!(
+            "/d/: [1, 2, .., 4]",
+            paths.display_common_prefix().limit(3).sep(", ").to_string()
+        );
+        assert_eq!(
+            "/d/: [..]",
+            paths.display_common_prefix().limit(0).to_string()
+        );
+        assert_eq!(
+            "/d/: [1,2,3,4]",
+            paths.display_common_prefix().limit(4).to_string()
+        );
+        assert_eq!(
+            "/d/: [1,...,3,4]",
+            paths
+                .display_common_prefix()
+                .head_tail(1, 2)
+                .ellipsis("...")
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_paths_config() {
+        let paths = ["/d/1", "/d/2", "/d/3", "/d/4"];
+
+        let _g = DisplayConfig::DEFAULT
+            .slice_limit(2)
+            .separator("|")
+            .scoped();
+        assert_eq!("/d/: [1|..|4]", paths.display_common_prefix().to_string());
+    }
+
+    #[test]
+    fn test_display_paths_not_lossy() {
+        let paths = [
+            Path::new(OsStr::from_bytes(b"/d/a\xFFb")),
+            Path::new("/d/x y"),
+            Path::new("/d/l\n"),
+        ];
+        assert_eq!(
+            r#"/d/: [a\xFFb,"x y",l\n]"#,
+            paths.display_common_prefix().to_string()
+        );
+    }
+}
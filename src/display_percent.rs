@@ -0,0 +1,159 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+
+use crate::NonFinite;
+
+/// Display a ratio as a percentage with a fixed number of decimals, such as `87.3%`.
+///
+/// `NaN` and infinities are displayed as `NaN`, `inf` and `-inf`, without the `%` sign, unless
+/// another [`NonFinite`] style is set.
+#[must_use]
+pub struct DisplayPercent {
+    ratio: f64,
+    precision: usize,
+    clamp: bool,
+    non_finite: NonFinite,
+}
+
+impl DisplayPercent {
+    pub const fn new(ratio: f64, precision: usize) -> Self {
+        Self {
+            ratio,
+            precision,
+            clamp: false,
+            non_finite: NonFinite::Std,
+        }
+    }
+
+    /// Set how `NaN` and infinities are displayed.
+    pub const fn non_finite(self, non_finite: NonFinite) -> Self {
+        Self { non_finite, ..self }
+    }
+
+    /// Clamp the ratio into `[0, 1]`, i.e., display it between `0%` and `100%`.
+    pub const fn clamped(self) -> Self {
+        Self {
+            clamp: true,
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for DisplayPercent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ratio.is_nan() {
+            return self.non_finite.write(f, self.ratio);
+        }
+
+        let ratio = if self.clamp {
+            self.ratio.clamp(0.0, 1.0)
+        } else {
+            self.ratio
+        };
+
+        if ratio.is_infinite() {
+            return self.non_finite.write(f, ratio);
+        }
+
+        let percent = ratio * 100.0;
+
+        // Avoid displaying "-0.0%" for tiny negative ratios
+        let p = 10f64.powi(self.precision as i32);
+        let percent = if (percent * p).round() == 0.0 {
+            0.0
+        } else {
+            percent
+        };
+
+        write!(f, "{:.*}%", self.precision, percent)
+    }
+}
+
+impl fmt::Debug for DisplayPercent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a ratio as a percentage.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayPercentExt;
+///
+/// assert_eq!(0.8734f64.display_percent(1).to_string(), "87.3%");
+/// assert_eq!(1.5f64.display_percent(0).to_string(), "150%");
+/// assert_eq!(1.5f64.display_percent(0).clamped().to_string(), "100%");
+/// ```
+pub trait DisplayPercentExt {
+    /// Display `self` multiplied by 100 with `precision` decimals.
+    fn display_percent(&self, precision: usize) -> DisplayPercent;
+}
+
+impl DisplayPercentExt for f64 {
+    fn display_percent(&self, precision: usize) -> DisplayPercent {
+        DisplayPercent::new(*self, precision)
+    }
+}
+
+impl DisplayPercentExt for f32 {
+    fn display_percent(&self, precision: usize) -> DisplayPercent {
+        DisplayPercent::new(*self as f64, precision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_percent() {
+        assert_eq!("87.3%", 0.8734.display_percent(1).to_string());
+        assert_eq!("87%", 0.8734.display_percent(0).to_string());
+        assert_eq!("87.340%", 0.8734.display_percent(3).to_string());
+        assert_eq!("0.0%", 0.0.display_percent(1).to_string());
+        assert_eq!("100.0%", 1.0.display_percent(1).to_string());
+        assert_eq!("50%", 0.5f32.display_percent(0).to_string());
+    }
+
+    #[test]
+    fn test_display_percent_clamp() {
+        assert_eq!("-20%", (-0.2).display_percent(0).to_string());
+        assert_eq!("0%", (-0.2).display_percent(0).clamped().to_string());
+        assert_eq!("100%", 3.0.display_percent(0).clamped().to_string());
+        assert_eq!(
+            "100%",
+            f64::INFINITY.display_percent(0).clamped().to_string()
+        );
+        assert_eq!("0.0%", (-0.0001).display_percent(1).to_string());
+    }
+
+    #[test]
+    fn test_display_percent_non_finite() {
+        assert_eq!("NaN", f64::NAN.display_percent(1).to_string());
+        assert_eq!("NaN", f64::NAN.display_percent(1).clamped().to_string());
+        assert_eq!("inf", f64::INFINITY.display_percent(1).to_string());
+        assert_eq!("-inf", f64::NEG_INFINITY.display_percent(1).to_string());
+        assert_eq!(
+            "-",
+            f64::NAN
+                .display_percent(1)
+                .non_finite(NonFinite::Dash)
+                .to_string()
+        );
+    }
+}
@@ -0,0 +1,261 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+use core::time::Duration;
+
+const IEC_UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+/// Display a progress line such as `[#####.....] 50% 512/1024 ETA 12s`.
+///
+/// The bar is followed by the percentage and the done and total amounts. Call
+/// [`elapsed`](Self::elapsed) to add the estimated time to finish, computed from the average
+/// rate so far, and [`show_rate`](Self::show_rate) to add the rate itself.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use display_more::DisplayProgress;
+///
+/// let p = DisplayProgress::new(1536 << 20, 3 << 30)
+///     .bytes(true)
+///     .elapsed(Duration::from_secs(12));
+/// assert_eq!(p.to_string(), "[#####.....] 50% 1.5/3 GiB ETA 12s");
+///
+/// let p = DisplayProgress::new(3, 4)
+///     .width(4)
+///     .show_rate(true)
+///     .elapsed(Duration::from_secs(2));
+/// assert_eq!(p.to_string(), "[###.] 75% 3/4 1.5/s ETA 1s");
+/// ```
+#[must_use]
+pub struct DisplayProgress {
+    done: u64,
+    total: u64,
+    width: usize,
+    bytes: bool,
+    elapsed: Option<Duration>,
+    show_rate: bool,
+}
+
+impl DisplayProgress {
+    pub const fn new(done: u64, total: u64) -> Self {
+        Self {
+            done,
+            total,
+            width: 10,
+            bytes: false,
+            elapsed: None,
+            show_rate: false,
+        }
+    }
+
+    /// Set the number of chars in the bar, excluding the brackets. Default is 10.
+    pub const fn width(self, width: usize) -> Self {
+        Self { width, ..self }
+    }
+
+    /// Display the amounts as bytes in the IEC unit of the total, such as `1.5/3 GiB`.
+    pub const fn bytes(self, bytes: bool) -> Self {
+        Self { bytes, ..self }
+    }
+
+    /// Set the time spent so far, to display the estimated time to finish.
+    pub const fn elapsed(self, elapsed: Duration) -> Self {
+        Self {
+            elapsed: Some(elapsed),
+            ..self
+        }
+    }
+
+    /// Display the average rate per second before the ETA. It requires [`elapsed`](Self::elapsed).
+    pub const fn show_rate(self, show_rate: bool) -> Self {
+        Self { show_rate, ..self }
+    }
+
+    /// The done amount, which is at most the total.
+    fn done(&self) -> u64 {
+        self.done.min(self.total)
+    }
+
+    /// Return the fraction done as `numerator / denominator`; an empty task is complete.
+    fn ratio(&self) -> (u128, u128) {
+        if self.total == 0 {
+            (1, 1)
+        } else {
+            (self.done() as u128, self.total as u128)
+        }
+    }
+
+    fn fmt_amount(f: &mut fmt::Formatter<'_>, value: u64, unit_size: u64) -> fmt::Result {
+        let (quotient, remainder) = (value / unit_size, value % unit_size);
+        if remainder == 0 {
+            return write!(f, "{}", quotient);
+        }
+        write!(f, "{:.1}", value as f64 / unit_size as f64)
+    }
+
+    /// Return the index of the largest IEC unit that `value` has at least one of.
+    fn unit_of(value: u64) -> usize {
+        let mut exp = 0;
+        while exp < IEC_UNITS.len() - 1 && value >> (10 * (exp + 1)) > 0 {
+            exp += 1;
+        }
+        exp
+    }
+
+    fn fmt_eta(f: &mut fmt::Formatter<'_>, secs: u64) -> fmt::Result {
+        let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+        if h > 0 {
+            write!(f, "{h}h{m:02}m")
+        } else if m > 0 {
+            write!(f, "{m}m{s:02}s")
+        } else {
+            write!(f, "{s}s")
+        }
+    }
+}
+
+impl fmt::Display for DisplayProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (num, den) = self.ratio();
+        let filled = (self.width as u128 * num / den) as usize;
+
+        f.write_str("[")?;
+        for _ in 0..filled {
+            f.write_str("#")?;
+        }
+        for _ in filled..self.width {
+            f.write_str(".")?;
+        }
+        write!(f, "] {}% ", num * 100 / den)?;
+
+        let done = self.done();
+        if self.bytes {
+            let exp = Self::unit_of(self.total);
+            let unit_size = 1u64 << (10 * exp);
+            Self::fmt_amount(f, done, unit_size)?;
+            f.write_str("/")?;
+            Self::fmt_amount(f, self.total, unit_size)?;
+            write!(f, " {}", IEC_UNITS[exp])?;
+        } else {
+            write!(f, "{}/{}", done, self.total)?;
+        }
+
+        let Some(elapsed) = self.elapsed else {
+            return Ok(());
+        };
+
+        let secs = elapsed.as_secs_f64();
+        if self.show_rate && secs > 0.0 {
+            let rate = done as f64 / secs;
+            if self.bytes {
+                let exp = Self::unit_of(rate as u64);
+                let unit_size = (1u64 << (10 * exp)) as f64;
+                write!(f, " {:.1} {}/s", rate / unit_size, IEC_UNITS[exp])?;
+            } else {
+                write!(f, " {:.1}/s", rate)?;
+            }
+        }
+
+        // The time left at the average rate so far: elapsed * remaining / done, rounded up so
+        // that an unfinished task never shows `ETA 0s`.
+        if done > 0 && done < self.total {
+            let remaining = (self.total - done) as u128;
+            let eta = elapsed.as_millis() * remaining / done as u128;
+            f.write_str(" ETA ")?;
+            Self::fmt_eta(f, eta.div_ceil(1000) as u64)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for DisplayProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_display_progress() {
+        assert_eq!(
+            "[..........] 0% 0/8",
+            DisplayProgress::new(0, 8).to_string()
+        );
+        assert_eq!(
+            "[###.......] 37% 3/8",
+            DisplayProgress::new(3, 8).to_string()
+        );
+        assert_eq!(
+            "[##########] 100% 8/8",
+            DisplayProgress::new(9, 8).to_string()
+        );
+        assert_eq!(
+            "[##########] 100% 0/0",
+            DisplayProgress::new(0, 0).to_string()
+        );
+        assert_eq!(
+            "[] 50% 1/2",
+            DisplayProgress::new(1, 2).width(0).to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_progress_bytes() {
+        let p = |done, total| {
+            DisplayProgress::new(done, total)
+                .bytes(true)
+                .width(2)
+                .to_string()
+        };
+        assert_eq!("[..] 0% 0/100 B", p(0, 100));
+        assert_eq!("[#.] 50% 0.5/1 GiB", p(512 << 20, 1 << 30));
+        assert_eq!("[#.] 74% 1.1/1.5 MiB", p(1_170_000, 3 << 19));
+    }
+
+    #[test]
+    fn test_display_progress_eta() {
+        let p = |done, secs| {
+            DisplayProgress::new(done, 100)
+                .width(0)
+                .elapsed(Duration::from_secs(secs))
+                .to_string()
+        };
+        assert_eq!("[] 0% 0/100", p(0, 5));
+        assert_eq!("[] 100% 100/100", p(100, 5));
+        assert_eq!("[] 10% 10/100 ETA 45s", p(10, 5));
+        assert_eq!("[] 1% 1/100 ETA 8m15s", p(1, 5));
+        assert_eq!("[] 1% 1/100 ETA 2h45m", p(1, 100));
+    }
+
+    #[test]
+    fn test_display_progress_rate() {
+        let p = DisplayProgress::new(3 << 20, 4 << 20)
+            .bytes(true)
+            .show_rate(true)
+            .elapsed(Duration::from_secs(2));
+        assert_eq!("[#######...] 75% 3/4 MiB 1.5 MiB/s ETA 1s", p.to_string());
+
+        let p = DisplayProgress::new(1, 4).show_rate(true);
+        assert_eq!("[##........] 25% 1/4", p.to_string());
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+use core::ops::Deref;
+
+/// Display the address a reference or smart pointer points to, optionally followed by the value.
+///
+/// It outputs the address in `{:p}` form, such as `0x7f3a5c001230`.
+/// With [`with_value`](Self::with_value) it outputs `0x7f3a5c001230(value)`.
+#[must_use]
+pub struct DisplayPtr<'a, T: ?Sized> {
+    inner: &'a T,
+    fmt_fn: Option<fn(&T, &mut fmt::Formatter<'_>) -> fmt::Result>,
+}
+
+impl<'a, T: ?Sized> DisplayPtr<'a, T> {
+    pub const fn new(inner: &'a T) -> Self {
+        Self {
+            inner,
+            fmt_fn: None,
+        }
+    }
+}
+
+impl<T: fmt::Display + ?Sized> DisplayPtr<'_, T> {
+    /// Also display the pointed-to value after the address.
+    pub const fn with_value(self) -> Self {
+        Self {
+            fmt_fn: Some(<T as fmt::Display>::fmt),
+            ..self
+        }
+    }
+}
+
+impl<T: ?Sized> fmt::Display for DisplayPtr<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Cast to a thin pointer so that unsized targets display only the address.
+        let addr = (self.inner as *const T).cast::<()>();
+        write!(f, "{:p}", addr)?;
+
+        if let Some(fmt_fn) = self.fmt_fn {
+            write!(f, "(")?;
+            fmt_fn(self.inner, f)?;
+            write!(f, ")")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for DisplayPtr<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display the address of the value behind a reference or smart pointer.
+///
+/// Clones of an `Rc` or `Arc` display the same address, which helps to tell whether two log
+/// lines refer to the same shared state.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::Arc;
+///
+/// use display_more::DisplayPtrExt;
+///
+/// let a = Arc::new(1);
+/// let b = a.clone();
+/// assert_eq!(a.display_ptr().to_string(), b.display_ptr().to_string());
+/// assert!(a.display_ptr().with_value().to_string().ends_with("(1)"));
+/// ```
+pub trait DisplayPtrExt<T: ?Sized> {
+    fn display_ptr(&self) -> DisplayPtr<'_, T>;
+}
+
+impl<P> DisplayPtrExt<P::Target> for P
+where P: Deref
+{
+    fn display_ptr(&self) -> DisplayPtr<'_, P::Target> {
+        DisplayPtr::new(self.deref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[test]
+    fn test_display_ptr() {
+        let x = 5;
+        let r = &x;
+        assert_eq!(format!("{:p}", r), r.display_ptr().to_string());
+        assert!(r.display_ptr().to_string().starts_with("0x"));
+
+        let b = Box::new(5);
+        assert_eq!(format!("{:p}", b), b.display_ptr().to_string());
+    }
+
+    #[test]
+    fn test_display_ptr_shared() {
+        let a = Arc::new(1);
+        let b = a.clone();
+        let c = Arc::new(1);
+        assert_eq!(a.display_ptr().to_string(), b.display_ptr().to_string());
+        assert_ne!(a.display_ptr().to_string(), c.display_ptr().to_string());
+
+        let a = Rc::new("foo");
+        let b = a.clone();
+        assert_eq!(a.display_ptr().to_string(), b.display_ptr().to_string());
+    }
+
+    #[test]
+    fn test_display_ptr_with_value() {
+        let a = Arc::new(42);
+        assert_eq!(
+            format!("{:p}(42)", a),
+            a.display_ptr().with_value().to_string()
+        );
+
+        // Unsized target
+        let s: Box<str> = "hello".into();
+        let display = s.display_ptr().with_value().to_string();
+        assert!(display.starts_with("0x"));
+        assert!(display.ends_with("(hello)"));
+    }
+}
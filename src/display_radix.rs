@@ -0,0 +1,252 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The radix of a [`DisplayRadix`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Radix {
+    Binary,
+    Octal,
+    Hex,
+}
+
+impl Radix {
+    fn bits(&self) -> u32 {
+        match self {
+            Radix::Binary => 1,
+            Radix::Octal => 3,
+            Radix::Hex => 4,
+        }
+    }
+
+    fn prefix(&self) -> &'static str {
+        match self {
+            Radix::Binary => "0b",
+            Radix::Octal => "0o",
+            Radix::Hex => "0x",
+        }
+    }
+}
+
+/// Display an integer in binary, octal or hex with digit grouping, such as `0xDEAD_BEEF` or
+/// `0b1010_0110`.
+///
+/// Signed integers are displayed as their two's complement bit pattern, as `{:x}` does.
+#[must_use]
+pub struct DisplayRadix {
+    value: u128,
+    radix: Radix,
+    group: usize,
+    width: usize,
+    separator: char,
+    uppercase: bool,
+    prefix: bool,
+}
+
+impl DisplayRadix {
+    pub fn new(value: u128, radix: Radix) -> Self {
+        Self {
+            value,
+            radix,
+            group: if radix == Radix::Octal { 3 } else { 4 },
+            width: 0,
+            separator: '_',
+            uppercase: true,
+            prefix: true,
+        }
+    }
+
+    /// Set the number of digits per group. 0 disables grouping.
+    ///
+    /// By default it is 4 for binary and hex, and 3 for octal.
+    pub const fn group(self, group: usize) -> Self {
+        Self { group, ..self }
+    }
+
+    /// Pad with leading zeros to at least `width` digits.
+    pub const fn width(self, width: usize) -> Self {
+        Self { width, ..self }
+    }
+
+    /// Set the separator between groups. By default it is `'_'`.
+    pub const fn sep(self, separator: char) -> Self {
+        Self { separator, ..self }
+    }
+
+    /// Use lowercase hex digits.
+    pub const fn lowercase(self) -> Self {
+        Self {
+            uppercase: false,
+            ..self
+        }
+    }
+
+    /// Do not output the `0x`, `0o` or `0b` prefix.
+    pub const fn no_prefix(self) -> Self {
+        Self {
+            prefix: false,
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for DisplayRadix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bits = self.radix.bits();
+        let mask = (1u128 << bits) - 1;
+        let alphabet: &[u8; 16] = if self.uppercase {
+            b"0123456789ABCDEF"
+        } else {
+            b"0123456789abcdef"
+        };
+
+        let mut digits = Vec::with_capacity(128);
+        let mut v = self.value;
+        loop {
+            digits.push(alphabet[(v & mask) as usize]);
+            v >>= bits;
+            if v == 0 {
+                break;
+            }
+        }
+        while digits.len() < self.width {
+            digits.push(b'0');
+        }
+        digits.reverse();
+
+        if self.prefix {
+            f.write_str(self.radix.prefix())?;
+        }
+
+        let n = digits.len();
+        for (i, d) in digits.iter().enumerate() {
+            if self.group > 0 && i > 0 && (n - i) % self.group == 0 {
+                write!(f, "{}", self.separator)?;
+            }
+            write!(f, "{}", *d as char)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for DisplayRadix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display integers in binary, octal or hex with digit grouping.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayRadixExt;
+///
+/// assert_eq!(0xDEADBEEFu32.display_hex().to_string(), "0xDEAD_BEEF");
+/// assert_eq!(0b10100110u8.display_bin().to_string(), "0b1010_0110");
+/// assert_eq!(5u8.display_bin().width(8).to_string(), "0b0000_0101");
+/// ```
+pub trait DisplayRadixExt {
+    fn display_radix(&self, radix: Radix) -> DisplayRadix;
+
+    fn display_hex(&self) -> DisplayRadix {
+        self.display_radix(Radix::Hex)
+    }
+
+    fn display_oct(&self) -> DisplayRadix {
+        self.display_radix(Radix::Octal)
+    }
+
+    fn display_bin(&self) -> DisplayRadix {
+        self.display_radix(Radix::Binary)
+    }
+}
+
+macro_rules! impl_radix {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl DisplayRadixExt for $t {
+                fn display_radix(&self, radix: Radix) -> DisplayRadix {
+                    DisplayRadix::new(*self as $u as u128, radix)
+                }
+            }
+        )*
+    };
+}
+
+impl_radix!(
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize,
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_hex() {
+        assert_eq!("0x0", 0u32.display_hex().to_string());
+        assert_eq!("0xFF", 255u8.display_hex().to_string());
+        assert_eq!("0x1_0000", 0x10000u32.display_hex().to_string());
+        assert_eq!("0xDEAD_BEEF", 0xDEADBEEFu32.display_hex().to_string());
+        assert_eq!(
+            "0xdead_beef",
+            0xDEADBEEFu32.display_hex().lowercase().to_string()
+        );
+        assert_eq!(
+            "0xDEADBEEF",
+            0xDEADBEEFu32.display_hex().group(0).to_string()
+        );
+        assert_eq!(
+            "DE:AD:BE:EF",
+            0xDEADBEEFu32
+                .display_hex()
+                .group(2)
+                .sep(':')
+                .no_prefix()
+                .to_string()
+        );
+        assert_eq!(
+            "0xFFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF",
+            u128::MAX.display_hex().to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_hex_signed() {
+        assert_eq!("0xFF", (-1i8).display_hex().to_string());
+        assert_eq!("0xFFFF_FFFE", (-2i32).display_hex().to_string());
+    }
+
+    #[test]
+    fn test_display_bin_and_oct() {
+        assert_eq!("0b1010_0110", 0b10100110u8.display_bin().to_string());
+        assert_eq!("0b101", 5u8.display_bin().to_string());
+        assert_eq!("0b0000_0101", 5u8.display_bin().width(8).to_string());
+        assert_eq!("0o755", 0o755u16.display_oct().to_string());
+        assert_eq!("0o1_777", 0o1777u16.display_oct().to_string());
+    }
+
+    #[test]
+    fn test_display_radix_width() {
+        assert_eq!("0x0000_00FF", 255u32.display_hex().width(8).to_string());
+        // Width smaller than the number of digits has no effect
+        assert_eq!(
+            "0xDEAD_BEEF",
+            0xDEADBEEFu32.display_hex().width(2).to_string()
+        );
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+use core::ops::Bound;
+use core::ops::RangeBounds;
+
+/// Display a range in interval notation, such as `[3, 10)` or `(-inf, 5]`.
+///
+/// An included bound is displayed with `[` or `]`, an excluded bound with `(` or `)`, and an
+/// unbounded start or end as `-inf` or `+inf`.
+#[must_use]
+pub struct DisplayRange<'a, T: fmt::Display> {
+    start: Bound<&'a T>,
+    end: Bound<&'a T>,
+}
+
+impl<'a, T: fmt::Display> DisplayRange<'a, T> {
+    pub const fn new(start: Bound<&'a T>, end: Bound<&'a T>) -> Self {
+        Self { start, end }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for DisplayRange<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.start {
+            Bound::Included(x) => write!(f, "[{}", x)?,
+            Bound::Excluded(x) => write!(f, "({}", x)?,
+            Bound::Unbounded => f.write_str("(-inf")?,
+        }
+        f.write_str(", ")?;
+        match self.end {
+            Bound::Included(x) => write!(f, "{}]", x),
+            Bound::Excluded(x) => write!(f, "{})", x),
+            Bound::Unbounded => f.write_str("+inf)"),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Debug for DisplayRange<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a range in interval notation.
+///
+/// It is implemented for every `RangeBounds`: `Range`, `RangeInclusive`, `RangeFrom`, `RangeTo`,
+/// `RangeFull` and `(Bound<T>, Bound<T>)` pairs.
+///
+/// # Example
+///
+/// ```rust
+/// use std::ops::Bound;
+///
+/// use display_more::DisplayRangeExt;
+///
+/// assert_eq!((3..10).display().to_string(), "[3, 10)");
+/// assert_eq!((..=5).display().to_string(), "(-inf, 5]");
+/// assert_eq!(
+///     (Bound::Excluded(1), Bound::Unbounded).display().to_string(),
+///     "(1, +inf)"
+/// );
+/// ```
+pub trait DisplayRangeExt<T: fmt::Display> {
+    fn display(&self) -> DisplayRange<'_, T>;
+}
+
+impl<T, R> DisplayRangeExt<T> for R
+where
+    T: fmt::Display,
+    R: RangeBounds<T> + ?Sized,
+{
+    fn display(&self) -> DisplayRange<'_, T> {
+        DisplayRange::new(self.start_bound(), self.end_bound())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use core::ops::RangeFull;
+
+    use super::*;
+
+    #[test]
+    fn test_display_range() {
+        assert_eq!("[3, 10)", (3..10).display().to_string());
+        assert_eq!("[3, 10]", (3..=10).display().to_string());
+        assert_eq!("[3, +inf)", (3..).display().to_string());
+        assert_eq!("(-inf, 10)", (..10).display().to_string());
+        assert_eq!("(-inf, 10]", (..=10).display().to_string());
+        assert_eq!(
+            "(-inf, +inf)",
+            DisplayRangeExt::<u8>::display(&RangeFull).to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_bound_pair() {
+        let r = (Bound::Excluded(1), Bound::Included(2));
+        assert_eq!("(1, 2]", r.display().to_string());
+
+        let r = (Bound::Unbounded, Bound::Excluded("k".to_string()));
+        assert_eq!("(-inf, k)", r.display().to_string());
+    }
+}
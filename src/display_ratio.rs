@@ -0,0 +1,99 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+
+use crate::DisplayPercent;
+
+/// Display progress as `done/total (percent)`, such as `750/1000 (75%)`.
+///
+/// If `total` is 0, the percentage is displayed as `-`: `0/0 (-)`.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayRatio;
+///
+/// assert_eq!(DisplayRatio::new(750, 1000).to_string(), "750/1000 (75%)");
+/// assert_eq!(
+///     DisplayRatio::new(1, 3).precision(1).to_string(),
+///     "1/3 (33.3%)"
+/// );
+/// assert_eq!(DisplayRatio::new(0, 0).to_string(), "0/0 (-)");
+/// ```
+#[must_use]
+pub struct DisplayRatio {
+    done: u64,
+    total: u64,
+    precision: usize,
+}
+
+impl DisplayRatio {
+    pub const fn new(done: u64, total: u64) -> Self {
+        Self {
+            done,
+            total,
+            precision: 0,
+        }
+    }
+
+    /// Set the number of decimals of the percentage. By default it is 0.
+    pub const fn precision(self, precision: usize) -> Self {
+        Self { precision, ..self }
+    }
+}
+
+impl fmt::Display for DisplayRatio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{} (", self.done, self.total)?;
+
+        if self.total == 0 {
+            write!(f, "-")?;
+        } else {
+            let ratio = self.done as f64 / self.total as f64;
+            write!(f, "{}", DisplayPercent::new(ratio, self.precision))?;
+        }
+
+        write!(f, ")")
+    }
+}
+
+impl fmt::Debug for DisplayRatio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_ratio() {
+        assert_eq!("750/1000 (75%)", DisplayRatio::new(750, 1000).to_string());
+        assert_eq!("0/10 (0%)", DisplayRatio::new(0, 10).to_string());
+        assert_eq!("10/10 (100%)", DisplayRatio::new(10, 10).to_string());
+        assert_eq!("15/10 (150%)", DisplayRatio::new(15, 10).to_string());
+        assert_eq!(
+            "2/3 (66.67%)",
+            DisplayRatio::new(2, 3).precision(2).to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_ratio_zero_total() {
+        assert_eq!("0/0 (-)", DisplayRatio::new(0, 0).to_string());
+        assert_eq!("5/0 (-)", DisplayRatio::new(5, 0).precision(2).to_string());
+    }
+}
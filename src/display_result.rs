@@ -12,23 +12,47 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt;
+use core::fmt;
 
 /// Implement `Display` for `Result<T, E>` if T and E are `Display`.
 ///
 /// It outputs a literal string `"Ok(T)"` if it is Ok. Otherwise it invokes the Display
 /// implementation for E.
+#[must_use]
 pub struct DisplayResult<'a, T: fmt::Display, E: fmt::Display>(pub &'a Result<T, E>);
 
 impl<T: fmt::Display, E: fmt::Display> fmt::Display for DisplayResult<'_, T, E> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.0 {
             Ok(t) => write!(f, "Ok({})", t),
-            Err(e) => write!(f, "Err({})", e),
+            Err(e) => write_styled!(f, Red, "Err({})", e),
         }
     }
 }
 
+impl<T: fmt::Display, E: fmt::Display> fmt::Debug for DisplayResult<'_, T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Owned counterpart of `DisplayResult` that can be stored in a struct or moved to another
+/// thread.
+#[must_use]
+pub struct DisplayResultOwned<T: fmt::Display, E: fmt::Display>(pub Result<T, E>);
+
+impl<T: fmt::Display, E: fmt::Display> fmt::Display for DisplayResultOwned<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        DisplayResult(&self.0).fmt(f)
+    }
+}
+
+impl<T: fmt::Display, E: fmt::Display> fmt::Debug for DisplayResultOwned<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
 /// Implement `Display` for `Result<T, E>` if T and E are `Display`.
 ///
 /// It outputs a literal string `"Ok(T)"` if it is Ok. Otherwise it invokes the Display
@@ -47,6 +71,11 @@ impl<T: fmt::Display, E: fmt::Display> fmt::Display for DisplayResult<'_, T, E>
 /// ```
 pub trait DisplayResultExt<'a, T: fmt::Display, E: fmt::Display> {
     fn display(&'a self) -> DisplayResult<'a, T, E>;
+
+    /// Same as [`display()`](Self::display); use it when another `display()` is in scope.
+    fn display_res(&'a self) -> DisplayResult<'a, T, E> {
+        self.display()
+    }
 }
 
 impl<T: fmt::Display, E: fmt::Display> DisplayResultExt<'_, T, E> for Result<T, E> {
@@ -66,5 +95,24 @@ mod tests {
 
         let result = Result::<i32, i32>::Err(2);
         assert_eq!(result.display().to_string(), "Err(2)");
+        assert_eq!(result.display_res().to_string(), "Err(2)");
+    }
+
+    #[test]
+    fn test_display_result_owned() {
+        let d = DisplayResultOwned(Result::<i32, String>::Err("bad".to_string()));
+        let s = std::thread::spawn(move || d.to_string()).join().unwrap();
+        assert_eq!(s, "Err(bad)");
+
+        assert_eq!(
+            DisplayResultOwned(Result::<i32, i32>::Ok(1)).to_string(),
+            "Ok(1)"
+        );
+    }
+
+    #[test]
+    fn test_display_result_debug() {
+        let result = Result::<i32, i32>::Err(2);
+        assert_eq!(format!("{:?}", result.display()), "Err(2)");
     }
 }
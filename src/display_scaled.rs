@@ -0,0 +1,112 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::string::ToString;
+use core::fmt;
+
+/// Display a scaled integer as a fixed-point decimal, such as `1234567` with scale 3 as
+/// `1234.567`.
+///
+/// It is exact: no conversion to floating point is involved.
+#[must_use]
+pub struct DisplayScaled {
+    value: i128,
+    scale: u8,
+}
+
+impl DisplayScaled {
+    pub const fn new(value: i128, scale: u8) -> Self {
+        Self { value, scale }
+    }
+}
+
+impl fmt::Display for DisplayScaled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.value < 0 {
+            write!(f, "-")?;
+        }
+
+        let digits = self.value.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+
+        if scale == 0 {
+            return write!(f, "{}", digits);
+        }
+
+        if digits.len() > scale {
+            let (int, frac) = digits.split_at(digits.len() - scale);
+            write!(f, "{}.{}", int, frac)
+        } else {
+            write!(f, "0.")?;
+            for _ in digits.len()..scale {
+                write!(f, "0")?;
+            }
+            write!(f, "{}", digits)
+        }
+    }
+}
+
+impl fmt::Debug for DisplayScaled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display `value` divided by `10^scale` as a fixed-point decimal.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::display_scaled;
+///
+/// assert_eq!(display_scaled(1234567, 3).to_string(), "1234.567");
+/// assert_eq!(display_scaled(-5, 2).to_string(), "-0.05");
+/// ```
+pub const fn display_scaled(value: i128, scale: u8) -> DisplayScaled {
+    DisplayScaled::new(value, scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_scaled() {
+        assert_eq!("1234.567", display_scaled(1234567, 3).to_string());
+        assert_eq!("1234567", display_scaled(1234567, 0).to_string());
+        assert_eq!("1.000", display_scaled(1000, 3).to_string());
+        assert_eq!("0.123", display_scaled(123, 3).to_string());
+        assert_eq!("0.005", display_scaled(5, 3).to_string());
+        assert_eq!("0.00", display_scaled(0, 2).to_string());
+        assert_eq!("0", display_scaled(0, 0).to_string());
+    }
+
+    #[test]
+    fn test_display_scaled_negative() {
+        assert_eq!("-1234.567", display_scaled(-1234567, 3).to_string());
+        assert_eq!("-0.5", display_scaled(-5, 1).to_string());
+        assert_eq!(
+            "-1.70141183460469231731687303715884105728",
+            display_scaled(i128::MIN, 38).to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_scaled_large_scale() {
+        let s = display_scaled(1, 40).to_string();
+        assert_eq!(42, s.len());
+        assert!(s.starts_with("0.000"));
+        assert!(s.ends_with("01"));
+    }
+}
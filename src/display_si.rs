@@ -0,0 +1,199 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+use core::fmt;
+
+use crate::NonFinite;
+
+/// Display a number with an SI magnitude suffix, such as `1.2k`, `3.4M` or `9.1G`.
+///
+/// The value is rounded to a configurable number of significant digits, but digits of the integer
+/// part are never dropped. Trailing zeros after the decimal point are removed: `1000` is displayed
+/// as `1k`.
+#[must_use]
+pub struct DisplaySi {
+    value: f64,
+    sig_digits: usize,
+    non_finite: NonFinite,
+}
+
+impl DisplaySi {
+    const SUFFIXES: [&'static str; 7] = ["", "k", "M", "G", "T", "P", "E"];
+
+    pub const fn new(value: f64) -> Self {
+        Self {
+            value,
+            sig_digits: 2,
+            non_finite: NonFinite::Std,
+        }
+    }
+
+    /// Set how `NaN` and infinities are displayed.
+    pub const fn non_finite(self, non_finite: NonFinite) -> Self {
+        Self { non_finite, ..self }
+    }
+
+    /// Set the number of significant digits. By default it is 2. The minimum is 1.
+    pub const fn sig_digits(self, sig_digits: usize) -> Self {
+        Self {
+            sig_digits: if sig_digits < 1 { 1 } else { sig_digits },
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for DisplaySi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.value.is_finite() {
+            return self.non_finite.write(f, self.value);
+        }
+
+        let abs = self.value.abs();
+        let max_exp = Self::SUFFIXES.len() - 1;
+
+        let mut exp = 0;
+        while exp < max_exp && abs >= 1000f64.powi(exp as i32 + 1) {
+            exp += 1;
+        }
+
+        let mut scaled = abs / 1000f64.powi(exp as i32);
+        let mut decimals = decimals_for(scaled, self.sig_digits);
+        let mut rounded = round_to(scaled, decimals);
+
+        // Rounding may carry into the next magnitude, e.g., 999.9k -> 1000k -> 1M
+        if rounded >= 1000.0 && exp < max_exp {
+            exp += 1;
+            scaled = abs / 1000f64.powi(exp as i32);
+            decimals = decimals_for(scaled, self.sig_digits);
+            rounded = round_to(scaled, decimals);
+        }
+
+        if self.value.is_sign_negative() && rounded != 0.0 {
+            write!(f, "-")?;
+        }
+
+        let s = format!("{:.*}", decimals, rounded);
+        let s = if s.contains('.') {
+            s.trim_end_matches('0').trim_end_matches('.')
+        } else {
+            &s
+        };
+
+        write!(f, "{}{}", s, Self::SUFFIXES[exp])
+    }
+}
+
+impl fmt::Debug for DisplaySi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Number of decimals to show `v` with `sig_digits` significant digits.
+///
+/// Digits of the integer part are never dropped.
+fn decimals_for(v: f64, sig_digits: usize) -> usize {
+    if v == 0.0 {
+        return 0;
+    }
+    let int_digits = v.log10().floor() as i64 + 1;
+    (sig_digits as i64 - int_digits).max(0) as usize
+}
+
+fn round_to(v: f64, decimals: usize) -> f64 {
+    let p = 10f64.powi(decimals as i32);
+    (v * p).round() / p
+}
+
+/// Display a number with an SI magnitude suffix.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplaySiExt;
+///
+/// assert_eq!(1234.display_si().to_string(), "1.2k");
+/// assert_eq!(3_400_000u64.display_si().to_string(), "3.4M");
+/// assert_eq!(
+///     9_123_456_789u64.display_si().sig_digits(3).to_string(),
+///     "9.12G"
+/// );
+/// ```
+pub trait DisplaySiExt {
+    fn display_si(&self) -> DisplaySi;
+}
+
+macro_rules! impl_si {
+    ($($t:ty),*) => {
+        $(
+            impl DisplaySiExt for $t {
+                fn display_si(&self) -> DisplaySi {
+                    DisplaySi::new(*self as f64)
+                }
+            }
+        )*
+    };
+}
+
+impl_si!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_si() {
+        assert_eq!("0", 0.display_si().to_string());
+        assert_eq!("5", 5.display_si().to_string());
+        assert_eq!("12", 12.display_si().to_string());
+        assert_eq!("123", 123.display_si().to_string());
+        assert_eq!("1k", 1000.display_si().to_string());
+        assert_eq!("1.2k", 1234.display_si().to_string());
+        assert_eq!("3.4M", 3_400_000.display_si().to_string());
+        assert_eq!("9.1G", 9_123_456_789u64.display_si().to_string());
+        assert_eq!("18E", u64::MAX.display_si().to_string());
+    }
+
+    #[test]
+    fn test_display_si_rounding_carry() {
+        assert_eq!("1M", 999_999.display_si().to_string());
+        assert_eq!("1k", 999.9.display_si().to_string());
+        assert_eq!("999.9", 999.9.display_si().sig_digits(4).to_string());
+    }
+
+    #[test]
+    fn test_display_si_sig_digits() {
+        assert_eq!("1k", 1234.display_si().sig_digits(1).to_string());
+        assert_eq!("1.234k", 1234.display_si().sig_digits(4).to_string());
+        assert_eq!("1k", 1234.display_si().sig_digits(0).to_string());
+        assert_eq!("0.12", 0.1234.display_si().to_string());
+    }
+
+    #[test]
+    fn test_display_si_negative_and_special() {
+        assert_eq!("-1.5k", (-1500).display_si().to_string());
+        assert_eq!("-0.0001", (-0.0001f64).display_si().to_string());
+        assert_eq!("0", (-0.0f64).display_si().to_string());
+        assert_eq!("NaN", f64::NAN.display_si().to_string());
+        assert_eq!("-inf", f64::NEG_INFINITY.display_si().to_string());
+        assert_eq!(
+            "null",
+            f64::NAN
+                .display_si()
+                .non_finite(NonFinite::Null)
+                .to_string()
+        );
+    }
+}
@@ -12,16 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt;
+use alloc::vec::Vec;
+use core::fmt;
 
+use crate::display_iterator_options::DisplayIteratorOptions;
 use crate::DisplayIntoIter;
 
 /// Implement `Display` for `&[T]` if T is `Display`.
 ///
 /// It outputs at most `limit` elements, excluding those from the 5th to the second-to-last one:
 /// - `DisplaySlice{ slice: &[1,2,3,4,5,6], ...}` outputs: `"[1,2,3,4,...,6]"`.
+#[must_use]
 pub struct DisplaySlice<'a, T: fmt::Display> {
-    inner: DisplayIntoIter<'a, T, std::slice::Iter<'a, T>>,
+    pub(crate) inner: DisplayIntoIter<'a, T, core::slice::Iter<'a, T>>,
 }
 
 impl<'a, T: fmt::Display> DisplaySlice<'a, T> {
@@ -31,44 +34,50 @@ impl<'a, T: fmt::Display> DisplaySlice<'a, T> {
         }
     }
 
-    pub fn at_most(mut self, limit: Option<usize>) -> Self {
-        self.inner = self.inner.at_most(limit);
-        self
-    }
+    iterator_options_builders!('a, inner.options);
+}
 
-    pub fn sep(mut self, separator: &'a str) -> Self {
-        self.inner = self.inner.sep(separator);
-        self
+impl<T: fmt::Display> fmt::Display for DisplaySlice<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
     }
+}
 
-    pub fn braces(mut self, left: &'a str, right: &'a str) -> Self {
-        self.inner = self.inner.braces(left, right);
-        self
+impl<T: fmt::Display> fmt::Debug for DisplaySlice<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
     }
+}
 
-    pub fn ellipsis(mut self, s: &'a str) -> Self {
-        self.inner = self.inner.ellipsis(s);
-        self
-    }
+/// Owned counterpart of [`DisplaySlice`] that can be stored in a struct or moved to another thread.
+///
+/// Separators, braces and other strings must be `'static`.
+#[must_use]
+pub struct DisplaySliceOwned<T: fmt::Display> {
+    pub(crate) items: Vec<T>,
+    pub(crate) options: DisplayIteratorOptions<'static>,
+}
 
-    pub fn elem(mut self, prefix: &'a str, suffix: &'a str) -> Self {
-        self.inner = self.inner.elem(prefix, suffix);
-        self
+impl<T: fmt::Display> DisplaySliceOwned<T> {
+    pub const fn new(items: Vec<T>) -> Self {
+        Self {
+            items,
+            options: DisplayIteratorOptions::new(),
+        }
     }
 
-    pub fn show_count(mut self) -> Self {
-        self.inner = self.inner.show_count();
-        self
-    }
+    iterator_options_builders!('static, options);
+}
 
-    pub fn limit(&self) -> usize {
-        self.inner.limit()
+impl<T: fmt::Display> fmt::Display for DisplaySliceOwned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        DisplayIntoIter::with_options(self.items.iter(), self.options).fmt(f)
     }
 }
 
-impl<T: fmt::Display> fmt::Display for DisplaySlice<'_, T> {
+impl<T: fmt::Display> fmt::Debug for DisplaySliceOwned<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.inner.fmt(f)
+        fmt::Display::fmt(self, f)
     }
 }
 
@@ -88,6 +97,11 @@ impl<T: fmt::Display> fmt::Display for DisplaySlice<'_, T> {
 pub trait DisplaySliceExt<'a, T: fmt::Display> {
     fn display(&'a self) -> DisplaySlice<'a, T>;
 
+    /// Same as [`display()`](Self::display); use it when another `display()` is in scope.
+    fn display_slice(&'a self) -> DisplaySlice<'a, T> {
+        self.display()
+    }
+
     /// Display at most `n` elements.
     fn display_n(&'a self, n: usize) -> DisplaySlice<'a, T> {
         self.display().at_most(Some(n))
@@ -97,7 +111,7 @@ pub trait DisplaySliceExt<'a, T: fmt::Display> {
 impl<T> DisplaySliceExt<'_, T> for [T]
 where T: fmt::Display
 {
-    fn display(&self) -> DisplaySlice<T> {
+    fn display(&self) -> DisplaySlice<'_, T> {
         DisplaySlice::new(self)
     }
 }
@@ -105,6 +119,7 @@ where T: fmt::Display
 #[cfg(test)]
 mod tests {
     use super::DisplaySlice;
+    use super::DisplaySliceOwned;
     use crate::DisplaySliceExt;
 
     #[test]
@@ -136,6 +151,8 @@ mod tests {
         assert_eq!("[..,7]", a.display_n(1).to_string());
 
         assert_eq!("[..]", a.display_n(0).to_string());
+
+        assert_eq!("[1,2,3,4,..,7]", a.display_slice().to_string());
     }
 
     #[test]
@@ -252,6 +269,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_display_slice_fit_width() {
+        let a = (1..=100).collect::<Vec<_>>();
+        assert_eq!("[1,2,..,100]", a.display_n(50).fit_width(12).to_string());
+        assert_eq!("[1,2,3,4,..,100]", a.display().fit_width(1000).to_string());
+        assert_eq!(
+            "[1,..,100]",
+            DisplaySliceOwned::new(a.clone()).fit_width(11).to_string()
+        );
+    }
+
+    /// Counts the elements formatted.
+    struct Counted<'a>(u32, &'a core::cell::Cell<usize>);
+
+    impl core::fmt::Display for Counted<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            self.1.set(self.1.get() + 1);
+            write!(f, "{}", self.0)
+        }
+    }
+
+    /// Fitting the width of a large slice formats a number of elements that depends on the
+    /// width, not on the length.
+    #[test]
+    fn test_display_slice_fit_width_large() {
+        let n = core::cell::Cell::new(0);
+        let len = 1_000_000;
+        let a = (0..len).map(|i| Counted(i, &n)).collect::<Vec<_>>();
+
+        assert_eq!(
+            "[0,1,2,3,4,5,6,7,8,9,10,11,12,..,999999]",
+            a.display_n(len as usize).fit_width(40).to_string()
+        );
+        assert!(n.get() < 1000, "formatted {} elements", n.get());
+    }
+
     #[test]
     fn test_display_slice_braces() {
         let a = [1, 2, 3];
@@ -281,4 +334,40 @@ mod tests {
         // Empty braces
         assert_eq!("1,2,3", a.display().braces("", "").to_string());
     }
+
+    #[test]
+    fn test_display_slice_owned() {
+        let d = DisplaySliceOwned::new(vec![1, 2, 3, 4, 5, 6]).sep(", ");
+        let s = std::thread::spawn(move || d.to_string()).join().unwrap();
+        assert_eq!("[1, 2, 3, 4, .., 6]", s);
+
+        let d = DisplaySliceOwned::new(vec![1, 2, 3, 4, 5, 6, 7])
+            .at_most(Some(2))
+            .ellipsis("...")
+            .show_count()
+            .elem("'", "'")
+            .braces("{", "}");
+        assert_eq!(2, d.limit());
+        assert_eq!("{'1',...(7 total),'7'}", d.to_string());
+
+        assert_eq!("[]", DisplaySliceOwned::<u8>::new(vec![]).to_string());
+    }
+
+    #[test]
+    fn test_display_slice_debug() {
+        let a = [1, 2, 3, 4, 5, 6];
+        assert_eq!("[1,2,3,4,..,6]", format!("{:?}", a.display()));
+        assert_eq!("[1|..|6]", format!("{:?}", a.display_n(2).sep("|")));
+    }
+
+    #[test]
+    fn test_display_slice_owned_const() {
+        const EMPTY: DisplaySliceOwned<u64> = DisplaySliceOwned::new(Vec::new())
+            .at_most(Some(2))
+            .sep(", ")
+            .braces("{", "}");
+
+        assert_eq!("{}", EMPTY.to_string());
+        assert_eq!(2, EMPTY.limit());
+    }
 }
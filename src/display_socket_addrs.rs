@@ -0,0 +1,189 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::net::IpAddr;
+use core::net::SocketAddr;
+
+use crate::DisplayConfig;
+
+/// Display a list of socket addresses with the ports of the same host grouped, such as
+/// `10.0.0.1:[9000,9001], 10.0.0.2:9000`.
+///
+/// Hosts are displayed in the order they first appear. At most `limit` hosts are displayed,
+/// followed by `.. (n more)`; by default the limit is the [`DisplayConfig`] slice limit.
+///
+/// A list of `IpAddr` has nothing to group and can be displayed with
+/// [`DisplaySliceExt`](crate::DisplaySliceExt).
+#[must_use]
+pub struct DisplaySocketAddrs<'a> {
+    addrs: &'a [SocketAddr],
+    limit: Option<usize>,
+}
+
+impl<'a> DisplaySocketAddrs<'a> {
+    pub const fn new(addrs: &'a [SocketAddr]) -> Self {
+        Self { addrs, limit: None }
+    }
+
+    /// Set the max number of hosts to display.
+    pub const fn limit(self, limit: usize) -> Self {
+        Self {
+            limit: Some(limit),
+            ..self
+        }
+    }
+
+    /// Group the ports by host, in the order the hosts first appear.
+    fn groups(&self) -> Vec<(IpAddr, Vec<u16>)> {
+        let mut groups: Vec<(IpAddr, Vec<u16>)> = Vec::new();
+        for addr in self.addrs {
+            match groups.iter_mut().find(|(ip, _)| *ip == addr.ip()) {
+                Some((_, ports)) => ports.push(addr.port()),
+                None => groups.push((addr.ip(), vec![addr.port()])),
+            }
+        }
+        groups
+    }
+}
+
+impl fmt::Display for DisplaySocketAddrs<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let groups = self.groups();
+        let limit = self
+            .limit
+            .unwrap_or_else(|| DisplayConfig::current().slice_limit);
+
+        for (i, (ip, ports)) in groups.iter().take(limit).enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+
+            match ip {
+                IpAddr::V4(ip) => write!(f, "{}:", ip)?,
+                IpAddr::V6(ip) => write!(f, "[{}]:", ip)?,
+            }
+
+            if let [port] = ports.as_slice() {
+                write!(f, "{}", port)?;
+                continue;
+            }
+
+            f.write_str("[")?;
+            for (j, port) in ports.iter().enumerate() {
+                if j > 0 {
+                    f.write_str(",")?;
+                }
+                write!(f, "{}", port)?;
+            }
+            f.write_str("]")?;
+        }
+
+        if groups.len() > limit {
+            if limit > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, ".. ({} more)", groups.len() - limit)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for DisplaySocketAddrs<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a list of socket addresses grouped by host, for logging cluster membership.
+///
+/// # Example
+///
+/// ```rust
+/// use std::net::SocketAddr;
+///
+/// use display_more::DisplaySocketAddrsExt;
+///
+/// let addrs: Vec<SocketAddr> = ["10.0.0.1:9000", "10.0.0.2:9000", "10.0.0.1:9001"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+///
+/// assert_eq!(
+///     addrs.display_by_host().to_string(),
+///     "10.0.0.1:[9000,9001], 10.0.0.2:9000"
+/// );
+/// assert_eq!(
+///     addrs.display_by_host().limit(1).to_string(),
+///     "10.0.0.1:[9000,9001], .. (1 more)"
+/// );
+/// ```
+pub trait DisplaySocketAddrsExt {
+    fn display_by_host(&self) -> DisplaySocketAddrs<'_>;
+}
+
+impl DisplaySocketAddrsExt for [SocketAddr] {
+    fn display_by_host(&self) -> DisplaySocketAddrs<'_> {
+        DisplaySocketAddrs::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    fn addrs(s: &[&str]) -> Vec<SocketAddr> {
+        s.iter().map(|s| s.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_display_by_host() {
+        assert_eq!("", addrs(&[]).display_by_host().to_string());
+        assert_eq!(
+            "127.0.0.1:1",
+            addrs(&["127.0.0.1:1"]).display_by_host().to_string()
+        );
+        assert_eq!(
+            "[::1]:[1,2], 1.2.3.4:5",
+            addrs(&["[::1]:1", "1.2.3.4:5", "[::1]:2"])
+                .display_by_host()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_by_host_limit() {
+        let a = addrs(&[
+            "10.0.0.1:1",
+            "10.0.0.2:1",
+            "10.0.0.3:1",
+            "10.0.0.4:1",
+            "10.0.0.5:1",
+            "10.0.0.6:1",
+        ]);
+        assert_eq!(
+            "10.0.0.1:1, 10.0.0.2:1, 10.0.0.3:1, 10.0.0.4:1, 10.0.0.5:1, .. (1 more)",
+            a.display_by_host().to_string()
+        );
+        assert_eq!(".. (6 more)", a.display_by_host().limit(0).to_string());
+        assert_eq!(
+            "10.0.0.1:1, 10.0.0.2:1, 10.0.0.3:1, 10.0.0.4:1, 10.0.0.5:1, 10.0.0.6:1",
+            a.display_by_host().limit(6).to_string()
+        );
+    }
+}
@@ -0,0 +1,429 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+use core::fmt::Write;
+
+/// Keywords that are reserved in standard SQL, PostgreSQL or MySQL, sorted, in upper case.
+///
+/// An identifier equal to one of them, in any case, is quoted.
+const RESERVED_KEYWORDS: &[&str] = &[
+    "ALL",
+    "ALTER",
+    "ANALYSE",
+    "ANALYZE",
+    "AND",
+    "ANY",
+    "ARRAY",
+    "AS",
+    "ASC",
+    "ASYMMETRIC",
+    "BETWEEN",
+    "BOTH",
+    "BY",
+    "CASE",
+    "CAST",
+    "CHECK",
+    "COLLATE",
+    "COLUMN",
+    "CONSTRAINT",
+    "CREATE",
+    "CROSS",
+    "CURRENT_DATE",
+    "CURRENT_ROLE",
+    "CURRENT_TIME",
+    "CURRENT_TIMESTAMP",
+    "CURRENT_USER",
+    "DEFAULT",
+    "DEFERRABLE",
+    "DELETE",
+    "DESC",
+    "DISTINCT",
+    "DO",
+    "DROP",
+    "ELSE",
+    "END",
+    "EXCEPT",
+    "EXISTS",
+    "FALSE",
+    "FETCH",
+    "FOR",
+    "FOREIGN",
+    "FROM",
+    "FULL",
+    "GRANT",
+    "GROUP",
+    "HAVING",
+    "IN",
+    "INDEX",
+    "INITIALLY",
+    "INNER",
+    "INSERT",
+    "INTERSECT",
+    "INTERVAL",
+    "INTO",
+    "IS",
+    "JOIN",
+    "KEY",
+    "LATERAL",
+    "LEADING",
+    "LEFT",
+    "LIKE",
+    "LIMIT",
+    "LOCALTIME",
+    "LOCALTIMESTAMP",
+    "NATURAL",
+    "NOT",
+    "NULL",
+    "OFFSET",
+    "ON",
+    "ONLY",
+    "OR",
+    "ORDER",
+    "OUTER",
+    "PLACING",
+    "PRIMARY",
+    "REFERENCES",
+    "RETURNING",
+    "RIGHT",
+    "SELECT",
+    "SESSION_USER",
+    "SOME",
+    "SYMMETRIC",
+    "TABLE",
+    "THEN",
+    "TO",
+    "TRAILING",
+    "TRUE",
+    "UNION",
+    "UNIQUE",
+    "UPDATE",
+    "USER",
+    "USING",
+    "VALUES",
+    "VARIADIC",
+    "WHEN",
+    "WHERE",
+    "WINDOW",
+    "WITH",
+];
+
+/// The SQL dialect that decides how literals are escaped and identifiers are quoted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SqlDialect {
+    /// Standard SQL: a `'` in a string is doubled and a backslash has no special meaning.
+    /// Identifiers are quoted with `"`, and an identifier with an upper case letter is quoted
+    /// so that it is not case-folded, as PostgreSQL does.
+    #[default]
+    Standard,
+    /// MySQL style: a backslash starts an escape sequence, so backslashes and control chars such
+    /// as `\n` are escaped with a backslash as well. Identifiers are quoted with `` ` ``.
+    MySql,
+}
+
+enum Literal<'a> {
+    Str(&'a str),
+    Bytes(&'a [u8]),
+}
+
+/// Display a string or bytes as a SQL literal.
+///
+/// A string is single-quoted and escaped for the [`SqlDialect`]. Bytes are displayed as a
+/// hexadecimal literal such as `X'0aff'`, which is valid in every dialect.
+#[must_use]
+pub struct DisplaySqlLiteral<'a> {
+    value: Literal<'a>,
+    dialect: SqlDialect,
+}
+
+impl DisplaySqlLiteral<'_> {
+    /// Set the dialect that decides how the string is escaped. Default is [`SqlDialect::Standard`].
+    pub const fn dialect(self, dialect: SqlDialect) -> Self {
+        Self { dialect, ..self }
+    }
+}
+
+impl fmt::Display for DisplaySqlLiteral<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value {
+            Literal::Str(s) => {
+                f.write_char('\'')?;
+                for c in s.chars() {
+                    match (c, self.dialect) {
+                        ('\'', _) => f.write_str("''")?,
+                        ('\\', SqlDialect::MySql) => f.write_str("\\\\")?,
+                        ('\0', SqlDialect::MySql) => f.write_str("\\0")?,
+                        ('\n', SqlDialect::MySql) => f.write_str("\\n")?,
+                        ('\r', SqlDialect::MySql) => f.write_str("\\r")?,
+                        ('\t', SqlDialect::MySql) => f.write_str("\\t")?,
+                        ('\x1a', SqlDialect::MySql) => f.write_str("\\Z")?,
+                        (c, _) => f.write_char(c)?,
+                    }
+                }
+                f.write_char('\'')
+            }
+            Literal::Bytes(b) => {
+                f.write_str("X'")?;
+                for byte in b {
+                    write!(f, "{:02x}", byte)?;
+                }
+                f.write_char('\'')
+            }
+        }
+    }
+}
+
+impl fmt::Debug for DisplaySqlLiteral<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a string or bytes as a SQL literal, so that generated statements are valid SQL.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplaySqlLiteralExt;
+/// use display_more::SqlDialect;
+///
+/// assert_eq!("it's".display_sql_literal().to_string(), "'it''s'");
+/// assert_eq!(
+///     "a\\b\n"
+///         .display_sql_literal()
+///         .dialect(SqlDialect::MySql)
+///         .to_string(),
+///     r"'a\\b\n'"
+/// );
+/// assert_eq!(b"\x00\xff".display_sql_literal().to_string(), "X'00ff'");
+/// ```
+pub trait DisplaySqlLiteralExt {
+    fn display_sql_literal(&self) -> DisplaySqlLiteral<'_>;
+}
+
+impl DisplaySqlLiteralExt for str {
+    fn display_sql_literal(&self) -> DisplaySqlLiteral<'_> {
+        DisplaySqlLiteral {
+            value: Literal::Str(self),
+            dialect: SqlDialect::default(),
+        }
+    }
+}
+
+impl DisplaySqlLiteralExt for [u8] {
+    fn display_sql_literal(&self) -> DisplaySqlLiteral<'_> {
+        DisplaySqlLiteral {
+            value: Literal::Bytes(self),
+            dialect: SqlDialect::default(),
+        }
+    }
+}
+
+/// Display an identifier such as a database, table or column name, quoted only when needed.
+///
+/// An identifier that is made of ASCII letters, digits and `_`, does not start with a digit and
+/// is not a reserved keyword is output as is; with [`SqlDialect::Standard`] it must also be in
+/// lower case. Otherwise it is quoted with `"` or `` ` `` according to the [`SqlDialect`], and
+/// an embedded quote char is doubled.
+#[must_use]
+pub struct DisplaySqlIdent<'a> {
+    ident: &'a str,
+    dialect: SqlDialect,
+    force_quote: bool,
+}
+
+impl DisplaySqlIdent<'_> {
+    /// Set the dialect that decides the quote char. Default is [`SqlDialect::Standard`].
+    pub const fn dialect(self, dialect: SqlDialect) -> Self {
+        Self { dialect, ..self }
+    }
+
+    /// Quote the identifier even if it does not need to be quoted.
+    pub const fn force_quote(self, force_quote: bool) -> Self {
+        Self {
+            force_quote,
+            ..self
+        }
+    }
+
+    fn needs_quote(&self) -> bool {
+        let mut chars = self.ident.chars();
+        match chars.next() {
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+            _ => return true,
+        }
+        if !chars.all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return true;
+        }
+
+        // Unquoted, an identifier in Standard SQL is case-folded.
+        if self.dialect == SqlDialect::Standard
+            && self.ident.contains(|c: char| c.is_ascii_uppercase())
+        {
+            return true;
+        }
+
+        Self::is_reserved(self.ident)
+    }
+
+    /// Whether `ident` is a reserved keyword, case-insensitively.
+    fn is_reserved(ident: &str) -> bool {
+        let upper = ident.bytes().map(|b| b.to_ascii_uppercase());
+        RESERVED_KEYWORDS
+            .binary_search_by(|kw| kw.bytes().cmp(upper.clone()))
+            .is_ok()
+    }
+}
+
+impl fmt::Display for DisplaySqlIdent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.force_quote && !self.needs_quote() {
+            return f.write_str(self.ident);
+        }
+
+        let quote = match self.dialect {
+            SqlDialect::Standard => '"',
+            SqlDialect::MySql => '`',
+        };
+
+        f.write_char(quote)?;
+        for c in self.ident.chars() {
+            if c == quote {
+                f.write_char(quote)?;
+            }
+            f.write_char(c)?;
+        }
+        f.write_char(quote)
+    }
+}
+
+impl fmt::Debug for DisplaySqlIdent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a SQL identifier, quoted only when needed.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplaySqlIdentExt;
+/// use display_more::SqlDialect;
+///
+/// assert_eq!("users".display_sql_ident().to_string(), "users");
+/// assert_eq!("my table".display_sql_ident().to_string(), r#""my table""#);
+/// assert_eq!("order".display_sql_ident().to_string(), r#""order""#);
+/// assert_eq!("Users".display_sql_ident().to_string(), r#""Users""#);
+///
+/// let name = format!(
+///     "{}.{}",
+///     "db".display_sql_ident()
+///         .dialect(SqlDialect::MySql)
+///         .force_quote(true),
+///     "t`1".display_sql_ident().dialect(SqlDialect::MySql),
+/// );
+/// assert_eq!(name, "`db`.`t``1`");
+/// ```
+pub trait DisplaySqlIdentExt {
+    fn display_sql_ident(&self) -> DisplaySqlIdent<'_>;
+}
+
+impl DisplaySqlIdentExt for str {
+    fn display_sql_ident(&self) -> DisplaySqlIdent<'_> {
+        DisplaySqlIdent {
+            ident: self,
+            dialect: SqlDialect::default(),
+            force_quote: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_display_sql_literal() {
+        assert_eq!("''", "".display_sql_literal().to_string());
+        assert_eq!("'a''b'''", "a'b'".display_sql_literal().to_string());
+        assert_eq!("'a\\b\n'", "a\\b\n".display_sql_literal().to_string());
+
+        let s = String::from("x");
+        assert_eq!("'x'", s.display_sql_literal().to_string());
+    }
+
+    #[test]
+    fn test_display_sql_literal_mysql() {
+        let d = "a'\\\0\r\n\t\x1ab"
+            .display_sql_literal()
+            .dialect(SqlDialect::MySql);
+        assert_eq!(r"'a''\\\0\r\n\t\Zb'", d.to_string());
+    }
+
+    #[test]
+    fn test_display_sql_literal_bytes() {
+        assert_eq!("X''", b"".display_sql_literal().to_string());
+        assert_eq!("X'0aff'", [0x0a_u8, 0xff].display_sql_literal().to_string());
+    }
+
+    #[test]
+    fn test_display_sql_ident() {
+        assert_eq!("_a1", "_a1".display_sql_ident().to_string());
+        assert_eq!(r#""Users""#, "Users".display_sql_ident().to_string());
+        assert_eq!(r#""aB""#, "aB".display_sql_ident().to_string());
+        assert_eq!(r#""""#, "".display_sql_ident().to_string());
+        assert_eq!(r#""1a""#, "1a".display_sql_ident().to_string());
+        assert_eq!(r#""a-b""#, "a-b".display_sql_ident().to_string());
+        assert_eq!(r#""a""b""#, r#"a"b"#.display_sql_ident().to_string());
+        assert_eq!(r#""表""#, "表".display_sql_ident().to_string());
+        assert_eq!(
+            r#""a""#,
+            "a".display_sql_ident().force_quote(true).to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_sql_ident_reserved() {
+        assert_eq!(r#""order""#, "order".display_sql_ident().to_string());
+        assert_eq!(r#""select""#, "select".display_sql_ident().to_string());
+        assert_eq!(r#""SELECT""#, "SELECT".display_sql_ident().to_string());
+        assert_eq!(
+            r#""current_user""#,
+            "current_user".display_sql_ident().to_string()
+        );
+        assert_eq!("orders", "orders".display_sql_ident().to_string());
+        assert_eq!("sel", "sel".display_sql_ident().to_string());
+        assert_eq!("zzz", "zzz".display_sql_ident().to_string());
+
+        let mut sorted = RESERVED_KEYWORDS.to_vec();
+        sorted.sort_unstable();
+        assert_eq!(RESERVED_KEYWORDS, sorted.as_slice());
+    }
+
+    #[test]
+    fn test_display_sql_ident_mysql() {
+        let d = |s: &str| s.display_sql_ident().dialect(SqlDialect::MySql).to_string();
+        assert_eq!("a", d("a"));
+        assert_eq!("Users", d("Users"));
+        assert_eq!("`order`", d("order"));
+        assert_eq!("`Select`", d("Select"));
+        assert_eq!("`a b`", d("a b"));
+        assert_eq!("`a``b`", d("a`b"));
+        assert_eq!("`a\"b`", d("a\"b"));
+    }
+}
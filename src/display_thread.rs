@@ -0,0 +1,114 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::thread;
+use std::thread::Thread;
+
+/// Display a thread as its name and id, such as `worker-3 (ThreadId(17))`.
+///
+/// A thread without a name is displayed as `<unnamed> (ThreadId(17))`.
+#[must_use]
+pub struct DisplayThread {
+    thread: Thread,
+}
+
+impl DisplayThread {
+    pub const fn new(thread: Thread) -> Self {
+        Self { thread }
+    }
+}
+
+impl fmt::Display for DisplayThread {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.thread.name().unwrap_or("<unnamed>");
+        write!(f, "{} ({:?})", name, self.thread.id())
+    }
+}
+
+impl fmt::Debug for DisplayThread {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display the current thread as its name and id, for log prefixes.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::display_current_thread;
+///
+/// std::thread::Builder::new()
+///     .name("worker-3".to_string())
+///     .spawn(|| {
+///         let s = display_current_thread().to_string();
+///         assert!(s.starts_with("worker-3 (ThreadId("));
+///     })
+///     .unwrap()
+///     .join()
+///     .unwrap();
+/// ```
+pub fn display_current_thread() -> DisplayThread {
+    DisplayThread::new(thread::current())
+}
+
+/// Display a `Thread` as its name and id.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayThreadExt;
+///
+/// let t = std::thread::current();
+/// println!("{}", t.display());
+/// ```
+pub trait DisplayThreadExt {
+    fn display(&self) -> DisplayThread;
+}
+
+impl DisplayThreadExt for Thread {
+    fn display(&self) -> DisplayThread {
+        DisplayThread::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_display_thread() {
+        let s = thread::Builder::new()
+            .name("worker-3".to_string())
+            .spawn(|| display_current_thread().to_string())
+            .unwrap()
+            .join()
+            .unwrap();
+        assert!(s.starts_with("worker-3 (ThreadId("), "{}", s);
+        assert!(s.ends_with("))"), "{}", s);
+    }
+
+    #[test]
+    fn test_display_thread_unnamed() {
+        let h = thread::Builder::new()
+            .spawn(|| thread::current().display().to_string())
+            .unwrap();
+        let id = h.thread().id();
+        let s = h.join().unwrap();
+        assert_eq!(format!("<unnamed> ({:?})", id), s);
+    }
+}
@@ -0,0 +1,332 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+use alloc::string::ToString;
+use core::fmt;
+use core::fmt::Write;
+
+use crate::display_lines::LineWriter;
+use crate::display_once;
+use crate::Summary;
+
+/// Display a tree with box-drawing branches, such as a query plan or a directory structure.
+///
+/// A node is displayed by the `label` function and its children are listed by the `children`
+/// function. Multi-line labels are indented to stay within their branch.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayTree;
+///
+/// struct Node {
+///     name: &'static str,
+///     children: Vec<Node>,
+/// }
+///
+/// let leaf = |name| Node {
+///     name,
+///     children: vec![],
+/// };
+/// let root = Node {
+///     name: "Project",
+///     children: vec![
+///         Node {
+///             name: "Filter",
+///             children: vec![leaf("Scan t1")],
+///         },
+///         leaf("Scan t2"),
+///     ],
+/// };
+///
+/// let t = DisplayTree::new(&root, |n, f| f.write_str(n.name), |n| &n.children);
+/// assert_eq!(
+///     t.to_string(),
+///     ["Project", "├── Filter", "│   └── Scan t1", "└── Scan t2",].join("\n")
+/// );
+/// ```
+#[must_use]
+pub struct DisplayTree<'a, N, L, C> {
+    root: &'a N,
+    label: L,
+    children: C,
+    max_depth: Option<usize>,
+    max_breadth: Option<usize>,
+    fit_width: Option<usize>,
+}
+
+impl<'a, N, L, C, I> DisplayTree<'a, N, L, C>
+where
+    L: Fn(&N, &mut fmt::Formatter<'_>) -> fmt::Result,
+    C: Fn(&'a N) -> I,
+    I: IntoIterator<Item = &'a N>,
+{
+    pub const fn new(root: &'a N, label: L, children: C) -> Self {
+        Self {
+            root,
+            label,
+            children,
+            max_depth: None,
+            max_breadth: None,
+            fit_width: None,
+        }
+    }
+
+    /// Set the max number of levels displayed below the root. By default it is unlimited.
+    ///
+    /// The children of a node at the max depth are replaced with a single `..`.
+    pub fn max_depth(self, max_depth: usize) -> Self {
+        Self {
+            max_depth: Some(max_depth),
+            ..self
+        }
+    }
+
+    /// Set the max number of children displayed for each node. By default it is unlimited.
+    ///
+    /// Children beyond the limit are replaced with `.. (n more)`.
+    pub fn max_breadth(self, max_breadth: usize) -> Self {
+        Self {
+            max_breadth: Some(max_breadth),
+            ..self
+        }
+    }
+
+    /// Cut lines longer than `width` chars, e.g., [`terminal_width()`](crate::terminal_width).
+    /// Cut lines end with [`Summary::MARKER`].
+    pub fn fit_width(self, width: usize) -> Self {
+        Self {
+            fit_width: Some(width),
+            ..self
+        }
+    }
+
+    fn fmt_tree(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.label)(self.root, f)?;
+        self.fmt_children(f, self.root, 0, "")
+    }
+
+    fn fmt_children(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        node: &'a N,
+        depth: usize,
+        prefix: &str,
+    ) -> fmt::Result {
+        let mut children = (self.children)(node).into_iter().peekable();
+        if children.peek().is_none() {
+            return Ok(());
+        }
+
+        if self.max_depth.is_some_and(|d| depth >= d) {
+            return write!(f, "\n{}└── ..", prefix);
+        }
+
+        let max_breadth = self.max_breadth.unwrap_or(usize::MAX);
+        let mut shown = 0;
+
+        while let Some(child) = children.next() {
+            if shown == max_breadth {
+                let more = 1 + children.count();
+                return write!(f, "\n{}└── .. ({} more)", prefix, more);
+            }
+
+            // A child followed by `.. (n more)` is not the last line either.
+            let (branch, indent) = if children.peek().is_none() {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+
+            f.write_str("\n")?;
+            {
+                let mut first = true;
+                let mut w = LineWriter::new(f, |f| {
+                    let b = if first { branch } else { indent };
+                    first = false;
+                    write!(f, "{}{}", prefix, b)
+                });
+                write!(w, "{}", Label {
+                    node: child,
+                    label: &self.label
+                })?;
+            }
+
+            let child_prefix = format!("{}{}", prefix, indent);
+            self.fmt_children(f, child, depth + 1, &child_prefix)?;
+            shown += 1;
+        }
+
+        Ok(())
+    }
+}
+
+/// Display a node with the label function.
+struct Label<'a, N, L> {
+    node: &'a N,
+    label: &'a L,
+}
+
+impl<N, L> fmt::Display for Label<'_, N, L>
+where L: Fn(&N, &mut fmt::Formatter<'_>) -> fmt::Result
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.label)(self.node, f)
+    }
+}
+
+impl<'a, N, L, C, I> fmt::Display for DisplayTree<'a, N, L, C>
+where
+    L: Fn(&N, &mut fmt::Formatter<'_>) -> fmt::Result,
+    C: Fn(&'a N) -> I,
+    I: IntoIterator<Item = &'a N>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(width) = self.fit_width else {
+            return self.fmt_tree(f);
+        };
+
+        let s = display_once(|f| self.fmt_tree(f)).to_string();
+        for (i, line) in s.split('\n').enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{}", Summary::of(line, width))?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, N, L, C, I> fmt::Debug for DisplayTree<'a, N, L, C>
+where
+    L: Fn(&N, &mut fmt::Formatter<'_>) -> fmt::Result,
+    C: Fn(&'a N) -> I,
+    I: IntoIterator<Item = &'a N>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Node {
+        name: String,
+        children: Vec<Node>,
+    }
+
+    fn node(name: &str, children: Vec<Node>) -> Node {
+        Node {
+            name: name.to_string(),
+            children,
+        }
+    }
+
+    fn sample() -> Node {
+        node("a", vec![
+            node("b", vec![
+                node("c", vec![]),
+                node("d", vec![node("e", vec![])]),
+            ]),
+            node("f", vec![]),
+            node("g", vec![node("h", vec![])]),
+        ])
+    }
+
+    fn label(n: &Node, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&n.name)
+    }
+
+    fn children(n: &Node) -> &Vec<Node> {
+        &n.children
+    }
+
+    macro_rules! tree {
+        ($n:expr) => {
+            DisplayTree::new($n, label, children)
+        };
+    }
+
+    #[test]
+    fn test_display_tree() {
+        let root = sample();
+        assert_eq!(
+            tree!(&root).to_string(),
+            [
+                "a",
+                "├── b",
+                "│   ├── c",
+                "│   └── d",
+                "│       └── e",
+                "├── f",
+                "└── g",
+                "    └── h",
+            ]
+            .join("\n")
+        );
+
+        assert_eq!("x", tree!(&node("x", vec![])).to_string());
+    }
+
+    #[test]
+    fn test_display_tree_limits() {
+        let root = sample();
+        assert_eq!(
+            tree!(&root).max_depth(1).to_string(),
+            ["a", "├── b", "│   └── ..", "├── f", "└── g", "    └── ..",].join("\n")
+        );
+
+        assert_eq!(
+            tree!(&root).max_breadth(1).to_string(),
+            [
+                "a",
+                "├── b",
+                "│   ├── c",
+                "│   └── .. (1 more)",
+                "└── .. (2 more)",
+            ]
+            .join("\n")
+        );
+
+        assert_eq!("a\n└── ..", tree!(&root).max_depth(0).to_string());
+        assert_eq!(
+            "a\n└── .. (3 more)",
+            tree!(&root).max_breadth(0).to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_tree_fit_width() {
+        let root = sample();
+        assert_eq!(
+            tree!(&root).max_depth(1).fit_width(6).to_string(),
+            ["a", "├── b", "│   └…", "├── f", "└── g", "    └…"].join("\n")
+        );
+    }
+
+    #[test]
+    fn test_display_tree_multi_line_label() {
+        let root = node("root", vec![
+            node("x\ny", vec![node("z", vec![])]),
+            node("p\nq", vec![]),
+        ]);
+        assert_eq!(
+            tree!(&root).to_string(),
+            ["root", "├── x", "│   y", "│   └── z", "└── p", "    q",].join("\n")
+        );
+    }
+}
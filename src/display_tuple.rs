@@ -0,0 +1,146 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+
+/// Display the members of a tuple, as `(a, b, c)` or joined with a custom separator.
+#[must_use]
+pub struct DisplayTuple<'a, T: ?Sized> {
+    inner: &'a T,
+    sep: &'a str,
+    parens: bool,
+}
+
+impl<'a, T: ?Sized> DisplayTuple<'a, T> {
+    pub const fn new(inner: &'a T) -> Self {
+        Self {
+            inner,
+            sep: ", ",
+            parens: true,
+        }
+    }
+
+    /// Join the members with `sep` and drop the enclosing parentheses, e.g. `a=1` for `sep("=")`.
+    pub const fn sep(self, sep: &'a str) -> Self {
+        Self {
+            sep,
+            parens: false,
+            ..self
+        }
+    }
+
+    /// Whether to enclose the members in parentheses.
+    pub const fn parens(self, parens: bool) -> Self {
+        Self { parens, ..self }
+    }
+}
+
+impl<T: DisplayTupleExt + ?Sized> fmt::Display for DisplayTuple<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.parens {
+            write!(f, "(")?;
+        }
+        self.inner.fmt_members(f, self.sep)?;
+        if self.parens {
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: DisplayTupleExt + ?Sized> fmt::Debug for DisplayTuple<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a tuple of up to 8 `Display` members, without destructuring it.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayTupleExt;
+///
+/// assert_eq!(("a", 1, 2.5).display().to_string(), "(a, 1, 2.5)");
+/// assert_eq!(("k", "v").display_with("=").to_string(), "k=v");
+/// ```
+pub trait DisplayTupleExt {
+    /// Write the members joined with `sep`.
+    fn fmt_members(&self, f: &mut fmt::Formatter<'_>, sep: &str) -> fmt::Result;
+
+    fn display(&self) -> DisplayTuple<'_, Self> {
+        DisplayTuple::new(self)
+    }
+
+    /// Display the members joined with `sep`, without parentheses.
+    fn display_with<'a>(&'a self, sep: &'a str) -> DisplayTuple<'a, Self> {
+        DisplayTuple::new(self).sep(sep)
+    }
+}
+
+macro_rules! impl_tuple {
+    ($first:ident $(, $rest:ident)*) => {
+        impl<$first: fmt::Display, $($rest: fmt::Display),*> DisplayTupleExt for ($first, $($rest,)*) {
+            #[allow(non_snake_case, unused_variables)]
+            fn fmt_members(&self, f: &mut fmt::Formatter<'_>, sep: &str) -> fmt::Result {
+                let ($first, $($rest,)*) = self;
+                write!(f, "{}", $first)?;
+                $(
+                    write!(f, "{}{}", sep, $rest)?;
+                )*
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_tuple!(A);
+impl_tuple!(A, B);
+impl_tuple!(A, B, C);
+impl_tuple!(A, B, C, D);
+impl_tuple!(A, B, C, D, E);
+impl_tuple!(A, B, C, D, E, F);
+impl_tuple!(A, B, C, D, E, F, G);
+impl_tuple!(A, B, C, D, E, F, G, H);
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_display_tuple() {
+        assert_eq!("(1)", (1,).display().to_string());
+        assert_eq!("(1, a)", (1, "a").display().to_string());
+        assert_eq!(
+            "(1, 2, 3, 4, 5, 6, 7, 8)",
+            (1, 2, 3, 4, 5, 6, 7, 8).display().to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_tuple_sep() {
+        assert_eq!("k=v", ("k", "v").display_with("=").to_string());
+        assert_eq!(
+            "a -> b -> c",
+            ("a", "b", "c").display_with(" -> ").to_string()
+        );
+        assert_eq!(
+            "(a; b)",
+            ("a", "b").display().sep("; ").parens(true).to_string()
+        );
+        assert_eq!("x", ("x",).display_with(", ").to_string());
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::any::type_name;
+use core::fmt;
+
+/// Display a type name with the module paths stripped, such as `HashMap<String, Vec<u8>>` for
+/// `std::collections::hash::map::HashMap<alloc::string::String, alloc::vec::Vec<u8>>`.
+///
+/// Every path in the name is shortened, including those in generic parameters, `dyn` and
+/// `impl` types.
+#[must_use]
+pub struct DisplayTypeName {
+    name: &'static str,
+}
+
+impl DisplayTypeName {
+    pub const fn new(name: &'static str) -> Self {
+        Self { name }
+    }
+}
+
+impl fmt::Display for DisplayTypeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+
+        let s = self.name;
+        let bytes = s.as_bytes();
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let start = i;
+            if is_ident(bytes[i]) {
+                while i < bytes.len() && is_ident(bytes[i]) {
+                    i += 1;
+                }
+                // A module segment: skip it together with the `::` after it.
+                if s[i..].starts_with("::") && bytes.get(i + 2).is_some_and(|b| is_ident(*b)) {
+                    i += 2;
+                    continue;
+                }
+            } else {
+                i += 1;
+                while i < bytes.len() && !is_ident(bytes[i]) {
+                    i += 1;
+                }
+            }
+            f.write_str(&s[start..i])?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for DisplayTypeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display the short name of type `T`, for concise type tags in error messages.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+///
+/// use display_more::display_type_name;
+///
+/// assert_eq!(
+///     display_type_name::<HashMap<String, Vec<u8>>>().to_string(),
+///     "HashMap<String, Vec<u8>>"
+/// );
+/// ```
+pub fn display_type_name<T: ?Sized>() -> DisplayTypeName {
+    DisplayTypeName::new(type_name::<T>())
+}
+
+/// Display the short type name of a value.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayTypeOfExt;
+///
+/// let v = vec![Some(1u8)];
+/// assert_eq!(v.display_type_of().to_string(), "Vec<Option<u8>>");
+/// ```
+pub trait DisplayTypeOfExt {
+    fn display_type_of(&self) -> DisplayTypeName {
+        display_type_name::<Self>()
+    }
+}
+
+impl<T: ?Sized> DisplayTypeOfExt for T {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::boxed::Box;
+    use alloc::collections::BTreeMap;
+    use alloc::string::String;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    fn short(name: &'static str) -> String {
+        DisplayTypeName::new(name).to_string()
+    }
+
+    #[test]
+    fn test_display_type_name() {
+        assert_eq!("u8", display_type_name::<u8>().to_string());
+        assert_eq!(
+            "BTreeMap<String, Vec<u8>>",
+            display_type_name::<BTreeMap<String, Vec<u8>>>().to_string()
+        );
+        assert_eq!(
+            "Box<dyn Display>",
+            display_type_name::<Box<dyn fmt::Display>>().to_string()
+        );
+        assert_eq!(
+            "(&str, [Option<i32>; 2])",
+            display_type_name::<(&str, [Option<i32>; 2])>().to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_type_name_paths() {
+        assert_eq!("Baz<Qux>", short("foo::bar::Baz<foo::Qux>"));
+        assert_eq!("<T as Trait>::Assoc", short("<a::T as b::Trait>::Assoc"));
+        assert_eq!("f::{{closure}}", short("m::f::{{closure}}"));
+        assert_eq!("", short(""));
+    }
+
+    #[test]
+    fn test_display_type_of() {
+        let v: Vec<u8> = Vec::new();
+        assert_eq!("Vec<u8>", v.display_type_of().to_string());
+        assert_eq!("str", "x".display_type_of().to_string());
+    }
+}
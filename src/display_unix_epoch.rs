@@ -12,45 +12,56 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt;
-use std::time::Duration;
+use core::fmt;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+#[cfg(feature = "std")]
 use std::time::UNIX_EPOCH;
 
 use chrono::DateTime;
+use chrono::Datelike;
+use chrono::Timelike;
 use chrono::Utc;
 
+use crate::display_once;
+use crate::DisplayConfig;
+
+#[must_use]
 pub struct DisplayUnixTimeStamp {
     /// The duration since the UNIX epoch.
-    duration: Option<Duration>,
+    pub(crate) duration: Option<Duration>,
 
-    in_millis: bool,
+    pub(crate) in_millis: bool,
 
-    with_timezone: bool,
+    pub(crate) with_timezone: bool,
 }
 
 impl fmt::Display for DisplayUnixTimeStamp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let duration = match self.duration {
-            Some(d) => d,
-            None => return write!(f, "None"),
+        let Some(datetime) = self.datetime() else {
+            return write_styled!(f, Dim, "{}", DisplayConfig::current().none);
         };
 
-        let system_time = UNIX_EPOCH + duration;
-        let datetime: DateTime<Utc> = system_time.into();
-
-        let fmt = if self.in_millis {
-            if self.with_timezone {
-                "%Y-%m-%dT%H:%M:%S%.3fZ%z"
-            } else {
-                "%Y-%m-%dT%H:%M:%S%.3f"
-            }
-        } else if self.with_timezone {
-            "%Y-%m-%dT%H:%M:%S%.6fZ%z"
-        } else {
-            "%Y-%m-%dT%H:%M:%S%.6f"
-        };
+        write_styled!(
+            f,
+            Cyan,
+            "{}",
+            display_once(|f| {
+                self.fmt_datetime(f, &datetime)?;
+                if self.with_timezone {
+                    // The time is always in UTC.
+                    f.write_str("Z+0000")?;
+                }
+                Ok(())
+            })
+        )
+    }
+}
 
-        write!(f, "{}", datetime.format(fmt))
+impl fmt::Debug for DisplayUnixTimeStamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
     }
 }
 
@@ -63,11 +74,57 @@ impl DisplayUnixTimeStamp {
         }
     }
 
-    pub fn in_millis(self, in_millis: bool) -> Self {
+    /// Return the time as a `DateTime`, or `None` if there is no time or it is out of the range
+    /// of `DateTime`.
+    pub(crate) fn datetime(&self) -> Option<DateTime<Utc>> {
+        let duration = self.duration?;
+        let secs = i64::try_from(duration.as_secs()).ok()?;
+        DateTime::<Utc>::from_timestamp(secs, duration.subsec_nanos())
+    }
+
+    /// Write the date and time without timezone, such as `2024-08-08T07:40:19.023`.
+    ///
+    /// The components are written directly, without chrono's `format()`, so that no memory is
+    /// allocated.
+    pub(crate) fn fmt_datetime(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        datetime: &DateTime<Utc>,
+    ) -> fmt::Result {
+        let precision = if self.in_millis {
+            3
+        } else {
+            DisplayConfig::current().timestamp_precision
+        };
+
+        // Same as chrono's `%Y`: a year out of `0..=9999` has a sign.
+        let year = datetime.year();
+        if (0..=9999).contains(&year) {
+            write!(f, "{:04}", year)?;
+        } else {
+            write!(f, "{:+}", year)?;
+        }
+        write!(
+            f,
+            "-{:02}-{:02}T{:02}:{:02}:{:02}",
+            datetime.month(),
+            datetime.day(),
+            datetime.hour(),
+            datetime.minute(),
+            datetime.second()
+        )?;
+        if precision > 0 {
+            let frac = datetime.timestamp_subsec_nanos() / 10u32.pow(9 - precision as u32);
+            write!(f, ".{:0width$}", frac, width = precision)?;
+        }
+        Ok(())
+    }
+
+    pub const fn in_millis(self, in_millis: bool) -> Self {
         Self { in_millis, ..self }
     }
 
-    pub fn with_timezone(self, with_timezone: bool) -> Self {
+    pub const fn with_timezone(self, with_timezone: bool) -> Self {
         Self {
             with_timezone,
             ..self
@@ -113,6 +170,14 @@ impl DisplayUnixTimeStampExt for Option<Duration> {
     }
 }
 
+/// A `SystemTime` earlier than the UNIX epoch is displayed as `None`.
+#[cfg(feature = "std")]
+impl DisplayUnixTimeStampExt for SystemTime {
+    fn display_unix_timestamp(&self) -> DisplayUnixTimeStamp {
+        DisplayUnixTimeStamp::new(self.duration_since(UNIX_EPOCH).ok())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
@@ -154,4 +219,44 @@ mod tests {
         assert_eq!(none.display_unix_timestamp().to_string(), "None");
         assert_eq!(none.display_unix_timestamp_short().to_string(), "None");
     }
+
+    #[test]
+    fn test_display_unix_epoch_debug() {
+        let epoch = Duration::from_millis(1723102819023);
+        assert_eq!(
+            format!("{:?}", epoch.display_unix_timestamp_short()),
+            "2024-08-08T07:40:19.023"
+        );
+    }
+
+    #[test]
+    fn test_display_system_time() {
+        let t = UNIX_EPOCH + Duration::from_millis(1723102819023);
+        assert_eq!(
+            t.display_unix_timestamp().to_string(),
+            "2024-08-08T07:40:19.023000Z+0000"
+        );
+
+        let before_epoch = UNIX_EPOCH - Duration::from_secs(1);
+        assert_eq!(before_epoch.display_unix_timestamp().to_string(), "None");
+    }
+
+    #[test]
+    fn test_display_unix_epoch_same_as_chrono_format() {
+        for secs in [
+            0,
+            59,
+            951_782_400,
+            1_723_102_819,
+            253_402_300_799,
+            253_402_300_800,
+        ] {
+            let d = Duration::new(secs, 123_456_789);
+            let datetime = DateTime::<Utc>::from_timestamp(secs as i64, 123_456_789).unwrap();
+            assert_eq!(
+                d.display_unix_timestamp().to_string(),
+                format!("{}Z+0000", datetime.format("%Y-%m-%dT%H:%M:%S%.6f"))
+            );
+        }
+    }
 }
@@ -0,0 +1,105 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Short display of `Uuid`, enabled by the `uuid` feature.
+
+use core::fmt;
+
+use uuid::Uuid;
+
+/// Display the first hex chars of a `Uuid` followed by `..`, such as `67e55044..`.
+///
+/// If the prefix length is at least 32, the full simple form is displayed without `..`.
+#[must_use]
+pub struct DisplayUuidShort {
+    pub(crate) uuid: Uuid,
+    pub(crate) prefix_len: usize,
+}
+
+impl DisplayUuidShort {
+    pub const fn new(uuid: Uuid) -> Self {
+        Self {
+            uuid,
+            prefix_len: 8,
+        }
+    }
+
+    /// Set the number of hex chars to display. By default it is 8.
+    pub const fn prefix_len(self, prefix_len: usize) -> Self {
+        Self { prefix_len, ..self }
+    }
+}
+
+impl fmt::Display for DisplayUuidShort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = Uuid::encode_buffer();
+        let hex = self.uuid.simple().encode_lower(&mut buf);
+
+        if self.prefix_len >= hex.len() {
+            return f.write_str(hex);
+        }
+        write!(f, "{}..", &hex[..self.prefix_len])
+    }
+}
+
+impl fmt::Debug for DisplayUuidShort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a `Uuid` by a short prefix that keeps it recognizable in a log line.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayUuidExt;
+/// use uuid::Uuid;
+///
+/// let id = Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+/// assert_eq!(id.display_short().to_string(), "67e55044..");
+/// assert_eq!(id.display_short().prefix_len(4).to_string(), "67e5..");
+/// ```
+pub trait DisplayUuidExt {
+    fn display_short(&self) -> DisplayUuidShort;
+}
+
+impl DisplayUuidExt for Uuid {
+    fn display_short(&self) -> DisplayUuidShort {
+        DisplayUuidShort::new(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_display_uuid_short() {
+        let id = Uuid::parse_str("67E55044-10B1-426F-9247-BB680E5FE0C8").unwrap();
+        assert_eq!("67e55044..", id.display_short().to_string());
+        assert_eq!("..", id.display_short().prefix_len(0).to_string());
+        assert_eq!(
+            "67e5504410b1426f9247bb680e5fe0c8",
+            id.display_short().prefix_len(32).to_string()
+        );
+        assert_eq!(
+            "67e5504410b1426f9247bb680e5fe0c8",
+            id.display_short().prefix_len(100).to_string()
+        );
+        assert_eq!("00000000..", Uuid::nil().display_short().to_string());
+    }
+}
@@ -0,0 +1,201 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+use core::fmt::Write;
+
+/// Display a `str` or `char` with control and invisible chars made visible.
+///
+/// By default they are displayed as escapes, such as `\n`, `\0` or `\u{200b}`. With
+/// [`symbols`](Self::symbols), ASCII control chars are displayed as Unicode control pictures
+/// instead, such as `␊` for `\n`. In both modes `\` is displayed as `\\`.
+///
+/// Invisible chars are the C0 and C1 controls, `DEL`, no-break and zero-width spaces, the soft
+/// hyphen, word joiners, bidi controls and the byte order mark.
+#[must_use]
+pub struct DisplayVisible<'a> {
+    text: Text<'a>,
+    symbols: bool,
+}
+
+enum Text<'a> {
+    Str(&'a str),
+    Char(char),
+}
+
+impl<'a> DisplayVisible<'a> {
+    pub const fn new(s: &'a str) -> Self {
+        Self {
+            text: Text::Str(s),
+            symbols: false,
+        }
+    }
+
+    pub const fn new_char(c: char) -> Self {
+        Self {
+            text: Text::Char(c),
+            symbols: false,
+        }
+    }
+
+    /// Display ASCII control chars as control pictures such as `␀` and `␊`, instead of escapes.
+    pub const fn symbols(self) -> Self {
+        Self {
+            symbols: true,
+            ..self
+        }
+    }
+}
+
+/// Return whether a char is invisible or may be mistaken for another.
+fn is_invisible(c: char) -> bool {
+    matches!(c,
+        '\u{0}'..='\u{1f}'
+        | '\u{7f}'..='\u{a0}'
+        | '\u{ad}'
+        | '\u{200b}'..='\u{200f}'
+        | '\u{2028}'..='\u{202f}'
+        | '\u{2060}'..='\u{2064}'
+        | '\u{2066}'..='\u{2069}'
+        | '\u{feff}'
+    )
+}
+
+impl DisplayVisible<'_> {
+    fn fmt_char(&self, c: char, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // `\` is escaped in both modes, so that it can not be mistaken for the start of an
+        // escape.
+        if c == '\\' {
+            return f.write_str("\\\\");
+        }
+
+        if self.symbols {
+            match c {
+                '\u{0}'..='\u{1f}' => {
+                    return f.write_char(char::from_u32(0x2400 + c as u32).unwrap_or(c));
+                }
+                '\u{7f}' => return f.write_char('\u{2421}'),
+                _ => {}
+            }
+        } else {
+            let esc = match c {
+                '\n' => "\\n",
+                '\r' => "\\r",
+                '\t' => "\\t",
+                '\0' => "\\0",
+                _ => "",
+            };
+            if !esc.is_empty() {
+                return f.write_str(esc);
+            }
+        }
+
+        if is_invisible(c) {
+            write!(f, "\\u{{{:x}}}", c as u32)
+        } else {
+            f.write_char(c)
+        }
+    }
+}
+
+impl fmt::Display for DisplayVisible<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.text {
+            Text::Str(s) => {
+                for c in s.chars() {
+                    self.fmt_char(c, f)?;
+                }
+                Ok(())
+            }
+            Text::Char(c) => self.fmt_char(c, f),
+        }
+    }
+}
+
+impl fmt::Debug for DisplayVisible<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a `str` or `char` with control and invisible chars made visible, so that strings
+/// that look the same in a log can be told apart.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayVisibleExt;
+///
+/// assert_eq!(
+///     "a\u{200b}b\n".display_visible().to_string(),
+///     "a\\u{200b}b\\n"
+/// );
+/// assert_eq!("a\tb".display_visible().symbols().to_string(), "a␉b");
+/// assert_eq!('\0'.display_visible().to_string(), "\\0");
+/// ```
+pub trait DisplayVisibleExt {
+    fn display_visible(&self) -> DisplayVisible<'_>;
+}
+
+impl DisplayVisibleExt for str {
+    fn display_visible(&self) -> DisplayVisible<'_> {
+        DisplayVisible::new(self)
+    }
+}
+
+impl DisplayVisibleExt for char {
+    fn display_visible(&self) -> DisplayVisible<'_> {
+        DisplayVisible::new_char(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_display_visible_escapes() {
+        assert_eq!("abc", "abc".display_visible().to_string());
+        assert_eq!("a\\\\b", "a\\b".display_visible().to_string());
+        assert_eq!("\\r\\n\\t\\0", "\r\n\t\0".display_visible().to_string());
+        assert_eq!("\\u{1b}[0m", "\x1b[0m".display_visible().to_string());
+        assert_eq!("\\u{7f}", "\x7f".display_visible().to_string());
+        assert_eq!("a\\u{a0}b", "a\u{a0}b".display_visible().to_string());
+        assert_eq!("\\u{feff}x", "\u{feff}x".display_visible().to_string());
+        assert_eq!("é中", "é中".display_visible().to_string());
+    }
+
+    #[test]
+    fn test_display_visible_symbols() {
+        assert_eq!("␀␊␡", "\0\n\x7f".display_visible().symbols().to_string());
+        assert_eq!("a\\\\b", "a\\b".display_visible().symbols().to_string());
+        assert_eq!(
+            "\\u{200b}\\\\u{200b}",
+            "\u{200b}\\u{200b}".display_visible().symbols().to_string()
+        );
+        assert_eq!(
+            "\\u{200b}",
+            "\u{200b}".display_visible().symbols().to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_visible_char() {
+        assert_eq!("\\n", '\n'.display_visible().to_string());
+        assert_eq!("␛", '\x1b'.display_visible().symbols().to_string());
+        assert_eq!("x", 'x'.display_visible().to_string());
+    }
+}
@@ -0,0 +1,178 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use core::fmt;
+
+/// Display a map of node to sequence number, sorted by node, followed by how far the nodes
+/// behind the highest one lag, such as `n1:482, n2:480, n3:Ø (lag: n3 behind by ∞, n2 by 2)`.
+///
+/// A node without a sequence number is displayed as `Ø` and lags by `∞`. The lagging nodes are
+/// listed from the furthest behind. The lag part is omitted if no node is behind.
+#[must_use]
+pub struct DisplayWatermarks<'a, K> {
+    entries: Vec<(&'a K, Option<u64>)>,
+}
+
+impl<'a, K: Ord> DisplayWatermarks<'a, K> {
+    pub fn new(entries: impl IntoIterator<Item = (&'a K, Option<u64>)>) -> Self {
+        let mut entries: Vec<_> = entries.into_iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        Self { entries }
+    }
+}
+
+impl<K: fmt::Display> fmt::Display for DisplayWatermarks<'_, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (node, seq)) in self.entries.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            match seq {
+                Some(seq) => write!(f, "{}:{}", node, seq)?,
+                None => write!(f, "{}:Ø", node)?,
+            }
+        }
+
+        let Some(max) = self.entries.iter().filter_map(|(_, seq)| *seq).max() else {
+            return Ok(());
+        };
+
+        // `None` lags by infinity, ordered before any finite lag.
+        let mut lagging: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|(_, seq)| *seq != Some(max))
+            .map(|(node, seq)| (*node, seq.map(|s| max - s)))
+            .collect();
+
+        if lagging.is_empty() {
+            return Ok(());
+        }
+        lagging.sort_by_key(|(_, lag)| lag.map(Reverse));
+
+        write!(f, " (lag: ")?;
+        for (i, (node, lag)) in lagging.iter().enumerate() {
+            let behind = if i == 0 { " behind" } else { "" };
+            match lag {
+                Some(lag) => write!(f, "{}{} by {}", node, behind, lag)?,
+                None => write!(f, "{}{} by ∞", node, behind)?,
+            }
+            if i + 1 < lagging.len() {
+                write!(f, ", ")?;
+            }
+        }
+        write!(f, ")")
+    }
+}
+
+impl<K: fmt::Display> fmt::Debug for DisplayWatermarks<'_, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a map of node to sequence number, such as a version vector or the replication
+/// progress of followers, with the lag of each node behind the highest one.
+///
+/// The values may be `u64` or `Option<u64>`, where `None` means no sequence number yet.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::BTreeMap;
+///
+/// use display_more::DisplayWatermarksExt;
+///
+/// let m = BTreeMap::from([("n1", Some(482)), ("n2", Some(480)), ("n3", None)]);
+/// assert_eq!(
+///     m.display_watermarks().to_string(),
+///     "n1:482, n2:480, n3:Ø (lag: n3 behind by ∞, n2 by 2)"
+/// );
+/// ```
+pub trait DisplayWatermarksExt<K> {
+    fn display_watermarks(&self) -> DisplayWatermarks<'_, K>;
+}
+
+impl<K: Ord> DisplayWatermarksExt<K> for BTreeMap<K, u64> {
+    fn display_watermarks(&self) -> DisplayWatermarks<'_, K> {
+        DisplayWatermarks::new(self.iter().map(|(k, v)| (k, Some(*v))))
+    }
+}
+
+impl<K: Ord> DisplayWatermarksExt<K> for BTreeMap<K, Option<u64>> {
+    fn display_watermarks(&self) -> DisplayWatermarks<'_, K> {
+        DisplayWatermarks::new(self.iter().map(|(k, v)| (k, *v)))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Ord, S> DisplayWatermarksExt<K> for std::collections::HashMap<K, u64, S> {
+    fn display_watermarks(&self) -> DisplayWatermarks<'_, K> {
+        DisplayWatermarks::new(self.iter().map(|(k, v)| (k, Some(*v))))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Ord, S> DisplayWatermarksExt<K> for std::collections::HashMap<K, Option<u64>, S> {
+    fn display_watermarks(&self) -> DisplayWatermarks<'_, K> {
+        DisplayWatermarks::new(self.iter().map(|(k, v)| (k, *v)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_display_watermarks() {
+        let m = BTreeMap::from([(3, 10u64), (1, 10), (2, 7)]);
+        assert_eq!(
+            "1:10, 2:7, 3:10 (lag: 2 behind by 3)",
+            m.display_watermarks().to_string()
+        );
+
+        let m = BTreeMap::from([("a", 5u64), ("b", 5)]);
+        assert_eq!("a:5, b:5", m.display_watermarks().to_string());
+
+        let m = BTreeMap::<u64, u64>::new();
+        assert_eq!("", m.display_watermarks().to_string());
+    }
+
+    #[test]
+    fn test_display_watermarks_none() {
+        let m = BTreeMap::from([("a", None), ("b", Some(1)), ("c", Some(4)), ("d", None)]);
+        assert_eq!(
+            "a:Ø, b:1, c:4, d:Ø (lag: a behind by ∞, d by ∞, b by 3)",
+            m.display_watermarks().to_string()
+        );
+
+        let m = BTreeMap::from([("a", None::<u64>)]);
+        assert_eq!("a:Ø", m.display_watermarks().to_string());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_display_watermarks_hash_map() {
+        let m = std::collections::HashMap::from([("n2", 480u64), ("n1", 482)]);
+        assert_eq!(
+            "n1:482, n2:480 (lag: n2 behind by 2)",
+            m.display_watermarks().to_string()
+        );
+    }
+}
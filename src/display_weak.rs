@@ -0,0 +1,100 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::sync::Weak;
+use core::fmt;
+
+/// Display the value a `Weak` points to, with the strong and weak counts, such as
+/// `5 (strong: 1, weak: 2)`, or `<dangling>` if the value has been dropped.
+///
+/// The counts are read before upgrading, so the upgrade for formatting is not counted.
+#[must_use]
+pub struct DisplayWeak<'a, T: ?Sized> {
+    inner: &'a Weak<T>,
+}
+
+impl<'a, T: ?Sized> DisplayWeak<'a, T> {
+    pub const fn new(inner: &'a Weak<T>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T: fmt::Display + ?Sized> fmt::Display for DisplayWeak<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let strong = self.inner.strong_count();
+        let weak = self.inner.weak_count();
+
+        match self.inner.upgrade() {
+            Some(v) => write!(f, "{} (strong: {}, weak: {})", v, strong, weak),
+            None => write!(f, "<dangling>"),
+        }
+    }
+}
+
+impl<T: fmt::Display + ?Sized> fmt::Debug for DisplayWeak<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a `Weak` by upgrading it when formatting, for debugging caches and registries.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::Arc;
+///
+/// use display_more::DisplayWeakExt;
+///
+/// let a = Arc::new(5);
+/// let w = Arc::downgrade(&a);
+/// assert_eq!(w.display().to_string(), "5 (strong: 1, weak: 1)");
+///
+/// drop(a);
+/// assert_eq!(w.display().to_string(), "<dangling>");
+/// ```
+pub trait DisplayWeakExt<T: ?Sized> {
+    fn display(&self) -> DisplayWeak<'_, T>;
+}
+
+impl<T: ?Sized> DisplayWeakExt<T> for Weak<T> {
+    fn display(&self) -> DisplayWeak<'_, T> {
+        DisplayWeak::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use alloc::sync::Arc;
+
+    use super::*;
+
+    #[test]
+    fn test_display_weak() {
+        let a: Arc<str> = Arc::from("a");
+        let b = a.clone();
+        let w1 = Arc::downgrade(&a);
+        let w2 = w1.clone();
+
+        assert_eq!("a (strong: 2, weak: 2)", w1.display().to_string());
+
+        drop(a);
+        assert_eq!("a (strong: 1, weak: 2)", w2.display().to_string());
+
+        drop(b);
+        assert_eq!("<dangling>", w1.display().to_string());
+        assert_eq!("<dangling>", Weak::<u8>::new().display().to_string());
+    }
+}
@@ -0,0 +1,267 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::string::String;
+use core::cmp::Ordering;
+use core::fmt;
+
+use crate::display_option::DisplayOption;
+use crate::display_slice::DisplaySlice;
+use crate::display_unix_epoch::DisplayUnixTimeStamp;
+use crate::measure;
+use crate::DisplayBTreeSet;
+use crate::DisplayBool;
+use crate::DisplayConfig;
+use crate::DisplayIntoIter;
+use crate::DisplayOrdering;
+use crate::SizeHint;
+use crate::Summary;
+
+/// The width of the output of a value in chars, as far as it is known without formatting it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Width {
+    /// The output is exactly this wide.
+    Exact(usize),
+    /// The output is at most this wide.
+    AtMost(usize),
+    /// The width is only known by formatting the value.
+    Unknown,
+}
+
+/// Report the width of the output in chars cheaply, so that layout code can skip measuring
+/// what is already known.
+///
+/// The width is the visible width: ANSI escape codes added by the `color` feature are not
+/// counted.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplaySliceExt;
+/// use display_more::DisplayWidth;
+/// use display_more::Width;
+///
+/// assert_eq!(12345.display_width(), Width::Exact(5));
+/// assert_eq!(
+///     [1, 2].display().fit_width(10).display_width(),
+///     Width::AtMost(10)
+/// );
+/// assert_eq!([1, 2].display().exact_width(), 5);
+/// ```
+pub trait DisplayWidth: fmt::Display {
+    fn display_width(&self) -> Width;
+
+    /// Return the exact width, measuring the output with [`measure`] if it is not known.
+    fn exact_width(&self) -> usize {
+        match self.display_width() {
+            Width::Exact(w) => w,
+            Width::AtMost(_) | Width::Unknown => measure(self).chars,
+        }
+    }
+}
+
+impl<T: DisplayWidth + ?Sized> DisplayWidth for &T {
+    fn display_width(&self) -> Width {
+        (**self).display_width()
+    }
+}
+
+impl DisplayWidth for str {
+    fn display_width(&self) -> Width {
+        Width::Exact(self.chars().count())
+    }
+}
+
+impl DisplayWidth for String {
+    fn display_width(&self) -> Width {
+        self.as_str().display_width()
+    }
+}
+
+impl DisplayWidth for Summary {
+    fn display_width(&self) -> Width {
+        self.as_str().display_width()
+    }
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl DisplayWidth for $t {
+                fn display_width(&self) -> Width {
+                    Width::Exact(self.checked_ilog10().unwrap_or(0) as usize + 1)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_signed {
+    ($($t:ty),*) => {
+        $(
+            impl DisplayWidth for $t {
+                fn display_width(&self) -> Width {
+                    let sign = usize::from(*self < 0);
+                    Width::Exact(sign + self.unsigned_abs().checked_ilog10().unwrap_or(0) as usize + 1)
+                }
+            }
+        )*
+    };
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_signed!(i8, i16, i32, i64, i128, isize);
+
+impl DisplayWidth for DisplayBool {
+    fn display_width(&self) -> Width {
+        let (t, f) = self.style.words();
+        let word = if self.value { t } else { f };
+        Width::Exact(word.chars().count())
+    }
+}
+
+impl DisplayWidth for DisplayOrdering {
+    fn display_width(&self) -> Width {
+        let w = match (self.ordering, self.words) {
+            (_, false) => 1,
+            (Ordering::Less, true) => "less".len(),
+            (Ordering::Equal, true) => "equal".len(),
+            (Ordering::Greater, true) => "greater".len(),
+        };
+        Width::Exact(w)
+    }
+}
+
+impl DisplayWidth for DisplayUnixTimeStamp {
+    fn display_width(&self) -> Width {
+        if self.datetime().is_none() {
+            return DisplayConfig::current().none.display_width();
+        }
+        // The time is ASCII, so its length in bytes is the width.
+        Width::Exact(self.size_hint())
+    }
+}
+
+impl<T> DisplayWidth for DisplayOption<'_, T> {
+    fn display_width(&self) -> Width {
+        match self.inner {
+            Some(_) => Width::Unknown,
+            None => DisplayConfig::current().none.display_width(),
+        }
+    }
+}
+
+impl<'a, T, S> DisplayWidth for DisplayIntoIter<'a, T, S>
+where
+    T: fmt::Display + 'a,
+    S: Clone + IntoIterator<Item = &'a T>,
+    S::IntoIter: DoubleEndedIterator + ExactSizeIterator,
+{
+    fn display_width(&self) -> Width {
+        match self.options.max_width {
+            Some(w) => Width::AtMost(w),
+            None => Width::Unknown,
+        }
+    }
+}
+
+impl<T: fmt::Display> DisplayWidth for DisplaySlice<'_, T> {
+    fn display_width(&self) -> Width {
+        self.inner.display_width()
+    }
+}
+
+impl<T: fmt::Display> DisplayWidth for DisplayBTreeSet<'_, T> {
+    fn display_width(&self) -> Width {
+        self.inner.display_width()
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl DisplayWidth for crate::DisplayUuidShort {
+    fn display_width(&self) -> Width {
+        const HEX_LEN: usize = 32;
+        if self.prefix_len >= HEX_LEN {
+            Width::Exact(HEX_LEN)
+        } else {
+            Width::Exact(self.prefix_len + "..".len())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+    use core::time::Duration;
+
+    use super::*;
+    use crate::BoolStyle;
+    use crate::DisplayBoolExt;
+    use crate::DisplayOptionExt;
+    use crate::DisplayOrderingExt;
+    use crate::DisplaySliceExt;
+    use crate::DisplayUnixTimeStampExt;
+
+    /// Assert that an exact width is the number of chars of the output.
+    fn check(d: impl DisplayWidth) {
+        if let Width::Exact(w) = d.display_width() {
+            assert_eq!(d.to_string().chars().count(), w, "{}", d);
+        }
+        assert_eq!(d.to_string().chars().count(), d.exact_width(), "{}", d);
+    }
+
+    #[test]
+    fn test_display_width_ints() {
+        for v in [0u64, 9, 10, 99, 100, u64::MAX] {
+            check(v);
+        }
+        for v in [0i32, -1, -9, -10, 10, i32::MIN, i32::MAX] {
+            check(v);
+        }
+        check(u128::MAX);
+        check(i8::MIN);
+    }
+
+    #[test]
+    fn test_display_width_wrappers() {
+        check("中文");
+        check(true.display_bool(BoolStyle::EnabledDisabled));
+        check(Ordering::Greater.display().words());
+        check(Ordering::Less.display());
+        check(Duration::from_secs(1).display_unix_timestamp());
+        check(None::<Duration>.display_unix_timestamp());
+        check(Duration::from_secs(253_402_300_800).display_unix_timestamp());
+        check(None::<u8>.display());
+        check(Some(5).display());
+
+        let a = (1..100).collect::<Vec<_>>();
+        check(a.display());
+        assert_eq!(Width::Unknown, a.display().display_width());
+
+        let d = a.display().fit_width(6);
+        assert_eq!(Width::AtMost(6), d.display_width());
+        assert!(d.to_string().chars().count() <= 6);
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_display_width_uuid() {
+        use crate::DisplayUuidExt;
+
+        let u = uuid::Uuid::nil();
+        check(u.display_short());
+        check(u.display_short().prefix_len(40));
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::boxed::Box;
+use core::fmt;
+
+/// An object-safe trait for values that can be displayed.
+///
+/// It is implemented for every `T: Display`, and `dyn Displayable` implements `Display`, so that a
+/// `Vec<Box<dyn Displayable>>` of heterogeneous values can be displayed with
+/// [`DisplaySliceExt`](crate::DisplaySliceExt).
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::displayables;
+/// use display_more::DisplayOptionExt;
+/// use display_more::DisplaySliceExt;
+///
+/// let opt = Some(3);
+/// let items = displayables![1, "foo", opt.display()];
+/// assert_eq!(items.display().to_string(), "[1,foo,3]");
+/// ```
+pub trait Displayable {
+    fn fmt_display(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    /// Box this value as a `dyn Displayable`.
+    fn into_boxed<'a>(self) -> Box<dyn Displayable + 'a>
+    where Self: Sized + 'a {
+        Box::new(self)
+    }
+}
+
+impl<T> Displayable for T
+where T: fmt::Display + ?Sized
+{
+    fn fmt_display(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for dyn Displayable + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_display(f)
+    }
+}
+
+impl fmt::Debug for dyn Displayable + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_display(f)
+    }
+}
+
+/// Build a `Vec<Box<dyn Displayable>>` from a list of `Display` values.
+#[macro_export]
+macro_rules! displayables {
+    ($($x:expr),* $(,)?) => {
+        $crate::__private::vec![$($crate::Displayable::into_boxed($x)),*]
+            as $crate::__private::Vec<$crate::__private::Box<dyn $crate::Displayable>>
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DisplaySliceExt;
+    use crate::DisplayUnixTimeStampExt;
+
+    #[test]
+    fn test_displayable() {
+        let items: Vec<Box<dyn Displayable>> = vec![
+            1.into_boxed(),
+            "a".into_boxed(),
+            std::time::Duration::from_secs(0)
+                .display_unix_timestamp_short()
+                .into_boxed(),
+        ];
+        assert_eq!(
+            "[1, a, 1970-01-01T00:00:00.000]",
+            items.display().sep(", ").to_string()
+        );
+    }
+
+    #[test]
+    fn test_displayables_macro() {
+        let empty = displayables![];
+        assert_eq!("[]", empty.display().to_string());
+
+        let items = displayables![1, 2.5, "x", 'c', 5, 6,];
+        assert_eq!("[1,2.5,x,c,..,6]", items.display().to_string());
+        assert_eq!("1", format!("{:?}", items[0]));
+    }
+}
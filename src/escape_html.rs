@@ -0,0 +1,120 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+use core::fmt::Write;
+
+/// A `fmt::Write` that escapes `<>&"'` as HTML entities.
+struct HtmlWriter<'a, 'f>(&'a mut fmt::Formatter<'f>);
+
+impl Write for HtmlWriter<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut start = 0;
+        for (i, b) in s.bytes().enumerate() {
+            let entity = match b {
+                b'<' => "&lt;",
+                b'>' => "&gt;",
+                b'&' => "&amp;",
+                b'"' => "&quot;",
+                b'\'' => "&#39;",
+                _ => continue,
+            };
+            self.0.write_str(&s[start..i])?;
+            self.0.write_str(entity)?;
+            start = i + 1;
+        }
+        self.0.write_str(&s[start..])
+    }
+}
+
+/// Display the inner value with `<>&"'` escaped as HTML entities.
+///
+/// The output of the inner value is escaped as it is written, without buffering.
+#[must_use]
+pub struct EscapeHtml<D: fmt::Display> {
+    inner: D,
+}
+
+impl<D: fmt::Display> EscapeHtml<D> {
+    pub const fn new(inner: D) -> Self {
+        Self { inner }
+    }
+}
+
+impl<D: fmt::Display> fmt::Display for EscapeHtml<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(HtmlWriter(f), "{}", self.inner)
+    }
+}
+
+impl<D: fmt::Display> fmt::Debug for EscapeHtml<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Escape the output of any `Display` for embedding in HTML.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplaySliceExt;
+/// use display_more::EscapeHtmlExt;
+///
+/// let tags = ["<b>", "a&b"];
+/// assert_eq!(
+///     tags.display().escape_html().to_string(),
+///     "[&lt;b&gt;,a&amp;b]"
+/// );
+/// ```
+pub trait EscapeHtmlExt: fmt::Display {
+    /// Escape `<>&"'` in the output as HTML entities.
+    fn escape_html(&self) -> EscapeHtml<&Self> {
+        EscapeHtml::new(self)
+    }
+}
+
+impl<T> EscapeHtmlExt for T where T: fmt::Display + ?Sized {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!("", "".escape_html().to_string());
+        assert_eq!("abc", "abc".escape_html().to_string());
+        assert_eq!(
+            "&lt;a href=&quot;x&quot;&gt;it&#39;s &amp; é&lt;/a&gt;",
+            r#"<a href="x">it's & é</a>"#.escape_html().to_string()
+        );
+    }
+
+    #[test]
+    fn test_escape_html_split_writes() {
+        struct Parts;
+
+        impl fmt::Display for Parts {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("<")?;
+                f.write_str("a>")?;
+                write!(f, "{}", 1)
+            }
+        }
+
+        assert_eq!("&lt;a&gt;1", Parts.escape_html().to_string());
+    }
+}
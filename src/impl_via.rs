@@ -0,0 +1,135 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Macros that implement one of `Display` and `Debug` by delegating to the other.
+
+/// Implement `Display` for types by delegating to their `Debug` implementation.
+///
+/// Pass a list of types, or `[generics] Type where [bounds]` items separated by `;` for generic
+/// types.
+///
+/// # Example
+///
+/// ```rust
+/// use std::fmt::Debug;
+///
+/// use display_more::impl_display_via_debug;
+///
+/// #[derive(Debug)]
+/// struct Point {
+///     x: i32,
+/// }
+///
+/// #[derive(Debug)]
+/// struct Wrapper<T>(T);
+///
+/// impl_display_via_debug!(Point);
+/// impl_display_via_debug!([T: Debug] Wrapper<T>);
+///
+/// assert_eq!(Point { x: 1 }.to_string(), "Point { x: 1 }");
+/// assert_eq!(Wrapper("a").to_string(), r#"Wrapper("a")"#);
+/// ```
+#[macro_export]
+macro_rules! impl_display_via_debug {
+    ($([$($g:tt)*] $ty:ty $(where [$($w:tt)*])?);+ $(;)?) => {
+        $(
+            impl<$($g)*> ::core::fmt::Display for $ty $(where $($w)*)? {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::core::fmt::Debug::fmt(self, f)
+                }
+            }
+        )+
+    };
+    ($($ty:ty),+ $(,)?) => {
+        $crate::impl_display_via_debug!($([] $ty);+);
+    };
+}
+
+/// Implement `Debug` for types by delegating to their `Display` implementation.
+///
+/// Pass a list of types, or `[generics] Type where [bounds]` items separated by `;` for generic
+/// types.
+///
+/// # Example
+///
+/// ```rust
+/// use std::fmt;
+///
+/// use display_more::impl_debug_via_display;
+///
+/// struct NodeId(u64);
+///
+/// impl fmt::Display for NodeId {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "n{}", self.0)
+///     }
+/// }
+///
+/// impl_debug_via_display!(NodeId);
+///
+/// assert_eq!(format!("{:?}", NodeId(3)), "n3");
+/// ```
+#[macro_export]
+macro_rules! impl_debug_via_display {
+    ($([$($g:tt)*] $ty:ty $(where [$($w:tt)*])?);+ $(;)?) => {
+        $(
+            impl<$($g)*> ::core::fmt::Debug for $ty $(where $($w)*)? {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::core::fmt::Display::fmt(self, f)
+                }
+            }
+        )+
+    };
+    ($($ty:ty),+ $(,)?) => {
+        $crate::impl_debug_via_display!($([] $ty);+);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+    use alloc::string::ToString;
+    use core::fmt;
+
+    #[derive(Debug)]
+    struct A;
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct B(u8);
+
+    impl_display_via_debug!(A, B);
+
+    struct Label<T>(T);
+
+    impl<T: fmt::Display> fmt::Display for Label<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "<{}>", self.0)
+        }
+    }
+
+    impl_debug_via_display!([T] Label<T> where [T: fmt::Display]);
+
+    #[test]
+    fn test_impl_display_via_debug() {
+        assert_eq!("A", A.to_string());
+        assert_eq!("B(1)", B(1).to_string());
+    }
+
+    #[test]
+    fn test_impl_debug_via_display() {
+        assert_eq!("<x>", format!("{:?}", Label("x")));
+        assert_eq!("[<1>, <2>]", format!("{:?}", [Label(1), Label(2)]));
+    }
+}
@@ -67,19 +67,332 @@
 //! );
 //! ```
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[macro_use]
+mod macros;
+
+#[cfg(feature = "color")]
+pub mod color;
+#[cfg(feature = "compact_str")]
+mod compact_string;
+mod debug_as_display;
+#[cfg(feature = "defmt")]
+mod defmt_format;
+pub mod diff;
+mod display_as_json;
+mod display_as_yaml;
+mod display_atomic;
+#[cfg(feature = "std")]
+mod display_backtrace;
+mod display_banner;
+mod display_bitset;
+mod display_bool;
+mod display_boxed;
 mod display_btreeset;
+#[cfg(feature = "std")]
+mod display_byte_size;
+#[cfg(feature = "std")]
+mod display_cached;
+mod display_cell;
+mod display_config;
+mod display_count;
+mod display_delta;
+mod display_duration;
+mod display_env;
+#[cfg(feature = "std")]
+mod display_exit_status;
+mod display_flags;
+mod display_float;
+mod display_grouped;
+mod display_headers;
 mod display_into_iter;
 mod display_iterator_options;
+#[cfg(feature = "json")]
+mod display_json;
+mod display_lines;
+#[cfg(feature = "std")]
+mod display_lock;
+mod display_once;
 pub mod display_option;
+mod display_ordering;
+mod display_pair;
+#[cfg(feature = "std")]
+mod display_path;
+#[cfg(feature = "std")]
+mod display_paths;
+#[cfg(feature = "std")]
+mod display_percent;
+mod display_progress;
+mod display_ptr;
+mod display_radix;
+mod display_range;
+#[cfg(feature = "std")]
+mod display_ratio;
 mod display_result;
+mod display_scaled;
+#[cfg(feature = "std")]
+mod display_si;
 pub mod display_slice;
+mod display_socket_addrs;
+mod display_sql;
+#[cfg(feature = "std")]
+mod display_thread;
+mod display_tree;
+mod display_tuple;
+mod display_type_name;
 pub mod display_unix_epoch;
+#[cfg(feature = "uuid")]
+mod display_uuid;
+mod display_visible;
+mod display_watermarks;
+mod display_weak;
+mod display_width;
+mod displayable;
+mod escape_html;
+mod impl_via;
+mod limited_writer;
+#[cfg(feature = "kv")]
+mod log_kv;
+mod log_line;
+mod measure;
+mod partial_eq;
+pub mod prelude;
+#[cfg(feature = "std")]
+mod scoped_timer;
+#[cfg(feature = "serde")]
+mod serialize;
+mod size_hint;
+#[cfg(feature = "slog")]
+mod slog_value;
+#[cfg(feature = "smallstr")]
+mod small_string;
+mod smart_pointer;
+mod summarize;
+pub mod table;
+#[cfg(feature = "std")]
+mod terminal;
+mod testing;
+#[cfg(feature = "std")]
+mod to_log_string;
+#[cfg(feature = "tracing")]
+mod tracing_field;
+mod unstyled;
+mod write_to;
 
+#[cfg(feature = "compact_str")]
+pub use compact_string::ToCompactStringExt;
+pub use debug_as_display::DebugAsDisplay;
+pub use debug_as_display::DebugAsDisplayExt;
+pub use debug_as_display::DebugOneLine;
+pub use diff::diff_debug;
+pub use display_as_json::AsJson;
+pub use display_as_json::AsJsonExt;
+pub use display_as_yaml::AsYaml;
+pub use display_as_yaml::AsYamlExt;
+pub use display_atomic::DisplayAtomic;
+pub use display_atomic::DisplayAtomicExt;
+#[cfg(feature = "std")]
+pub use display_backtrace::DisplayBacktraceExt;
+#[cfg(feature = "std")]
+pub use display_backtrace::DisplayBacktraceSummary;
+pub use display_banner::banner;
+pub use display_banner::Banner;
+pub use display_bitset::DisplayBitset;
+pub use display_bitset::DisplayBitsetExt;
+pub use display_bool::BoolStyle;
+pub use display_bool::DisplayBool;
+pub use display_bool::DisplayBoolExt;
+pub use display_boxed::BoxedDisplay;
+pub use display_boxed::ToBoxedDisplay;
 pub use display_btreeset::DisplayBTreeSet;
 pub use display_btreeset::DisplayBTreeSetExt;
+pub use display_btreeset::DisplayBTreeSetOwned;
+#[cfg(feature = "std")]
+pub use display_byte_size::DisplayByteSize;
+#[cfg(feature = "std")]
+pub use display_byte_size::DisplayByteSizeExt;
+#[cfg(feature = "std")]
+pub use display_cached::Cached;
+pub use display_cell::DisplayCell;
+pub use display_cell::DisplayCellExt;
+pub use display_cell::DisplayOnceCell;
+pub use display_cell::DisplayOnceCellExt;
+pub use display_cell::DisplayRefCell;
+pub use display_cell::DisplayRefCellExt;
+#[cfg(feature = "std")]
+pub use display_config::ConfigGuard;
+pub use display_config::DisplayConfig;
+pub use display_count::DisplayCount;
+pub use display_count::DisplayCountExt;
+pub use display_delta::DisplayDelta;
+pub use display_delta::DisplayDeltaExt;
+pub use display_duration::DisplayDurationExt;
+pub use display_duration::DisplayDurationStats;
+pub use display_duration::DisplayDurationStatsExt;
+pub use display_duration::DisplayIso8601Duration;
+pub use display_duration::DisplaySecs;
+pub use display_duration::DisplaySecsExt;
+pub use display_env::display_env;
+pub use display_env::DisplayEnv;
+pub use display_env::DEFAULT_SECRET_PATTERNS;
+#[cfg(feature = "std")]
+pub use display_exit_status::DisplayExitStatus;
+#[cfg(feature = "std")]
+pub use display_exit_status::DisplayExitStatusExt;
+pub use display_flags::display_flags;
+#[cfg(feature = "bitflags")]
+pub use display_flags::DisplayBitflags;
+#[cfg(feature = "bitflags")]
+pub use display_flags::DisplayBitflagsExt;
+pub use display_flags::DisplayFlags;
+pub use display_float::DisplayFixed;
+pub use display_float::DisplayFloatExt;
+pub use display_float::DisplayFloatSliceExt;
+pub use display_float::DisplaySci;
+pub use display_float::DisplayShortest;
+pub use display_float::DisplayShortestSlice;
+pub use display_float::DisplaySigFigs;
+pub use display_float::NonFinite;
+pub use display_grouped::DisplayGrouped;
+pub use display_grouped::DisplayGroupedExt;
+pub use display_headers::display_headers;
+pub use display_headers::DisplayHeaders;
+pub use display_headers::DEFAULT_SENSITIVE_HEADERS;
 pub use display_into_iter::DisplayIntoIter;
+#[cfg(feature = "json")]
+pub use display_json::DisplayJsonSummary;
+#[cfg(feature = "json")]
+pub use display_json::DisplayJsonSummaryExt;
+pub use display_lines::DisplayLineNumbers;
+pub use display_lines::DisplayLinePrefix;
+pub use display_lines::DisplayLinesExt;
+pub use display_lines::DisplayWrap;
+#[cfg(feature = "std")]
+pub use display_lock::DisplayMutex;
+#[cfg(feature = "std")]
+pub use display_lock::DisplayMutexExt;
+#[cfg(feature = "std")]
+pub use display_lock::DisplayRwLock;
+#[cfg(feature = "std")]
+pub use display_lock::DisplayRwLockExt;
+#[cfg(feature = "derive")]
+pub use display_more_derive::DisplayForward;
+#[cfg(feature = "derive")]
+pub use display_more_derive::DisplayMore;
+pub use display_once::display_once;
+pub use display_once::DisplayOnce;
 pub use display_option::DisplayDebugOptionExt;
 pub use display_option::DisplayOptionExt;
+pub use display_option::DisplayOptionOwned;
+pub use display_ordering::DisplayOrdering;
+pub use display_ordering::DisplayOrderingExt;
+pub use display_pair::DisplayPair;
+#[cfg(feature = "std")]
+pub use display_path::DisplayPath;
+#[cfg(feature = "std")]
+pub use display_path::DisplayPathExt;
+#[cfg(feature = "std")]
+pub use display_paths::DisplayPaths;
+#[cfg(feature = "std")]
+pub use display_paths::DisplayPathsExt;
+#[cfg(feature = "std")]
+pub use display_percent::DisplayPercent;
+#[cfg(feature = "std")]
+pub use display_percent::DisplayPercentExt;
+pub use display_progress::DisplayProgress;
+pub use display_ptr::DisplayPtr;
+pub use display_ptr::DisplayPtrExt;
+pub use display_radix::DisplayRadix;
+pub use display_radix::DisplayRadixExt;
+pub use display_radix::Radix;
+pub use display_range::DisplayRange;
+pub use display_range::DisplayRangeExt;
+#[cfg(feature = "std")]
+pub use display_ratio::DisplayRatio;
 pub use display_result::DisplayResultExt;
+pub use display_result::DisplayResultOwned;
+pub use display_scaled::display_scaled;
+pub use display_scaled::DisplayScaled;
+#[cfg(feature = "std")]
+pub use display_si::DisplaySi;
+#[cfg(feature = "std")]
+pub use display_si::DisplaySiExt;
 pub use display_slice::DisplaySliceExt;
+pub use display_slice::DisplaySliceOwned;
+pub use display_socket_addrs::DisplaySocketAddrs;
+pub use display_socket_addrs::DisplaySocketAddrsExt;
+pub use display_sql::DisplaySqlIdent;
+pub use display_sql::DisplaySqlIdentExt;
+pub use display_sql::DisplaySqlLiteral;
+pub use display_sql::DisplaySqlLiteralExt;
+pub use display_sql::SqlDialect;
+#[cfg(feature = "std")]
+pub use display_thread::display_current_thread;
+#[cfg(feature = "std")]
+pub use display_thread::DisplayThread;
+#[cfg(feature = "std")]
+pub use display_thread::DisplayThreadExt;
+pub use display_tree::DisplayTree;
+pub use display_tuple::DisplayTuple;
+pub use display_tuple::DisplayTupleExt;
+pub use display_type_name::display_type_name;
+pub use display_type_name::DisplayTypeName;
+pub use display_type_name::DisplayTypeOfExt;
 pub use display_unix_epoch::DisplayUnixTimeStampExt;
+#[cfg(feature = "uuid")]
+pub use display_uuid::DisplayUuidExt;
+#[cfg(feature = "uuid")]
+pub use display_uuid::DisplayUuidShort;
+pub use display_visible::DisplayVisible;
+pub use display_visible::DisplayVisibleExt;
+pub use display_watermarks::DisplayWatermarks;
+pub use display_watermarks::DisplayWatermarksExt;
+pub use display_weak::DisplayWeak;
+pub use display_weak::DisplayWeakExt;
+pub use display_width::DisplayWidth;
+pub use display_width::Width;
+pub use displayable::Displayable;
+pub use escape_html::EscapeHtml;
+pub use escape_html::EscapeHtmlExt;
+pub use limited_writer::DisplayLimited;
+pub use limited_writer::LimitBytesExt;
+pub use limited_writer::LimitedWriter;
+pub use log_line::DisplayLogJson;
+pub use log_line::DisplayLogfmt;
+pub use log_line::LogLine;
+pub use measure::measure;
+pub use measure::Measure;
+#[cfg(feature = "std")]
+pub use scoped_timer::DisplayElapsed;
+#[cfg(feature = "std")]
+pub use scoped_timer::ScopedTimer;
+pub use size_hint::SizeHint;
+#[cfg(feature = "smallstr")]
+pub use small_string::ToSmallStringExt;
+pub use summarize::Summarize;
+pub use summarize::Summary;
+#[cfg(feature = "std")]
+pub use terminal::terminal_width;
+#[cfg(feature = "std")]
+pub use terminal::DEFAULT_TERMINAL_WIDTH;
+#[cfg(feature = "std")]
+pub use to_log_string::ToLogStringExt;
+#[cfg(feature = "tracing")]
+pub use tracing_field::TracingFieldExt;
+pub use unstyled::Unstyled;
+pub use write_to::WriteToExt;
+
+/// Items used by the exported macros, so that they work in `no_std` crates.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::boxed::Box;
+    pub use alloc::vec;
+    pub use alloc::vec::Vec;
+
+    pub use crate::testing::assert_display_eq;
+    #[cfg(feature = "snapshot")]
+    pub use crate::testing::assert_display_snapshot;
+}
@@ -0,0 +1,179 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+use core::fmt::Write;
+
+use crate::Summary;
+
+/// A `fmt::Write` adapter that passes at most `limit` bytes to the inner writer.
+///
+/// When the limit is reached, the output is cut at a char boundary, [`is_truncated`] becomes
+/// true and the write returns `fmt::Error`, so that the `Display` being written stops early
+/// instead of producing output that is thrown away.
+///
+/// [`is_truncated`]: Self::is_truncated
+pub struct LimitedWriter<W> {
+    inner: W,
+    remaining: usize,
+    truncated: bool,
+}
+
+impl<W: Write> LimitedWriter<W> {
+    pub const fn new(inner: W, limit: usize) -> Self {
+        Self {
+            inner,
+            remaining: limit,
+            truncated: false,
+        }
+    }
+
+    /// Whether some output was dropped because of the limit.
+    pub const fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for LimitedWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.truncated {
+            return Err(fmt::Error);
+        }
+
+        if s.len() <= self.remaining {
+            self.remaining -= s.len();
+            return self.inner.write_str(s);
+        }
+
+        let mut end = self.remaining;
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.inner.write_str(&s[..end])?;
+        self.remaining = 0;
+        self.truncated = true;
+        Err(fmt::Error)
+    }
+}
+
+/// Display the inner value cut to at most `limit` bytes, followed by [`Summary::MARKER`] if
+/// cut.
+///
+/// The inner value stops being formatted once the limit is reached.
+#[must_use]
+pub struct DisplayLimited<D> {
+    inner: D,
+    limit: usize,
+}
+
+impl<D: fmt::Display> DisplayLimited<D> {
+    pub const fn new(inner: D, limit: usize) -> Self {
+        Self { inner, limit }
+    }
+}
+
+impl<D: fmt::Display> fmt::Display for DisplayLimited<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut w = LimitedWriter::new(&mut *f, self.limit);
+        let res = write!(w, "{}", self.inner);
+
+        if w.is_truncated() {
+            return f.write_char(Summary::MARKER);
+        }
+        res
+    }
+}
+
+impl<D: fmt::Display> fmt::Debug for DisplayLimited<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Cap the output of any `Display` to a number of bytes, so that a nested third-party value
+/// cannot produce unbounded log output.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::LimitBytesExt;
+///
+/// let huge = "x".repeat(1 << 20);
+/// assert_eq!(huge.as_str().limit_bytes(4).to_string(), "xxxx…");
+/// assert_eq!("abc".limit_bytes(4).to_string(), "abc");
+/// ```
+pub trait LimitBytesExt: fmt::Display + Sized {
+    fn limit_bytes(self, limit: usize) -> DisplayLimited<Self> {
+        DisplayLimited::new(self, limit)
+    }
+}
+
+impl<T: fmt::Display> LimitBytesExt for T {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use alloc::string::ToString;
+    use core::cell::Cell;
+
+    use super::*;
+    use crate::DisplaySliceExt;
+
+    #[test]
+    fn test_limited_writer() {
+        let mut w = LimitedWriter::new(String::new(), 5);
+        w.write_str("abc").unwrap();
+        assert!(!w.is_truncated());
+        assert!(w.write_str("中d").is_err());
+        assert!(w.is_truncated());
+        assert!(w.write_str("e").is_err());
+        assert_eq!("abc", w.into_inner());
+
+        let mut w = LimitedWriter::new(String::new(), 3);
+        w.write_str("abc").unwrap();
+        assert!(!w.is_truncated());
+        assert!(w.write_str("d").is_err());
+        assert_eq!("abc", w.into_inner());
+    }
+
+    #[test]
+    fn test_display_limited() {
+        assert_eq!("[1,2,…", [1, 2, 3].display().limit_bytes(5).to_string());
+        assert_eq!("[1,2,3]", [1, 2, 3].display().limit_bytes(7).to_string());
+        assert_eq!("…", "a".limit_bytes(0).to_string());
+        assert_eq!("", "".limit_bytes(0).to_string());
+    }
+
+    #[test]
+    fn test_display_limited_stops_early() {
+        struct Endless<'a>(&'a Cell<usize>);
+
+        impl fmt::Display for Endless<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                loop {
+                    self.0.set(self.0.get() + 1);
+                    f.write_str("ab")?;
+                }
+            }
+        }
+
+        let writes = Cell::new(0);
+        assert_eq!("abab…", Endless(&writes).limit_bytes(4).to_string());
+        assert_eq!(3, writes.get());
+    }
+}
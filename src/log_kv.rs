@@ -0,0 +1,67 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `log::kv::ToValue` implementations for the wrappers, enabled by the `kv` feature.
+//!
+//! A wrapper is captured as a `Display` value, formatted only when a backend reads it.
+//!
+//! # Example
+//!
+//! ```rust
+//! use display_more::DisplaySliceExt;
+//!
+//! let nodes = vec![1, 2, 3];
+//! log::info!(peers = nodes.display(); "membership changed");
+//! ```
+
+use log::kv::ToValue;
+use log::kv::Value;
+
+use crate::Unstyled;
+
+/// Implement `ToValue` as a `Display` value, for `[generics] Type where [bounds]`.
+macro_rules! impl_to_value {
+    ($([$($g:tt)*] $ty:ty $(where [$($w:tt)*])?;)*) => {
+        $(
+            impl<$($g)*> ToValue for $ty $(where $($w)*)? {
+                fn to_value(&self) -> Value<'_> {
+                    Value::from_display(Unstyled::from_ref(self))
+                }
+            }
+        )*
+    };
+}
+
+for_each_wrapper!(impl_to_value);
+
+#[cfg(test)]
+mod tests {
+    use log::kv::ToValue;
+
+    use crate::DisplayOptionExt;
+    use crate::DisplayResultExt;
+    use crate::DisplaySliceExt;
+
+    #[test]
+    fn test_to_value() {
+        let a = [1, 2, 3, 4, 5, 6, 7];
+        assert_eq!("[1,2,3,4,..,7]", a.display().to_value().to_string());
+        assert_eq!("None", None::<u64>.display().to_value().to_string());
+        assert_eq!(
+            "Err(1)",
+            Err::<u64, u64>(1).display().to_value().to_string()
+        );
+        assert_eq!("==== a ====", crate::banner("a").to_value().to_string());
+    }
+}
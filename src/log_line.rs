@@ -0,0 +1,253 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Write;
+
+use crate::display_as_json::is_json_escaped;
+use crate::display_as_json::write_json_escaped;
+use crate::display_as_json::write_json_string;
+use crate::Unstyled;
+
+/// Accumulate named fields and render them as a single logfmt or JSON line.
+///
+/// A field value is any `Display`, including the wrappers of this crate. Values are formatted
+/// only when the line is rendered. `Display` of a `LogLine` outputs logfmt.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayOptionExt;
+/// use display_more::DisplaySliceExt;
+/// use display_more::LogLine;
+///
+/// let voters = vec![1, 2, 3];
+/// let leader: Option<u64> = None;
+///
+/// let line = LogLine::new()
+///     .field("event", "election")
+///     .field("voters", voters.display())
+///     .field("leader", leader.display());
+///
+/// assert_eq!(
+///     line.logfmt().to_string(),
+///     "event=election voters=[1,2,3] leader=None"
+/// );
+/// assert_eq!(
+///     line.json().to_string(),
+///     r#"{"event":"election","voters":"[1,2,3]","leader":"None"}"#
+/// );
+/// ```
+#[derive(Default)]
+#[must_use]
+pub struct LogLine<'a> {
+    fields: Vec<(&'a str, Box<dyn fmt::Display + 'a>)>,
+}
+
+impl<'a> LogLine<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a field.
+    pub fn field(mut self, key: &'a str, value: impl fmt::Display + 'a) -> Self {
+        self.push(key, value);
+        self
+    }
+
+    /// Append a field in place, e.g., in a loop.
+    pub fn push(&mut self, key: &'a str, value: impl fmt::Display + 'a) {
+        self.fields.push((key, Box::new(value)));
+    }
+
+    /// Display as logfmt: `key=value` pairs separated by spaces. A key or value is quoted and
+    /// escaped if it is empty or contains a space, `=`, `"`, `\` or a control char; otherwise it
+    /// is output as is.
+    ///
+    /// To decide on quoting without buffering, each value is formatted twice, so a value that
+    /// can be displayed only once, such as [`DisplayOnce`](crate::DisplayOnce), is not suitable.
+    pub const fn logfmt(&self) -> DisplayLogfmt<'_, 'a> {
+        DisplayLogfmt { line: self }
+    }
+
+    /// Display as a JSON object with string values.
+    pub const fn json(&self) -> DisplayLogJson<'_, 'a> {
+        DisplayLogJson { line: self }
+    }
+}
+
+impl fmt::Display for LogLine<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.logfmt().fmt(f)
+    }
+}
+
+impl fmt::Debug for LogLine<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a [`LogLine`] as logfmt.
+#[must_use]
+pub struct DisplayLogfmt<'l, 'a> {
+    line: &'l LogLine<'a>,
+}
+
+impl fmt::Display for DisplayLogfmt<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.line.fields.iter().enumerate() {
+            if i > 0 {
+                f.write_char(' ')?;
+            }
+            write_logfmt(f, key)?;
+            f.write_char('=')?;
+            write_logfmt(f, Unstyled::new(value))?;
+        }
+        Ok(())
+    }
+}
+
+/// Write a logfmt key or value, quoted and escaped if it is empty or contains a space, `=` or a
+/// char that JSON escapes.
+///
+/// `d` is formatted twice: first to find out if it needs quotes, then into `f`; nothing is
+/// buffered.
+fn write_logfmt(f: &mut fmt::Formatter<'_>, d: impl fmt::Display) -> fmt::Result {
+    let mut check = QuoteCheck {
+        empty: true,
+        quote: false,
+    };
+    // `QuoteCheck` returns an error to stop formatting once the answer is known.
+    let _ = write!(check, "{}", d);
+
+    if check.empty || check.quote {
+        write_json_string(f, d)
+    } else {
+        write!(f, "{}", d)
+    }
+}
+
+/// A `fmt::Write` that discards the output and records whether it needs quotes in logfmt.
+struct QuoteCheck {
+    empty: bool,
+    quote: bool,
+}
+
+impl Write for QuoteCheck {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.empty &= s.is_empty();
+        if s.contains(|c| c == ' ' || c == '=' || is_json_escaped(c)) {
+            self.quote = true;
+            return Err(fmt::Error);
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for DisplayLogfmt<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display a [`LogLine`] as a JSON object.
+#[must_use]
+pub struct DisplayLogJson<'l, 'a> {
+    line: &'l LogLine<'a>,
+}
+
+impl fmt::Display for DisplayLogJson<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('{')?;
+        for (i, (key, value)) in self.line.fields.iter().enumerate() {
+            if i > 0 {
+                f.write_char(',')?;
+            }
+            f.write_char('"')?;
+            write_json_escaped(f, key)?;
+            f.write_str("\":")?;
+            write_json_string(f, Unstyled::new(value))?;
+        }
+        f.write_char('}')
+    }
+}
+
+impl fmt::Debug for DisplayLogJson<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_line_logfmt() {
+        let line = LogLine::new()
+            .field("a", 1)
+            .field("b", "x y")
+            .field("c", "")
+            .field("d", "say \"hi\"\n");
+        assert_eq!(
+            r#"a=1 b="x y" c="" d="say \"hi\"\n""#,
+            line.logfmt().to_string()
+        );
+        assert_eq!(line.logfmt().to_string(), line.to_string());
+
+        assert_eq!("", LogLine::new().to_string());
+    }
+
+    #[test]
+    fn test_log_line_logfmt_escaped_values_are_quoted() {
+        let line = LogLine::new()
+            .field("path", r"C:\tmp")
+            .field("msg", "a\nb")
+            .field("bell", "\u{7}")
+            .field("ok", "a-b:c");
+        assert_eq!(
+            r#"path="C:\\tmp" msg="a\nb" bell="\u0007" ok=a-b:c"#,
+            line.logfmt().to_string()
+        );
+    }
+
+    #[test]
+    fn test_log_line_logfmt_escaped_keys_are_quoted() {
+        let line = LogLine::new()
+            .field("a b", 1)
+            .field("k=v", 2)
+            .field("say \"hi\"", 3)
+            .field("", 4);
+        assert_eq!(
+            r#""a b"=1 "k=v"=2 "say \"hi\""=3 ""=4"#,
+            line.logfmt().to_string()
+        );
+    }
+
+    #[test]
+    fn test_log_line_json() {
+        let mut line = LogLine::new();
+        for (k, v) in [("k1", "v1"), ("k\"2", "a\\b\u{1}")] {
+            line.push(k, v);
+        }
+        assert_eq!(
+            r#"{"k1":"v1","k\"2":"a\\b\u0001"}"#,
+            line.json().to_string()
+        );
+        assert_eq!("{}", LogLine::new().json().to_string());
+    }
+}
@@ -0,0 +1,223 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Crate-internal macros.
+
+/// `write!()` in the given [`color::Style`](crate::color::Style) if the `color` feature is
+/// enabled.
+macro_rules! write_styled {
+    ($f:expr, $style:ident, $($arg:tt)*) => {{
+        #[cfg(feature = "color")]
+        {
+            write!($f, "{}", $crate::color::Style::$style.paint(format_args!($($arg)*)))
+        }
+        #[cfg(not(feature = "color"))]
+        {
+            write!($f, $($arg)*)
+        }
+    }};
+}
+
+/// Define the builder methods of a wrapper that displays a sequence, by updating its
+/// `DisplayIteratorOptions` at `self.$field`.
+///
+/// The borrowed and the owned wrappers share the builders this way; `$lt` is the lifetime of the
+/// strings, `'static` for the owned ones.
+macro_rules! iterator_options_builders {
+    ($lt:lifetime, $($field:ident).+) => {
+        /// Display at most `limit` elements; `None` uses [`DisplayConfig`](crate::DisplayConfig).
+        pub const fn at_most(mut self, limit: Option<usize>) -> Self {
+            self.$($field).+ = self.$($field).+.at_most(limit);
+            self
+        }
+
+        pub const fn sep(mut self, separator: &$lt str) -> Self {
+            self.$($field).+ = self.$($field).+.sep(separator);
+            self
+        }
+
+        pub const fn braces(mut self, left: &$lt str, right: &$lt str) -> Self {
+            self.$($field).+ = self.$($field).+.braces(left, right);
+            self
+        }
+
+        pub const fn ellipsis(mut self, s: &$lt str) -> Self {
+            self.$($field).+ = self.$($field).+.ellipsis(s);
+            self
+        }
+
+        pub const fn elem(mut self, prefix: &$lt str, suffix: &$lt str) -> Self {
+            self.$($field).+ = self.$($field).+.elem(prefix, suffix);
+            self
+        }
+
+        pub const fn show_count(mut self) -> Self {
+            self.$($field).+ = self.$($field).+.show_count();
+            self
+        }
+
+        /// Elide elements, and cut the text if still needed, so that the output fits in `width`
+        /// chars.
+        ///
+        /// See [`Summarize`](crate::Summarize).
+        pub const fn fit_width(mut self, width: usize) -> Self {
+            self.$($field).+ = self.$($field).+.fit_width(width);
+            self
+        }
+
+        /// Display the first `head` and the last `tail` elements when truncated.
+        ///
+        /// It sets the limit to `head + tail`; by default, the tail is the last element.
+        pub const fn head_tail(mut self, head: usize, tail: usize) -> Self {
+            self.$($field).+ = self.$($field).+.head_tail(head, tail);
+            self
+        }
+
+        pub fn limit(&self) -> usize {
+            self.$($field).+.limit()
+        }
+    };
+}
+
+/// Invoke `$m!` with every wrapper type, as `[generics] Type where [bounds];` items.
+///
+/// It is used to implement an integration trait for all the wrappers in one place.
+#[allow(unused_macros)]
+macro_rules! for_each_wrapper {
+    ($m:ident) => {
+        for_each_string_wrapper!($m);
+
+        $m! {
+            [T: ::core::fmt::Display] $crate::DisplayBTreeSet<'_, T>;
+            [T: ::core::fmt::Display] $crate::DisplayBTreeSetOwned<T>;
+            ['a, T, S] $crate::DisplayIntoIter<'a, T, S> where [
+                T: ::core::fmt::Display + 'a,
+                S: Clone + IntoIterator<Item = &'a T>,
+                S::IntoIter: DoubleEndedIterator + ExactSizeIterator,
+            ];
+            [T] $crate::display_option::DisplayOption<'_, T>;
+            [T] $crate::DisplayOptionOwned<T>;
+            [T: ::core::fmt::Display, E: ::core::fmt::Display]
+                $crate::display_result::DisplayResult<'_, T, E>;
+            [T: ::core::fmt::Display, E: ::core::fmt::Display] $crate::DisplayResultOwned<T, E>;
+            [T: ::core::fmt::Display] $crate::display_slice::DisplaySlice<'_, T>;
+            [T: ::core::fmt::Display] $crate::DisplaySliceOwned<T>;
+            [] $crate::display_unix_epoch::DisplayUnixTimeStamp;
+        }
+    };
+}
+
+/// Invoke `$m!` with every wrapper type that has no structure beyond its text.
+///
+/// These wrappers serialize as strings; the others are listed only in `for_each_wrapper!`.
+#[allow(unused_macros)]
+macro_rules! for_each_string_wrapper {
+    ($m:ident) => {
+        $m! {
+            [D: ::core::fmt::Display] $crate::Banner<D>;
+            [T: ::core::fmt::Debug + ?Sized] $crate::DebugAsDisplay<'_, T>;
+            [] $crate::diff::DisplayDiff;
+            [A: $crate::DisplayAtomicExt + ?Sized] $crate::DisplayAtomic<'_, A>;
+            [] $crate::DisplayBitset<'_>;
+            [] $crate::DisplayBool;
+            [T: ::core::fmt::Display + Copy] $crate::DisplayCell<'_, T>;
+            [N: ::core::fmt::Display] $crate::DisplayCount<'_, N>;
+            [N: ::core::fmt::Display] $crate::DisplayDelta<N>;
+            [] $crate::DisplayDurationStats;
+            [K: ::core::convert::AsRef<str>, V: ::core::convert::AsRef<str>] $crate::DisplayEnv<'_, K, V>;
+            [] $crate::DisplayFixed;
+            [T: ::core::convert::Into<u64> + Copy] $crate::DisplayFlags<'_, T>;
+            [] $crate::DisplayIso8601Duration;
+            [] $crate::DisplayGrouped;
+            [K: ::core::convert::AsRef<str>, V: ::core::convert::AsRef<str>] $crate::DisplayHeaders<'_, K, V>;
+            [D: ::core::fmt::Display] $crate::DisplayLimited<D>;
+            [D: ::core::fmt::Display] $crate::DisplayLineNumbers<D>;
+            [D: ::core::fmt::Display] $crate::DisplayLinePrefix<'_, D>;
+            [F] $crate::DisplayOnce<F> where [
+                F: FnOnce(&mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result,
+            ];
+            [T: ::core::fmt::Display] $crate::DisplayOnceCell<'_, T>;
+            [] $crate::DisplayOrdering;
+            [A: ::core::fmt::Display, B: ::core::fmt::Display] $crate::DisplayPair<'_, A, B>;
+            [] $crate::DisplayProgress;
+            [T: ?Sized] $crate::DisplayPtr<'_, T>;
+            [] $crate::DisplayRadix;
+            [T: ::core::fmt::Display] $crate::DisplayRange<'_, T>;
+            [T: ::core::fmt::Display + ?Sized] $crate::DisplayRefCell<'_, T>;
+            [] $crate::DisplayScaled;
+            [] $crate::DisplaySci;
+            [] $crate::DisplaySecs;
+            [] $crate::DisplayShortest;
+            [T: $crate::DisplayFloatExt] $crate::DisplayShortestSlice<'_, T>;
+            [] $crate::DisplaySigFigs;
+            [] $crate::DisplaySocketAddrs<'_>;
+            [] $crate::DisplaySqlIdent<'_>;
+            [] $crate::DisplaySqlLiteral<'_>;
+            ['a, N, L, C, I] $crate::DisplayTree<'a, N, L, C> where [
+                L: Fn(&N, &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result,
+                C: Fn(&'a N) -> I,
+                I: IntoIterator<Item = &'a N>,
+            ];
+            [T: $crate::DisplayTupleExt + ?Sized] $crate::DisplayTuple<'_, T>;
+            [] $crate::DisplayTypeName;
+            [] $crate::DisplayVisible<'_>;
+            [K: ::core::fmt::Display] $crate::DisplayWatermarks<'_, K>;
+            [T: ::core::fmt::Display + ?Sized] $crate::DisplayWeak<'_, T>;
+            [D: ::core::fmt::Display] $crate::DisplayWrap<D>;
+            [D: ::core::fmt::Display] $crate::EscapeHtml<D>;
+            [] $crate::Summary;
+            [] $crate::table::Table;
+        }
+
+        #[cfg(feature = "std")]
+        $m! {
+            [D: ::core::fmt::Display] $crate::Cached<D>;
+            [] $crate::DisplayBacktraceSummary<'_>;
+            [] $crate::DisplayByteSize;
+            [L: ::core::fmt::Display] $crate::DisplayElapsed<'_, L>;
+            [] $crate::DisplayExitStatus;
+            [T: ::core::fmt::Display + ?Sized] $crate::DisplayMutex<'_, T>;
+            [] $crate::DisplayPath<'_>;
+            [P: ::core::convert::AsRef<::std::path::Path>] $crate::DisplayPaths<'_, P>;
+            [] $crate::DisplayPercent;
+            [] $crate::DisplayRatio;
+            [T: ::core::fmt::Display + ?Sized] $crate::DisplayRwLock<'_, T>;
+            [] $crate::DisplaySi;
+            [] $crate::DisplayThread;
+        }
+
+        #[cfg(feature = "color")]
+        $m! {
+            [D: ::core::fmt::Display] $crate::color::Styled<D>;
+        }
+
+        #[cfg(feature = "uuid")]
+        $m! {
+            [] $crate::DisplayUuidShort;
+        }
+
+        #[cfg(feature = "bitflags")]
+        $m! {
+            [F] $crate::DisplayBitflags<'_, F> where [
+                F: ::bitflags::Flags,
+                F::Bits: ::core::convert::Into<u64>,
+            ];
+        }
+
+        #[cfg(feature = "json")]
+        $m! {
+            [] $crate::DisplayJsonSummary<'_>;
+        }
+    };
+}
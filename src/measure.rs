@@ -0,0 +1,72 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+use core::fmt::Write;
+
+/// The size of the output of a `Display` value, see [`measure`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Measure {
+    /// The length in bytes.
+    pub bytes: usize,
+    /// The number of chars, which is the width used for layout throughout this crate.
+    pub chars: usize,
+}
+
+impl Write for Measure {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.bytes += s.len();
+        self.chars += s.chars().count();
+        Ok(())
+    }
+}
+
+/// Return the number of bytes and chars a `Display` value produces, without allocating.
+///
+/// The output is counted as it is written and then discarded, so layout code such as padding
+/// can plan with it before rendering into the destination once.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::measure;
+///
+/// let m = measure(&"héllo");
+/// assert_eq!(m.bytes, 6);
+/// assert_eq!(m.chars, 5);
+/// ```
+pub fn measure(d: &(impl fmt::Display + ?Sized)) -> Measure {
+    let mut m = Measure::default();
+    // Writing to `Measure` never fails; an error could only come from `d` itself, in which case
+    // the size of what was written before it is returned.
+    let _ = write!(m, "{}", d);
+    m
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DisplaySliceExt;
+
+    #[test]
+    fn test_measure() {
+        assert_eq!(Measure::default(), measure(""));
+        assert_eq!(
+            Measure { bytes: 7, chars: 7 },
+            measure(&[1, 2, 3].display())
+        );
+        assert_eq!(Measure { bytes: 6, chars: 2 }, measure("中文"));
+        assert_eq!(Measure { bytes: 3, chars: 3 }, measure(&123));
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `PartialEq<str>` implementations for the wrappers, comparing the displayed output.
+//!
+//! The output is compared piece by piece as it is written, without building a `String`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use display_more::DisplaySliceExt;
+//!
+//! assert_eq!([1, 2, 3].display(), "[1,2,3]");
+//! ```
+
+use core::fmt;
+use core::fmt::Write;
+
+/// A `fmt::Write` that consumes the expected text and fails at the first mismatch.
+struct Matcher<'a> {
+    rest: &'a str,
+}
+
+impl Write for Matcher<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match self.rest.strip_prefix(s) {
+            Some(rest) => {
+                self.rest = rest;
+                Ok(())
+            }
+            None => Err(fmt::Error),
+        }
+    }
+}
+
+/// Return whether `d` displays exactly as `expected`.
+pub(crate) fn display_eq(d: &(impl fmt::Display + ?Sized), expected: &str) -> bool {
+    let mut m = Matcher { rest: expected };
+    write!(m, "{}", d).is_ok() && m.rest.is_empty()
+}
+
+/// Implement `PartialEq<str>` and `PartialEq<&str>`, for `[generics] Type where [bounds]`.
+macro_rules! impl_partial_eq_str {
+    ($([$($g:tt)*] $ty:ty $(where [$($w:tt)*])?;)*) => {
+        $(
+            impl<$($g)*> PartialEq<str> for $ty $(where $($w)*)? {
+                fn eq(&self, other: &str) -> bool {
+                    display_eq(self, other)
+                }
+            }
+
+            impl<$($g)*> PartialEq<&str> for $ty $(where $($w)*)? {
+                fn eq(&self, other: &&str) -> bool {
+                    display_eq(self, other)
+                }
+            }
+        )*
+    };
+}
+
+for_each_wrapper!(impl_partial_eq_str);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DisplayOptionExt;
+    use crate::DisplayResultExt;
+    use crate::DisplaySliceExt;
+
+    #[test]
+    fn test_display_eq() {
+        assert!(display_eq("abc", "abc"));
+        assert!(display_eq("", ""));
+        assert!(!display_eq("abc", "ab"));
+        assert!(!display_eq("ab", "abc"));
+        assert!(!display_eq("abd", "abc"));
+    }
+
+    #[test]
+    fn test_partial_eq_str() {
+        let a = [1, 2, 3, 4, 5, 6, 7];
+        assert_eq!(a.display(), "[1,2,3,4,..,7]");
+        assert_ne!(a.display(), "[1,2,3,4,..,7");
+        assert_ne!(a.display(), "[1,2,3,4,..,7]]");
+        assert!(a.display() == *"[1,2,3,4,..,7]");
+
+        assert_eq!(None::<u64>.display(), "None");
+        assert_eq!(Err::<u64, u64>(1).display(), "Err(1)");
+        assert_eq!(crate::banner("a"), "==== a ====");
+    }
+}
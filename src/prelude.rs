@@ -0,0 +1,134 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Import all the extension traits at once.
+//!
+//! # Example
+//!
+//! ```rust
+//! use display_more::prelude::*;
+//!
+//! assert_eq!(Some(1).display().to_string(), "1");
+//! assert_eq!(Ok::<u64, &str>(1).display().to_string(), "Ok(1)");
+//! assert_eq!([1, 2, 3].display().to_string(), "[1,2,3]");
+//! ```
+
+pub use crate::AsJsonExt;
+pub use crate::AsYamlExt;
+pub use crate::DebugAsDisplayExt;
+pub use crate::DisplayAtomicExt;
+pub use crate::DisplayBTreeSetExt;
+#[cfg(feature = "std")]
+pub use crate::DisplayBacktraceExt;
+#[cfg(feature = "bitflags")]
+pub use crate::DisplayBitflagsExt;
+pub use crate::DisplayBitsetExt;
+pub use crate::DisplayBoolExt;
+#[cfg(feature = "std")]
+pub use crate::DisplayByteSizeExt;
+pub use crate::DisplayCellExt;
+pub use crate::DisplayCountExt;
+pub use crate::DisplayDebugOptionExt;
+pub use crate::DisplayDeltaExt;
+pub use crate::DisplayDurationExt;
+pub use crate::DisplayDurationStatsExt;
+#[cfg(feature = "std")]
+pub use crate::DisplayExitStatusExt;
+pub use crate::DisplayFloatExt;
+pub use crate::DisplayFloatSliceExt;
+pub use crate::DisplayGroupedExt;
+#[cfg(feature = "json")]
+pub use crate::DisplayJsonSummaryExt;
+pub use crate::DisplayLinesExt;
+#[cfg(feature = "std")]
+pub use crate::DisplayMutexExt;
+pub use crate::DisplayOnceCellExt;
+pub use crate::DisplayOptionExt;
+pub use crate::DisplayOrderingExt;
+#[cfg(feature = "std")]
+pub use crate::DisplayPathExt;
+#[cfg(feature = "std")]
+pub use crate::DisplayPathsExt;
+#[cfg(feature = "std")]
+pub use crate::DisplayPercentExt;
+pub use crate::DisplayPtrExt;
+pub use crate::DisplayRadixExt;
+pub use crate::DisplayRangeExt;
+pub use crate::DisplayRefCellExt;
+pub use crate::DisplayResultExt;
+#[cfg(feature = "std")]
+pub use crate::DisplayRwLockExt;
+pub use crate::DisplaySecsExt;
+#[cfg(feature = "std")]
+pub use crate::DisplaySiExt;
+pub use crate::DisplaySliceExt;
+pub use crate::DisplaySocketAddrsExt;
+pub use crate::DisplaySqlIdentExt;
+pub use crate::DisplaySqlLiteralExt;
+#[cfg(feature = "std")]
+pub use crate::DisplayThreadExt;
+pub use crate::DisplayTupleExt;
+pub use crate::DisplayTypeOfExt;
+pub use crate::DisplayUnixTimeStampExt;
+#[cfg(feature = "uuid")]
+pub use crate::DisplayUuidExt;
+pub use crate::DisplayVisibleExt;
+pub use crate::DisplayWatermarksExt;
+pub use crate::DisplayWeakExt;
+pub use crate::DisplayWidth;
+pub use crate::EscapeHtmlExt;
+pub use crate::LimitBytesExt;
+pub use crate::SizeHint;
+pub use crate::Summarize;
+pub use crate::ToBoxedDisplay;
+#[cfg(feature = "compact_str")]
+pub use crate::ToCompactStringExt;
+#[cfg(feature = "std")]
+pub use crate::ToLogStringExt;
+#[cfg(feature = "smallstr")]
+pub use crate::ToSmallStringExt;
+#[cfg(feature = "tracing")]
+pub use crate::TracingFieldExt;
+pub use crate::WriteToExt;
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeSet;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+    use core::cell::RefCell;
+    use core::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_prelude_methods_are_unambiguous() {
+        assert_eq!("None", None::<u64>.display().to_string());
+        assert_eq!("\"a\"", Some("a").display_debug().to_string());
+        assert_eq!("Err(e)", Err::<u64, _>("e").display().to_string());
+        assert_eq!("[1,2]", Vec::from([1, 2]).display().to_string());
+        assert_eq!("[1,2]", BTreeSet::from([1, 2]).display().to_string());
+        assert_eq!("(1, a)", (1, "a").display().to_string());
+        assert_eq!("1", RefCell::new(1).display().to_string());
+        assert_eq!("[3, 10)", (3..10).display().to_string());
+        assert_eq!(
+            "1970-01-01T00:00:01.000",
+            Duration::from_secs(1)
+                .display_unix_timestamp_short()
+                .to_string()
+        );
+        assert_eq!("abc", "abc".limit_bytes(3).to_string());
+        assert_eq!("u8", 1u8.display_type_of().to_string());
+    }
+}
@@ -0,0 +1,157 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::display_duration::fmt_human;
+
+/// Display a labeled elapsed time, such as `load segment took 182.4ms`.
+#[must_use]
+pub struct DisplayElapsed<'a, L: fmt::Display> {
+    label: &'a L,
+    elapsed: Duration,
+}
+
+impl<'a, L: fmt::Display> DisplayElapsed<'a, L> {
+    pub const fn new(label: &'a L, elapsed: Duration) -> Self {
+        Self { label, elapsed }
+    }
+
+    pub const fn label(&self) -> &'a L {
+        self.label
+    }
+
+    pub const fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+impl<L: fmt::Display> fmt::Display for DisplayElapsed<'_, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} took ", self.label)?;
+        fmt_human(f, self.elapsed)
+    }
+}
+
+impl<L: fmt::Display> fmt::Debug for DisplayElapsed<'_, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// A guard that measures the time since it is created, and reports it when dropped.
+///
+/// The report is a [`DisplayElapsed`], such as `load segment took 182.4ms`, passed to the sink.
+/// [`new`](Self::new) prints it to stderr; [`with_sink`](Self::with_sink) accepts any closure,
+/// such as one that logs it.
+///
+/// # Example
+///
+/// ```rust
+/// use std::cell::RefCell;
+///
+/// use display_more::ScopedTimer;
+///
+/// let report = RefCell::new(String::new());
+/// {
+///     let _t = ScopedTimer::with_sink("load segment", |r| *report.borrow_mut() = r.to_string());
+///     // load the segment
+/// }
+/// assert!(report.borrow().starts_with("load segment took "));
+/// ```
+#[must_use = "the elapsed time is reported as soon as the timer is dropped"]
+pub struct ScopedTimer<L, F = fn(&DisplayElapsed<'_, L>)>
+where
+    L: fmt::Display,
+    F: FnOnce(&DisplayElapsed<'_, L>),
+{
+    label: L,
+    start: Instant,
+    sink: Option<F>,
+}
+
+impl<L: fmt::Display> ScopedTimer<L> {
+    /// Create a timer that prints the elapsed time to stderr when dropped.
+    pub fn new(label: L) -> Self {
+        Self::with_sink(label, |r| eprintln!("{r}"))
+    }
+}
+
+impl<L, F> ScopedTimer<L, F>
+where
+    L: fmt::Display,
+    F: FnOnce(&DisplayElapsed<'_, L>),
+{
+    /// Create a timer that passes the elapsed time to `sink` when dropped.
+    pub fn with_sink(label: L, sink: F) -> Self {
+        Self {
+            label,
+            start: Instant::now(),
+            sink: Some(sink),
+        }
+    }
+
+    /// Return the time elapsed so far.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+impl<L, F> Drop for ScopedTimer<L, F>
+where
+    L: fmt::Display,
+    F: FnOnce(&DisplayElapsed<'_, L>),
+{
+    fn drop(&mut self) {
+        if let Some(sink) = self.sink.take() {
+            sink(&DisplayElapsed::new(&self.label, self.elapsed()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[test]
+    fn test_display_elapsed() {
+        let d = DisplayElapsed::new(&"load segment", Duration::from_micros(182_400));
+        assert_eq!("load segment took 182.4ms", d.to_string());
+        assert_eq!(Duration::from_micros(182_400), d.elapsed());
+    }
+
+    #[test]
+    fn test_scoped_timer() {
+        let reports = RefCell::new(Vec::new());
+        {
+            let t = ScopedTimer::with_sink(format!("segment {}", 3), |r| {
+                reports.borrow_mut().push((r.to_string(), r.elapsed()));
+            });
+            std::thread::sleep(Duration::from_millis(2));
+            assert!(t.elapsed() >= Duration::from_millis(2));
+            assert!(reports.borrow().is_empty());
+        }
+
+        let reports = reports.into_inner();
+        assert_eq!(1, reports.len());
+        let (msg, elapsed) = &reports[0];
+        assert!(*elapsed >= Duration::from_millis(2));
+        assert!(msg.starts_with("segment 3 took "), "{msg}");
+        assert!(msg.ends_with('s'), "{msg}");
+    }
+}
@@ -0,0 +1,269 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `Serialize` implementations for the wrappers, enabled by the `serde` feature.
+//!
+//! A wrapper serializes as the string it displays, except:
+//! - slices and sets serialize as an array of the displayed elements, with the ellipsis as an
+//!   element in place of the omitted ones;
+//! - `None` serializes as `null` and `Some` as the displayed value;
+//! - `Result` serializes as `{"Ok": ..}` or `{"Err": ..}` with the displayed value.
+
+use core::fmt;
+
+use serde::ser::SerializeSeq;
+use serde::Serialize;
+use serde::Serializer;
+
+use crate::display_once;
+use crate::display_option::DisplayOption;
+use crate::display_result::DisplayResult;
+use crate::display_slice::DisplaySlice;
+use crate::display_unix_epoch::DisplayUnixTimeStamp;
+use crate::DisplayBTreeSet;
+use crate::DisplayBTreeSetOwned;
+use crate::DisplayIntoIter;
+use crate::DisplayOptionOwned;
+use crate::DisplayResultOwned;
+use crate::DisplaySliceOwned;
+use crate::Unstyled;
+
+/// Serialize a `Display` as the string it outputs, without styling.
+struct Str<D>(D);
+
+impl<D: fmt::Display> Serialize for Str<D> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&Unstyled::new(&self.0))
+    }
+}
+
+/// Implement `Serialize` as the displayed string, for `[generics] Type where [bounds]`.
+macro_rules! impl_serialize_as_str {
+    ($([$($g:tt)*] $ty:ty $(where [$($w:tt)*])?;)*) => {
+        $(
+            impl<$($g)*> Serialize for $ty $(where $($w)*)? {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(&Unstyled::new(self))
+                }
+            }
+        )*
+    };
+}
+
+for_each_string_wrapper!(impl_serialize_as_str);
+
+impl<'a, T, S> Serialize for DisplayIntoIter<'a, T, S>
+where
+    T: fmt::Display + 'a,
+    S: Clone + IntoIterator<Item = &'a T>,
+    S::IntoIter: DoubleEndedIterator + ExactSizeIterator,
+{
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let iter = self.items.clone().into_iter();
+        let len = match self.options.head_tail_of(iter.len()) {
+            Some((head, tail)) => head + 1 + tail,
+            None => iter.len(),
+        };
+
+        let mut seq = serializer.serialize_seq(Some(len))?;
+        for elem in self.options.layout(iter) {
+            seq.serialize_element(&Str(elem))?;
+        }
+        seq.end()
+    }
+}
+
+impl<T: fmt::Display> Serialize for DisplaySlice<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.inner.serialize(serializer)
+    }
+}
+
+impl<T: fmt::Display> Serialize for DisplaySliceOwned<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        DisplayIntoIter::with_options(self.items.iter(), self.options).serialize(serializer)
+    }
+}
+
+impl<T: fmt::Display> Serialize for DisplayBTreeSet<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.inner.serialize(serializer)
+    }
+}
+
+impl<T: fmt::Display> Serialize for DisplayBTreeSetOwned<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        DisplayIntoIter::with_options(&self.items, self.options).serialize(serializer)
+    }
+}
+
+impl<T> Serialize for DisplayOption<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.inner {
+            None => serializer.serialize_none(),
+            Some(_) => serializer.serialize_some(&Str(self)),
+        }
+    }
+}
+
+impl<T> Serialize for DisplayOptionOwned<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.inner {
+            None => serializer.serialize_none(),
+            Some(_) => serializer.serialize_some(&Str(self)),
+        }
+    }
+}
+
+impl<T: fmt::Display, E: fmt::Display> Serialize for DisplayResult<'_, T, E> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Ok(t) => serializer.serialize_newtype_variant("Result", 0, "Ok", &Str(t)),
+            Err(e) => serializer.serialize_newtype_variant("Result", 1, "Err", &Str(e)),
+        }
+    }
+}
+
+impl<T: fmt::Display, E: fmt::Display> Serialize for DisplayResultOwned<T, E> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        DisplayResult(&self.0).serialize(serializer)
+    }
+}
+
+impl Serialize for DisplayUnixTimeStamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Some(datetime) = self.datetime() else {
+            return serializer.serialize_none();
+        };
+        serializer.collect_str(&display_once(|f| {
+            self.fmt_datetime(f, &datetime)?;
+            if self.with_timezone {
+                f.write_str("Z+0000")?;
+            }
+            Ok(())
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::time::Duration;
+
+    use serde_json::json;
+    use serde_json::to_value;
+
+    use super::*;
+    use crate::DisplayBTreeSetExt;
+    use crate::DisplayByteSizeExt;
+    use crate::DisplayDebugOptionExt;
+    use crate::DisplayOptionExt;
+    use crate::DisplayResultExt;
+    use crate::DisplaySliceExt;
+    use crate::DisplayUnixTimeStampExt;
+
+    #[test]
+    fn test_serialize_as_str() {
+        assert_eq!(
+            json!("1.50 KiB"),
+            to_value(1536u64.display_byte_size()).unwrap()
+        );
+        assert_eq!(json!("==== a ===="), to_value(crate::banner("a")).unwrap());
+        assert_eq!(
+            json!("1970-01-01T00:00:00.000"),
+            to_value(Duration::ZERO.display_unix_timestamp_short()).unwrap()
+        );
+        assert_eq!(
+            json!(null),
+            to_value(None::<Duration>.display_unix_timestamp()).unwrap()
+        );
+        assert_eq!(
+            json!(null),
+            to_value(Duration::MAX.display_unix_timestamp()).unwrap()
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_serialize_json_summary() {
+        use crate::DisplayJsonSummaryExt;
+
+        let v = json!({"a": [1, 2]});
+        assert_eq!(
+            json!(r#"{"a":[1,2]}"#),
+            to_value(v.display_json_summary()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_serialize_slice() {
+        let a = [1, 2, 3, 4, 5, 6, 7];
+        assert_eq!(
+            json!(["1", "2", "3", "4", "..", "7"]),
+            to_value(a.display()).unwrap()
+        );
+        assert_eq!(json!(["1", "2", "3"]), to_value(a[..3].display()).unwrap());
+        assert_eq!(json!([".."]), to_value(a.display_n(0)).unwrap());
+        assert_eq!(json!(["..", "7"]), to_value(a.display_n(1)).unwrap());
+        assert_eq!(
+            json!(["1", "..(7 total)", "7"]),
+            to_value(a.display_n(2).show_count()).unwrap()
+        );
+        assert_eq!(
+            json!(["1", "..", "6", "7"]),
+            to_value(a.display().head_tail(1, 2)).unwrap()
+        );
+        assert_eq!(
+            json!(["1", "..", "7"]),
+            to_value(DisplaySliceOwned::new(a.to_vec()).at_most(Some(2))).unwrap()
+        );
+
+        let set = ["b", "a"].into_iter().collect::<BTreeSet<_>>();
+        assert_eq!(json!(["a", "b"]), to_value(set.display()).unwrap());
+    }
+
+    #[test]
+    fn test_serialize_slice_same_layout_as_json() {
+        use crate::AsJsonExt;
+
+        let a = [1, 2, 3, 4, 5, 6, 7];
+        for d in [
+            a.display(),
+            a.display_n(0),
+            a.display_n(3).show_count(),
+            a.display().head_tail(2, 2),
+            a.display().head_tail(3, 0),
+            a.display().head_tail(usize::MAX, 1),
+        ] {
+            let json: serde_json::Value = serde_json::from_str(&d.as_json().to_string()).unwrap();
+            assert_eq!(json, to_value(&d).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_serialize_option_result() {
+        assert_eq!(json!("1"), to_value(Some(1).display()).unwrap());
+        assert_eq!(json!(null), to_value(None::<u64>.display()).unwrap());
+        assert_eq!(json!("\"a\""), to_value(Some("a").display_debug()).unwrap());
+
+        assert_eq!(
+            json!({"Ok": "1"}),
+            to_value(Ok::<u64, u64>(1).display()).unwrap()
+        );
+        assert_eq!(
+            json!({"Err": "bad"}),
+            to_value(DisplayResultOwned(Err::<u64, &str>("bad"))).unwrap()
+        );
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::string::String;
+use core::fmt;
+use core::fmt::Write;
+
+use chrono::Datelike;
+
+use crate::display_option::DisplayOption;
+use crate::display_result::DisplayResult;
+use crate::display_slice::DisplaySlice;
+use crate::display_unix_epoch::DisplayUnixTimeStamp;
+use crate::DisplayBTreeSet;
+use crate::DisplayConfig;
+use crate::DisplayIntoIter;
+
+/// The estimated length of a value whose output length is unknown, such as a generic element.
+const UNKNOWN_SIZE: usize = 8;
+
+/// Estimate the length of the output in bytes, to pre-allocate a `String` for it.
+///
+/// The estimate is exact for fixed-width output such as timestamps. For collections it is
+/// computed from the number of elements displayed, assuming 8 bytes for each.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplaySliceExt;
+/// use display_more::SizeHint;
+///
+/// let a = (1..=100).collect::<Vec<_>>();
+/// let s = a.display().to_string_with_capacity();
+/// assert_eq!(s, "[1,2,3,4,..,100]");
+/// assert!(s.capacity() >= a.display().size_hint());
+/// ```
+pub trait SizeHint: fmt::Display {
+    /// Return the estimated length of the output in bytes.
+    fn size_hint(&self) -> usize;
+
+    /// Format into a `String` allocated with the estimated length.
+    fn to_string_with_capacity(&self) -> String {
+        let mut s = String::with_capacity(self.size_hint());
+        write!(s, "{}", self).expect("a Display implementation returned an error unexpectedly");
+        s
+    }
+}
+
+impl<'a, T, S> SizeHint for DisplayIntoIter<'a, T, S>
+where
+    T: fmt::Display + 'a,
+    S: Clone + IntoIterator<Item = &'a T>,
+    S::IntoIter: DoubleEndedIterator + ExactSizeIterator,
+{
+    fn size_hint(&self) -> usize {
+        let o = self.options.resolved();
+        let len = self.items.clone().into_iter().len();
+        let shown = len.min(o.limit());
+
+        let elem = o.elem_prefix.len() + UNKNOWN_SIZE + o.elem_suffix.len();
+        let mut size = o.left_brace.len() + o.right_brace.len() + shown * elem;
+        size += shown.saturating_sub(1) * o.separator().len();
+
+        if len > shown {
+            size += o.ellipsis.len() + o.separator().len();
+            if o.show_count {
+                size += " total)".len() + 1 + UNKNOWN_SIZE;
+            }
+        }
+
+        match o.max_width {
+            // A width in chars is at most 4 bytes each.
+            Some(w) => size.min(w.saturating_mul(4)),
+            None => size,
+        }
+    }
+}
+
+impl<T: fmt::Display> SizeHint for DisplaySlice<'_, T> {
+    fn size_hint(&self) -> usize {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: fmt::Display> SizeHint for DisplayBTreeSet<'_, T> {
+    fn size_hint(&self) -> usize {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> SizeHint for DisplayOption<'_, T> {
+    fn size_hint(&self) -> usize {
+        match self.inner {
+            Some(_) => UNKNOWN_SIZE,
+            None => DisplayConfig::current().none.len(),
+        }
+    }
+}
+
+impl<T: fmt::Display, E: fmt::Display> SizeHint for DisplayResult<'_, T, E> {
+    fn size_hint(&self) -> usize {
+        match self.0 {
+            Ok(_) => "Ok()".len() + UNKNOWN_SIZE,
+            Err(_) => "Err()".len() + UNKNOWN_SIZE,
+        }
+    }
+}
+
+impl SizeHint for DisplayUnixTimeStamp {
+    fn size_hint(&self) -> usize {
+        let Some(datetime) = self.datetime() else {
+            return DisplayConfig::current().none.len();
+        };
+
+        let precision = if self.in_millis {
+            3
+        } else {
+            DisplayConfig::current().timestamp_precision
+        };
+
+        // A year out of `0..=9999` has a sign and as many digits as needed.
+        let year = datetime.year();
+        let mut size = if (0..=9999).contains(&year) {
+            4
+        } else {
+            1 + year.unsigned_abs().ilog10() as usize + 1
+        };
+        size += "-08-08T07:40:19".len();
+        if precision > 0 {
+            size += 1 + precision;
+        }
+        if self.with_timezone {
+            size += "Z+0000".len();
+        }
+        size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeSet;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+    use core::time::Duration;
+
+    use super::*;
+    use crate::DisplayBTreeSetExt;
+    use crate::DisplayOptionExt;
+    use crate::DisplayResultExt;
+    use crate::DisplaySliceExt;
+    use crate::DisplayUnixTimeStampExt;
+
+    #[test]
+    fn test_size_hint_timestamp_is_exact() {
+        let t = Duration::from_millis(1723102819023);
+        for d in [
+            t.display_unix_timestamp(),
+            t.display_unix_timestamp_short(),
+            t.display_unix_timestamp().in_millis(true),
+            t.display_unix_timestamp().with_timezone(false),
+            None.display_unix_timestamp(),
+            // Year 10000, which has a sign and 5 digits.
+            Duration::from_secs(253_402_300_800).display_unix_timestamp(),
+        ] {
+            assert_eq!(d.to_string().len(), d.size_hint());
+        }
+    }
+
+    #[test]
+    fn test_size_hint_slice() {
+        let a = (1..=100).collect::<Vec<_>>();
+        // "[" + 5 elements + 4 separators + ".." + "]"
+        assert_eq!(1 + 5 * 8 + 5 + 2 + 1, a.display().size_hint());
+        assert_eq!(2, Vec::<u8>::new().display().size_hint());
+        assert_eq!(2 + 8 + 8 + 1, [1, 2].display().size_hint());
+
+        assert_eq!(
+            a.display().size_hint(),
+            a.display().fit_width(usize::MAX).size_hint()
+        );
+
+        let set = (1..=3).collect::<BTreeSet<_>>();
+        assert_eq!(set.display().size_hint(), [1, 2, 3].display().size_hint());
+    }
+
+    #[test]
+    fn test_to_string_with_capacity() {
+        let a = [1, 2, 3];
+        let s = a.display().to_string_with_capacity();
+        assert_eq!("[1,2,3]", s);
+        assert!(s.capacity() >= a.display().size_hint());
+
+        assert_eq!("None", None::<u8>.display().to_string_with_capacity());
+        assert_eq!(4, None::<u8>.display().size_hint());
+        let r = Result::<u8, u8>::Err(1);
+        assert_eq!("Err(1)", r.display().to_string_with_capacity());
+    }
+}
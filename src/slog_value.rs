@@ -0,0 +1,102 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `slog::Value` implementations for the wrappers, enabled by the `slog` feature.
+//!
+//! A wrapper is emitted as formatted arguments, without an intermediate `String`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use display_more::DisplaySliceExt;
+//!
+//! let logger = slog::Logger::root(slog::Discard, slog::o!());
+//! let nodes = vec![1, 2, 3];
+//! slog::info!(logger, "membership changed"; "peers" => nodes.display());
+//! ```
+
+use slog::Key;
+use slog::Record;
+use slog::Serializer;
+use slog::Value;
+
+use crate::Unstyled;
+
+/// Implement `slog::Value` by emitting the displayed value, for `[generics] Type where [bounds]`.
+macro_rules! impl_slog_value {
+    ($([$($g:tt)*] $ty:ty $(where [$($w:tt)*])?;)*) => {
+        $(
+            impl<$($g)*> Value for $ty $(where $($w)*)? {
+                fn serialize(
+                    &self,
+                    _record: &Record<'_>,
+                    key: Key,
+                    serializer: &mut dyn Serializer,
+                ) -> slog::Result {
+                    serializer.emit_arguments(key, &format_args!("{}", Unstyled::new(self)))
+                }
+            }
+        )*
+    };
+}
+
+for_each_wrapper!(impl_slog_value);
+
+#[cfg(test)]
+mod tests {
+    use std::fmt;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    use slog::Drain;
+    use slog::OwnedKVList;
+
+    use super::*;
+    use crate::DisplayOptionExt;
+    use crate::DisplaySliceExt;
+
+    /// Collect the key-values of every record as `key=value`.
+    #[derive(Clone, Default)]
+    struct Collect(Arc<Mutex<Vec<String>>>);
+
+    impl Serializer for Collect {
+        fn emit_arguments(&mut self, key: Key, val: &fmt::Arguments<'_>) -> slog::Result {
+            self.0.lock().unwrap().push(format!("{}={}", key, val));
+            Ok(())
+        }
+    }
+
+    impl Drain for Collect {
+        type Ok = ();
+        type Err = slog::Never;
+
+        fn log(&self, record: &Record<'_>, _: &OwnedKVList) -> Result<(), slog::Never> {
+            slog::KV::serialize(&record.kv(), record, &mut self.clone()).unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_slog_value() {
+        let c = Collect::default();
+        let logger = slog::Logger::root(c.clone(), slog::o!());
+        let a = [1, 2, 3, 4, 5, 6, 7];
+
+        slog::info!(logger, "x"; "peers" => a.display(), "leader" => None::<u64>.display());
+
+        let mut got = c.0.lock().unwrap().clone();
+        got.sort();
+        assert_eq!(got, vec!["leader=None", "peers=[1,2,3,4,..,7]"]);
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+use core::fmt::Write;
+
+use smallstr::SmallString;
+
+/// Format a value into a `SmallString` that stores up to `N` bytes inline, on the stack, and
+/// only allocates on the heap for a longer output.
+///
+/// Most wrapper outputs, such as timestamps and short slices, fit in a few dozen bytes.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use display_more::DisplayUnixTimeStampExt;
+/// use display_more::ToSmallStringExt;
+///
+/// let t = Duration::from_millis(1723102819023).display_unix_timestamp_short();
+/// let s = t.to_small_string::<32>();
+/// assert_eq!(s.as_str(), "2024-08-08T07:40:19.023");
+/// assert!(!s.spilled());
+/// ```
+pub trait ToSmallStringExt: fmt::Display {
+    fn to_small_string<const N: usize>(&self) -> SmallString<[u8; N]> {
+        let mut s = SmallString::new();
+        write!(s, "{}", self).expect("a Display implementation returned an error unexpectedly");
+        s
+    }
+}
+
+impl<T: fmt::Display + ?Sized> ToSmallStringExt for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DisplaySliceExt;
+
+    #[test]
+    fn test_to_small_string() {
+        let s = [1, 2, 3].display().to_small_string::<8>();
+        assert_eq!("[1,2,3]", s.as_str());
+        assert!(!s.spilled());
+
+        let s = "0123456789".to_small_string::<8>();
+        assert_eq!("0123456789", s.as_str());
+        assert!(s.spilled());
+
+        let s = "".to_small_string::<0>();
+        assert_eq!("", s.as_str());
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implement the extension traits for `Box<T>`, `Rc<T>`, `Arc<T>` and `Cow<'_, T>` by
+//! forwarding to `T`, so that generic code bounded on an extension trait accepts wrapped values.
+
+use alloc::borrow::Cow;
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use core::fmt;
+
+use crate::display_option::DisplayOption;
+use crate::display_result::DisplayResult;
+use crate::display_slice::DisplaySlice;
+use crate::display_unix_epoch::DisplayUnixTimeStamp;
+use crate::DisplayBTreeSet;
+use crate::DisplayBTreeSetExt;
+use crate::DisplayDebugOptionExt;
+use crate::DisplayOptionExt;
+use crate::DisplayResultExt;
+use crate::DisplaySliceExt;
+use crate::DisplaySocketAddrs;
+use crate::DisplaySocketAddrsExt;
+use crate::DisplaySqlIdent;
+use crate::DisplaySqlIdentExt;
+use crate::DisplaySqlLiteral;
+use crate::DisplaySqlLiteralExt;
+use crate::DisplayUnixTimeStampExt;
+
+/// Implement `$tr` for the smart pointers to a `P: $tr`, with the methods in the body, for
+/// `[generics,] Trait { methods }`.
+///
+/// In the body `P` is the pointee type and a method forwards with `P::method(self)`.
+macro_rules! impl_for_pointers {
+    ([$($g:tt)*] $tr:path { $($body:tt)* }) => {
+        impl<$($g)* P: $tr + ?Sized> $tr for Box<P> { $($body)* }
+        impl<$($g)* P: $tr + ?Sized> $tr for Rc<P> { $($body)* }
+        impl<$($g)* P: $tr + ?Sized> $tr for Arc<P> { $($body)* }
+        impl<'c, $($g)* P: $tr + ToOwned + ?Sized + 'c> $tr for Cow<'c, P> { $($body)* }
+    };
+}
+
+impl_for_pointers!(['a, T: fmt::Display,] DisplayOptionExt<'a, T> {
+    fn display(&'a self) -> DisplayOption<'a, T> {
+        P::display(self)
+    }
+});
+
+impl_for_pointers!(['a, T: fmt::Debug,] DisplayDebugOptionExt<'a, T> {
+    fn display_debug(&'a self) -> DisplayOption<'a, T> {
+        P::display_debug(self)
+    }
+});
+
+impl_for_pointers!(['a, T: fmt::Display, E: fmt::Display,] DisplayResultExt<'a, T, E> {
+    fn display(&'a self) -> DisplayResult<'a, T, E> {
+        P::display(self)
+    }
+});
+
+impl_for_pointers!(['a, T: fmt::Display,] DisplaySliceExt<'a, T> {
+    fn display(&'a self) -> DisplaySlice<'a, T> {
+        P::display(self)
+    }
+});
+
+impl_for_pointers!(['a, T: fmt::Display,] DisplayBTreeSetExt<'a, T> {
+    fn display(&'a self) -> DisplayBTreeSet<'a, T> {
+        P::display(self)
+    }
+});
+
+impl_for_pointers!([] DisplayUnixTimeStampExt {
+    fn display_unix_timestamp(&self) -> DisplayUnixTimeStamp {
+        P::display_unix_timestamp(self)
+    }
+});
+
+impl_for_pointers!([] DisplaySqlLiteralExt {
+    fn display_sql_literal(&self) -> DisplaySqlLiteral<'_> {
+        P::display_sql_literal(self)
+    }
+});
+
+impl_for_pointers!([] DisplaySqlIdentExt {
+    fn display_sql_ident(&self) -> DisplaySqlIdent<'_> {
+        P::display_sql_ident(self)
+    }
+});
+
+impl_for_pointers!([] DisplaySocketAddrsExt {
+    fn display_by_host(&self) -> DisplaySocketAddrs<'_> {
+        P::display_by_host(self)
+    }
+});
+
+#[cfg(feature = "std")]
+impl_for_pointers!([] crate::DisplayPathExt {
+    fn display_path(&self) -> crate::DisplayPath<'_> {
+        P::display_path(self)
+    }
+});
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeSet;
+    use alloc::string::String;
+    use alloc::string::ToString;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use core::time::Duration;
+
+    use super::*;
+
+    fn show<'a, T: fmt::Display + 'a>(v: &'a impl DisplaySliceExt<'a, T>) -> String {
+        v.display().to_string()
+    }
+
+    #[test]
+    fn test_slice_through_pointers() {
+        let v: Box<[i32]> = vec![1, 2, 3].into_boxed_slice();
+        assert_eq!(show(&v), "[1,2,3]");
+
+        let v: Rc<[i32]> = Rc::from(vec![1, 2, 3]);
+        assert_eq!(show(&v), "[1,2,3]");
+
+        let v: Arc<[i32]> = Arc::from(vec![1, 2, 3]);
+        assert_eq!(show(&v), "[1,2,3]");
+
+        let v: Cow<'_, [i32]> = Cow::Borrowed(&[1, 2, 3]);
+        assert_eq!(show(&v), "[1,2,3]");
+
+        let v: Cow<'_, [i32]> = Cow::Owned(vec![1, 2, 3]);
+        assert_eq!(v.display_n(2).to_string(), "[1,..,3]");
+    }
+
+    #[test]
+    fn test_option_result_through_pointers() {
+        assert_eq!(Box::new(Some(1)).display().to_string(), "1");
+        assert_eq!(Rc::new(None::<i32>).display().to_string(), "None");
+        assert_eq!(Arc::new(Some("a")).display_debug().to_string(), "\"a\"");
+
+        let r: Box<Result<i32, &str>> = Box::new(Err("e"));
+        assert_eq!(r.display().to_string(), "Err(e)");
+    }
+
+    #[test]
+    fn test_set_through_pointers() {
+        let s = Arc::new([1, 2].into_iter().collect::<BTreeSet<_>>());
+        assert_eq!(s.display().to_string(), "[1,2]");
+    }
+
+    #[test]
+    fn test_value_traits_through_pointers() {
+        let d = Box::new(Duration::from_secs(0));
+        assert_eq!(
+            d.display_unix_timestamp().to_string(),
+            Duration::from_secs(0).display_unix_timestamp().to_string()
+        );
+
+        let s: Cow<'_, str> = Cow::Borrowed("it's");
+        assert_eq!(s.display_sql_literal().to_string(), "'it''s'");
+
+        let s: Rc<str> = Rc::from("a b");
+        assert_eq!(s.display_sql_ident().to_string(), "\"a b\"");
+
+        let addrs: Vec<core::net::SocketAddr> = vec!["10.0.0.1:1".parse().unwrap()];
+        let addrs: Arc<[core::net::SocketAddr]> = Arc::from(addrs);
+        assert_eq!(addrs.display_by_host().to_string(), "10.0.0.1:1");
+    }
+}
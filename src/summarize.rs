@@ -0,0 +1,320 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::string::String;
+use core::fmt;
+use core::fmt::Write;
+
+use crate::display_option::DisplayOption;
+use crate::display_result::DisplayResult;
+use crate::display_slice::DisplaySlice;
+use crate::display_unix_epoch::DisplayUnixTimeStamp;
+use crate::DisplayBTreeSet;
+use crate::DisplayIntoIter;
+use crate::Unstyled;
+
+/// The result of [`Summarize::summarize`]: a rendering of a value in at most a given number of
+/// chars.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use]
+pub struct Summary {
+    text: String,
+    truncated: bool,
+}
+
+impl Summary {
+    /// The marker appended to a rendering that is cut at the budget.
+    pub const MARKER: char = '…';
+
+    /// Render `d` and cut it to at most `budget` chars, ending with [`Summary::MARKER`] if cut.
+    ///
+    /// `d` stops being formatted once the output exceeds the budget.
+    pub fn of(d: impl fmt::Display, budget: usize) -> Self {
+        let mut w = CharsWriter {
+            text: String::new(),
+            remaining: budget.saturating_add(1),
+        };
+        // The width of escape codes must not count: render without styling.
+        let res = write!(w, "{}", Unstyled::new(d));
+
+        if w.remaining > 0 {
+            res.expect("a Display implementation returned an error unexpectedly");
+            return Self {
+                text: w.text,
+                truncated: false,
+            };
+        }
+
+        // One char more than the budget is written: cut it with the marker in place.
+        let mut text = w.text;
+        let keep = text
+            .char_indices()
+            .nth(budget.saturating_sub(1))
+            .map_or(text.len(), |(i, _)| i);
+        text.truncate(keep);
+        if budget > 0 {
+            text.push(Self::MARKER);
+        }
+
+        Self {
+            text,
+            truncated: true,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Whether some content is omitted, either by eliding elements or by cutting the text.
+    pub const fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    pub fn into_string(self) -> String {
+        self.text
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// A `fmt::Write` that keeps at most `remaining` chars, then returns `fmt::Error` so that the
+/// `Display` being written stops early.
+struct CharsWriter {
+    text: String,
+    remaining: usize,
+}
+
+impl Write for CharsWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match s.char_indices().nth(self.remaining) {
+            None => {
+                self.remaining -= s.chars().count();
+                self.text.push_str(s);
+                Ok(())
+            }
+            Some((end, _)) => {
+                self.text.push_str(&s[..end]);
+                self.remaining = 0;
+                Err(fmt::Error)
+            }
+        }
+    }
+}
+
+/// Describe a value in at most `budget` chars.
+///
+/// Collections elide elements, as their `Display` does, until the output fits; only if that is
+/// not enough the text is cut. Other values are cut at the budget.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplaySliceExt;
+/// use display_more::Summarize;
+///
+/// let a = (1..=100).collect::<Vec<_>>();
+/// assert_eq!(a.display().summarize(12).to_string(), "[1,2,..,100]");
+/// assert_eq!(a.display().summarize(4).to_string(), "[..]");
+/// ```
+pub trait Summarize {
+    fn summarize(&self, budget: usize) -> Summary;
+}
+
+impl<'a, T, S> Summarize for DisplayIntoIter<'a, T, S>
+where
+    T: fmt::Display + 'a,
+    S: Clone + IntoIterator<Item = &'a T>,
+    S::IntoIter: DoubleEndedIterator + ExactSizeIterator,
+{
+    fn summarize(&self, budget: usize) -> Summary {
+        // Read the config once for all of the attempts.
+        let this = DisplayIntoIter::with_options(self.items.clone(), self.options.resolved());
+        let len = self.items.clone().into_iter().len();
+        let top = this.limit().min(len);
+
+        let fit = |n: usize| {
+            let s = Summary::of(this.clone_with_limit(n), budget);
+            (!s.truncated).then_some(s.text)
+        };
+
+        // Try the configured limit first.
+        if let Some(text) = fit(top) {
+            return Summary {
+                text,
+                truncated: top < len,
+            };
+        }
+
+        // With fewer elements the output is truncated, and it grows with the number of elements
+        // displayed: binary search the most that fit.
+        let (mut lo, mut hi) = (0, top);
+        let mut best = None;
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match fit(mid) {
+                Some(text) => {
+                    best = Some(text);
+                    lo = mid + 1;
+                }
+                None => hi = mid,
+            }
+        }
+
+        match best {
+            Some(text) => Summary {
+                text,
+                truncated: true,
+            },
+            None => Summary::of(this.clone_with_limit(0), budget),
+        }
+    }
+}
+
+impl<T: fmt::Display> Summarize for DisplaySlice<'_, T> {
+    fn summarize(&self, budget: usize) -> Summary {
+        self.inner.summarize(budget)
+    }
+}
+
+impl<T: fmt::Display> Summarize for DisplayBTreeSet<'_, T> {
+    fn summarize(&self, budget: usize) -> Summary {
+        self.inner.summarize(budget)
+    }
+}
+
+impl<T> Summarize for DisplayOption<'_, T> {
+    fn summarize(&self, budget: usize) -> Summary {
+        Summary::of(self, budget)
+    }
+}
+
+impl<T: fmt::Display, E: fmt::Display> Summarize for DisplayResult<'_, T, E> {
+    fn summarize(&self, budget: usize) -> Summary {
+        Summary::of(self, budget)
+    }
+}
+
+impl Summarize for DisplayUnixTimeStamp {
+    fn summarize(&self, budget: usize) -> Summary {
+        Summary::of(self, budget)
+    }
+}
+
+impl Summarize for str {
+    fn summarize(&self, budget: usize) -> Summary {
+        Summary::of(self, budget)
+    }
+}
+
+impl Summarize for String {
+    fn summarize(&self, budget: usize) -> Summary {
+        Summary::of(self, budget)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::DisplayBTreeSetExt;
+    use crate::DisplayOptionExt;
+    use crate::DisplayResultExt;
+    use crate::DisplaySliceExt;
+    use crate::DisplayUnixTimeStampExt;
+
+    #[test]
+    fn test_summary_of() {
+        let s = Summary::of("hello", 10);
+        assert_eq!("hello", s.as_str());
+        assert!(!s.is_truncated());
+
+        let s = Summary::of("hello", 4);
+        assert_eq!("hel…", s.as_str());
+        assert!(s.is_truncated());
+
+        assert_eq!("…", Summary::of("hello", 1).as_str());
+        assert_eq!("", Summary::of("hello", 0).as_str());
+        assert_eq!("éé…", Summary::of("ééééé", 3).as_str());
+        assert_eq!("ééé", Summary::of("ééé", 3).as_str());
+    }
+
+    /// Counts the elements formatted, to check that formatting stops at the budget.
+    struct Counted<'a>(&'a core::cell::Cell<usize>);
+
+    impl fmt::Display for Counted<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.set(self.0.get() + 1);
+            f.write_str("x")
+        }
+    }
+
+    #[test]
+    fn test_summary_of_stops_at_budget() {
+        let n = core::cell::Cell::new(0);
+        let items = (0..1000).map(|_| Counted(&n)).collect::<Vec<_>>();
+
+        let s = Summary::of(items.display_n(1000).sep(""), 10);
+        assert_eq!("[xxxxxxxx…", s.as_str());
+        assert!(n.get() < 20);
+    }
+
+    #[test]
+    fn test_summarize_slice() {
+        let a = (1..=10).collect::<Vec<_>>();
+
+        let s = a.display().summarize(100);
+        assert_eq!("[1,2,3,4,..,10]", s.as_str());
+        assert!(s.is_truncated());
+
+        assert_eq!("[1,2,3,..,10]", a.display().summarize(13).as_str());
+        assert_eq!("[..,10]", a.display().summarize(7).as_str());
+        assert_eq!("[..]", a.display().summarize(4).as_str());
+        assert_eq!("[.…", a.display().summarize(3).as_str());
+
+        let b = [1, 2, 3];
+        let s = b.display().summarize(7);
+        assert_eq!("[1,2,3]", s.as_str());
+        assert!(!s.is_truncated());
+        assert_eq!("[1,..,3]", b.display_n(2).summarize(100).as_str());
+    }
+
+    #[test]
+    fn test_summarize_btreeset() {
+        let set = (1..=10).collect::<BTreeSet<_>>();
+        assert_eq!("[1,2,..,10]", set.display().summarize(11).as_str());
+    }
+
+    #[test]
+    fn test_summarize_others() {
+        let o = Some("a long value".to_string());
+        assert_eq!("a lon…", o.display().summarize(6).as_str());
+
+        let r = Result::<u64, String>::Err("boom".to_string());
+        assert_eq!("Err(boom)", r.display().summarize(9).as_str());
+        assert_eq!("Err(…", r.display().summarize(5).as_str());
+
+        let t = Duration::from_millis(0).display_unix_timestamp_short();
+        assert_eq!("1970-01-01…", t.summarize(11).as_str());
+
+        assert_eq!("ab…", "abcd".summarize(3).as_str());
+        assert_eq!("abcd", "abcd".to_string().summarize(4).as_str());
+    }
+}
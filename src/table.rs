@@ -0,0 +1,497 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Render rows of cells as an aligned text table, such as the output of a `SHOW` command.
+//!
+//! # Example
+//!
+//! ```rust
+//! use display_more::table::Align;
+//! use display_more::table::Border;
+//! use display_more::table::Table;
+//!
+//! let t = Table::new()
+//!     .header(["name", "rows"])
+//!     .row(["t1", "10"])
+//!     .row(["t2", "2000"])
+//!     .footer(["total", "2010"])
+//!     .align(1, Align::Right)
+//!     .border(Border::Ascii);
+//!
+//! assert_eq!(
+//!     t.to_string(),
+//!     [
+//!         "+-------+------+",
+//!         "| name  | rows |",
+//!         "+-------+------+",
+//!         "| t1    |   10 |",
+//!         "| t2    | 2000 |",
+//!         "+-------+------+",
+//!         "| total | 2010 |",
+//!         "+-------+------+",
+//!     ]
+//!     .join("\n")
+//! );
+//! ```
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::Summary;
+
+/// Horizontal alignment of the cells in a column.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+    /// Centered; the extra space, if any, goes to the right.
+    Center,
+}
+
+/// The frame drawn around and between cells.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Border {
+    /// No frame: columns are separated by two spaces and sections by a line of `-`.
+    #[default]
+    None,
+    /// Frame drawn with `+`, `-` and `|`.
+    Ascii,
+    /// Frame drawn with box-drawing chars such as `┌`, `─` and `│`.
+    Unicode,
+}
+
+/// The chars of a frame: `[left, middle, right]` joints of a rule, and the lines.
+struct Frame {
+    top: [char; 3],
+    mid: [char; 3],
+    bottom: [char; 3],
+    horizontal: char,
+    vertical: char,
+}
+
+impl Border {
+    fn frame(&self) -> Option<Frame> {
+        match self {
+            Border::None => None,
+            Border::Ascii => Some(Frame {
+                top: ['+', '+', '+'],
+                mid: ['+', '+', '+'],
+                bottom: ['+', '+', '+'],
+                horizontal: '-',
+                vertical: '|',
+            }),
+            Border::Unicode => Some(Frame {
+                top: ['┌', '┬', '┐'],
+                mid: ['├', '┼', '┤'],
+                bottom: ['└', '┴', '┘'],
+                horizontal: '─',
+                vertical: '│',
+            }),
+        }
+    }
+}
+
+/// A text table with an optional header and footer.
+///
+/// Cells are rendered when added. Widths are counted in chars. Rows with fewer cells than
+/// others are padded with empty cells.
+#[derive(Clone, Default)]
+#[must_use]
+pub struct Table {
+    header: Option<Vec<String>>,
+    rows: Vec<Vec<String>>,
+    footer: Vec<Vec<String>>,
+    aligns: Vec<Align>,
+    max_width: Option<usize>,
+    fit_width: Option<usize>,
+    border: Border,
+}
+
+impl Table {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the header row.
+    pub fn header<I>(self, cells: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: fmt::Display,
+    {
+        Self {
+            header: Some(render(cells)),
+            ..self
+        }
+    }
+
+    /// Append a body row.
+    pub fn row<I>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: fmt::Display,
+    {
+        self.push_row(cells);
+        self
+    }
+
+    /// Append a body row in place, e.g., in a loop.
+    pub fn push_row<I>(&mut self, cells: I)
+    where
+        I: IntoIterator,
+        I::Item: fmt::Display,
+    {
+        self.rows.push(render(cells));
+    }
+
+    /// Append a footer row, displayed after the body and separated from it.
+    pub fn footer<I>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: fmt::Display,
+    {
+        self.footer.push(render(cells));
+        self
+    }
+
+    /// Set the alignment of column `col`, starting from 0. By default columns are left aligned.
+    pub fn align(mut self, col: usize, align: Align) -> Self {
+        if self.aligns.len() <= col {
+            self.aligns.resize(col + 1, Align::Left);
+        }
+        self.aligns[col] = align;
+        self
+    }
+
+    /// Set the max width of every column; longer cells are cut and end with
+    /// [`Summary::MARKER`]. By default the width is unlimited.
+    pub fn max_width(self, max_width: usize) -> Self {
+        Self {
+            max_width: Some(max_width),
+            ..self
+        }
+    }
+
+    /// Shrink the widest columns so that every line fits in `width` chars, e.g.,
+    /// [`terminal_width()`](crate::terminal_width). A column is at least 1 char wide.
+    pub fn fit_width(self, width: usize) -> Self {
+        Self {
+            fit_width: Some(width),
+            ..self
+        }
+    }
+
+    /// Set the frame style. By default it is [`Border::None`].
+    pub fn border(self, border: Border) -> Self {
+        Self { border, ..self }
+    }
+
+    fn all_rows(&self) -> impl Iterator<Item = &Vec<String>> {
+        self.header.iter().chain(&self.rows).chain(&self.footer)
+    }
+
+    /// Return the cell at `col`, cut to at most `width` chars.
+    fn cell(row: &[String], col: usize, width: usize) -> Cow<'_, str> {
+        let cell = row.get(col).map(|s| s.as_str()).unwrap_or_default();
+        if cell.chars().count() > width {
+            Summary::of(cell, width).into_string().into()
+        } else {
+            cell.into()
+        }
+    }
+
+    /// Return the width of every column.
+    fn widths(&self) -> Vec<usize> {
+        let n_cols = self.all_rows().map(|r| r.len()).max().unwrap_or_default();
+        let max_width = self.max_width.unwrap_or(usize::MAX);
+
+        let mut widths = (0..n_cols)
+            .map(|col| {
+                self.all_rows()
+                    .map(|r| Self::cell(r, col, max_width).chars().count())
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+
+        let Some(fit_width) = self.fit_width else {
+            return widths;
+        };
+
+        let decoration = match self.border {
+            Border::None => 2 * n_cols.saturating_sub(1),
+            Border::Ascii | Border::Unicode => 3 * n_cols + 1,
+        };
+
+        while widths.iter().sum::<usize>() + decoration > fit_width {
+            let widest = widths.iter_mut().max_by_key(|w| **w);
+            match widest {
+                Some(w) if *w > 1 => *w -= 1,
+                _ => break,
+            }
+        }
+
+        widths
+    }
+
+    fn write_row(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        row: &[String],
+        widths: &[usize],
+        frame: &Option<Frame>,
+    ) -> fmt::Result {
+        let mut line = String::new();
+
+        for (col, width) in widths.iter().enumerate() {
+            let cell = Self::cell(row, col, *width);
+            let pad = width - cell.chars().count();
+            let (left, right) = match self.aligns.get(col).copied().unwrap_or_default() {
+                Align::Left => (0, pad),
+                Align::Right => (pad, 0),
+                Align::Center => (pad / 2, pad - pad / 2),
+            };
+
+            match frame {
+                Some(frame) => {
+                    line.push(frame.vertical);
+                    line.push(' ');
+                }
+                None if col > 0 => line.push_str("  "),
+                None => {}
+            }
+
+            line.extend(core::iter::repeat_n(' ', left));
+            line.push_str(&cell);
+            line.extend(core::iter::repeat_n(' ', right));
+
+            if frame.is_some() {
+                line.push(' ');
+            }
+        }
+
+        match frame {
+            Some(frame) => line.push(frame.vertical),
+            None => line.truncate(line.trim_end().len()),
+        }
+
+        f.write_str(&line)
+    }
+
+    fn write_rule(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        widths: &[usize],
+        frame: &Option<Frame>,
+        joints: impl Fn(&Frame) -> [char; 3],
+    ) -> fmt::Result {
+        let mut line = String::new();
+
+        match frame {
+            Some(frame) => {
+                let [left, middle, right] = joints(frame);
+                line.push(left);
+                for (col, width) in widths.iter().enumerate() {
+                    if col > 0 {
+                        line.push(middle);
+                    }
+                    line.extend(core::iter::repeat_n(frame.horizontal, width + 2));
+                }
+                line.push(right);
+            }
+            None => {
+                for (col, width) in widths.iter().enumerate() {
+                    if col > 0 {
+                        line.push_str("  ");
+                    }
+                    line.extend(core::iter::repeat_n('-', *width));
+                }
+            }
+        }
+
+        f.write_str(&line)
+    }
+}
+
+fn render<I>(cells: I) -> Vec<String>
+where
+    I: IntoIterator,
+    I::Item: fmt::Display,
+{
+    cells.into_iter().map(|c| c.to_string()).collect()
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let widths = self.widths();
+        let frame = self.border.frame();
+
+        // Collect the lines to write, `None` stands for a rule.
+        let mut lines: Vec<Option<&Vec<String>>> = Vec::new();
+        if let Some(header) = &self.header {
+            lines.push(Some(header));
+            lines.push(None);
+        }
+        lines.extend(self.rows.iter().map(Some));
+        if !self.footer.is_empty() {
+            lines.push(None);
+            lines.extend(self.footer.iter().map(Some));
+        }
+
+        if frame.is_some() {
+            self.write_rule(f, &widths, &frame, |fr| fr.top)?;
+            writeln!(f)?;
+        }
+
+        for (i, line) in lines.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            match line {
+                Some(row) => self.write_row(f, row, &widths, &frame)?,
+                None => self.write_rule(f, &widths, &frame, |fr| fr.mid)?,
+            }
+        }
+
+        if frame.is_some() {
+            if !lines.is_empty() {
+                writeln!(f)?;
+            }
+            self.write_rule(f, &widths, &frame, |fr| fr.bottom)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(t: &Table) -> Vec<String> {
+        t.to_string().lines().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_table_plain() {
+        let t = Table::new()
+            .header(["id", "name"])
+            .row([1.to_string(), "alice".to_string()])
+            .row([22.to_string(), "bob".to_string()]);
+
+        assert_eq!(lines(&t), vec![
+            "id  name",
+            "--  -----",
+            "1   alice",
+            "22  bob",
+        ]);
+
+        // No header
+        let t = Table::new().row(["a", "b"]).row(["ccc"]);
+        assert_eq!(lines(&t), vec!["a    b", "ccc"]);
+
+        assert_eq!("", Table::new().to_string());
+    }
+
+    #[test]
+    fn test_table_align() {
+        let t = Table::new()
+            .header(["a", "b", "c"])
+            .row(["xxxxx", "xxxxx", "xxxxx"])
+            .row(["1", "1", "12"])
+            .align(1, Align::Right)
+            .align(2, Align::Center);
+
+        assert_eq!(lines(&t), vec![
+            "a          b    c",
+            "-----  -----  -----",
+            "xxxxx  xxxxx  xxxxx",
+            "1          1   12",
+        ]);
+    }
+
+    #[test]
+    fn test_table_max_width() {
+        let t = Table::new()
+            .header(["key", "value"])
+            .row(["k", "a very long value"])
+            .max_width(6)
+            .border(Border::Ascii);
+
+        assert_eq!(lines(&t), vec![
+            "+-----+--------+",
+            "| key | value  |",
+            "+-----+--------+",
+            "| k   | a ver… |",
+            "+-----+--------+",
+        ]);
+    }
+
+    #[test]
+    fn test_table_fit_width() {
+        let t = Table::new()
+            .header(["id", "description", "comment"])
+            .row(["1", "a long description", "short"])
+            .fit_width(24);
+
+        assert_eq!(lines(&t), vec![
+            "id  description  comment",
+            "--  -----------  -------",
+            "1   a long des…  short",
+        ]);
+
+        let t = t.border(Border::Ascii);
+        assert!(lines(&t).iter().all(|l| l.chars().count() <= 24));
+        assert_eq!("| 1  | a lon… | short  |", lines(&t)[3]);
+
+        // Too narrow: every column is 1 char wide
+        let t = Table::new().row(["abc", "def"]).fit_width(1);
+        assert_eq!("…  …", t.to_string());
+    }
+
+    #[test]
+    fn test_table_unicode() {
+        let mut t = Table::new().header(["n", "sq"]).border(Border::Unicode);
+        for i in [2, 10] {
+            t.push_row([i, i * i]);
+        }
+        let t = t.footer(["Σ", "104"]).align(1, Align::Right);
+
+        assert_eq!(lines(&t), vec![
+            "┌────┬─────┐",
+            "│ n  │  sq │",
+            "├────┼─────┤",
+            "│ 2  │   4 │",
+            "│ 10 │ 100 │",
+            "├────┼─────┤",
+            "│ Σ  │ 104 │",
+            "└────┴─────┘",
+        ]);
+    }
+
+    #[test]
+    fn test_table_footer_without_header() {
+        let t = Table::new().row(["a", "1"]).footer(["sum", "1"]);
+        assert_eq!(lines(&t), vec!["a    1", "---  -", "sum  1"]);
+    }
+}
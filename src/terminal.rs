@@ -0,0 +1,48 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::env;
+
+/// The width assumed when the terminal width is unknown.
+pub const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+/// Return the width of the terminal in chars, to pass to the `fit_width()` of a renderer.
+///
+/// It is the `COLUMNS` environment variable if set; otherwise, with the `terminal-size` feature,
+/// the width of the terminal attached to stdout; otherwise [`DEFAULT_TERMINAL_WIDTH`].
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::terminal_width;
+/// use display_more::DisplaySliceExt;
+///
+/// let a = (0..1000).collect::<Vec<_>>();
+/// let s = a.display_n(100).fit_width(terminal_width()).to_string();
+/// assert!(s.chars().count() <= terminal_width());
+/// ```
+pub fn terminal_width() -> usize {
+    if let Some(w) = env::var("COLUMNS").ok().and_then(|s| s.trim().parse().ok()) {
+        if w > 0 {
+            return w;
+        }
+    }
+
+    #[cfg(feature = "terminal-size")]
+    if let Some((terminal_size::Width(w), _)) = terminal_size::terminal_size() {
+        return w as usize;
+    }
+
+    DEFAULT_TERMINAL_WIDTH
+}
@@ -0,0 +1,184 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test assertions that report a line diff of the displayed values on failure.
+
+use alloc::string::ToString;
+use core::fmt;
+#[cfg(feature = "snapshot")]
+use std::path::Path;
+
+use crate::diff::diff;
+
+/// Assert that a value displays as the expected string.
+///
+/// On failure the panic message contains a line diff of the expected and the actual output, with
+/// `-` for expected lines and `+` for actual lines. An optional format string and arguments are
+/// added to the message, as with `assert_eq!`.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::assert_display_eq;
+/// use display_more::DisplaySliceExt;
+///
+/// assert_display_eq!([1, 2, 3].display(), "[1,2,3]");
+/// assert_display_eq!(1 + 1, "2", "adding {}", "ones");
+/// ```
+#[macro_export]
+macro_rules! assert_display_eq {
+    ($value:expr, $expected:expr $(,)?) => {
+        $crate::__private::assert_display_eq(&$value, &$expected, ::core::option::Option::None)
+    };
+    ($value:expr, $expected:expr, $($arg:tt)+) => {
+        $crate::__private::assert_display_eq(
+            &$value,
+            &$expected,
+            ::core::option::Option::Some(::core::format_args!($($arg)+)),
+        )
+    };
+}
+
+/// Assert that a value displays as the snapshot stored in `snapshots/<name>.snap` under the
+/// directory of the calling crate's `Cargo.toml`. Requires the `snapshot` feature.
+///
+/// A missing snapshot is created from the actual output. Set the environment variable
+/// `DISPLAY_MORE_UPDATE_SNAPSHOTS=1` to overwrite mismatching snapshots instead of failing. On
+/// failure the panic message contains a line diff of the snapshot and the actual output.
+///
+/// ```rust,ignore
+/// use display_more::assert_display_snapshot;
+///
+/// assert_display_snapshot!("cluster_status", status.display());
+/// ```
+#[cfg(feature = "snapshot")]
+#[macro_export]
+macro_rules! assert_display_snapshot {
+    ($name:expr, $value:expr $(,)?) => {
+        $crate::__private::assert_display_snapshot(
+            &::std::path::Path::new(::core::env!("CARGO_MANIFEST_DIR"))
+                .join("snapshots")
+                .join(::std::format!("{}.snap", $name)),
+            &$value,
+        )
+    };
+}
+
+#[track_caller]
+pub fn assert_display_eq(
+    value: &dyn fmt::Display,
+    expected: &dyn fmt::Display,
+    msg: Option<fmt::Arguments<'_>>,
+) {
+    let (actual, expected) = (value.to_string(), expected.to_string());
+    if actual == expected {
+        return;
+    }
+
+    let diff = diff(&expected, &actual);
+    match msg {
+        None => panic!("assertion `display == expected` failed (-expected +actual)\n{diff}"),
+        Some(msg) => {
+            panic!("assertion `display == expected` failed: {msg} (-expected +actual)\n{diff}")
+        }
+    }
+}
+
+/// The environment variable that makes [`assert_display_snapshot`] overwrite snapshots.
+#[cfg(feature = "snapshot")]
+const UPDATE_SNAPSHOTS: &str = "DISPLAY_MORE_UPDATE_SNAPSHOTS";
+
+#[cfg(feature = "snapshot")]
+#[track_caller]
+pub fn assert_display_snapshot(path: &Path, value: &dyn fmt::Display) {
+    let actual = value.to_string();
+    let update = std::env::var(UPDATE_SNAPSHOTS).is_ok_and(|v| v == "1");
+
+    let expected = match std::fs::read_to_string(path) {
+        Ok(expected) => expected,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            write_snapshot(path, &actual);
+            return;
+        }
+        Err(e) => panic!("failed to read snapshot {}: {e}", path.display()),
+    };
+
+    if actual == expected {
+        return;
+    }
+
+    if update {
+        write_snapshot(path, &actual);
+        return;
+    }
+
+    panic!(
+        "snapshot {} does not match (-snapshot +actual); set {UPDATE_SNAPSHOTS}=1 to update it\n{}",
+        path.display(),
+        diff(&expected, &actual)
+    );
+}
+
+#[cfg(feature = "snapshot")]
+#[track_caller]
+fn write_snapshot(path: &Path, actual: &str) {
+    let res = match path.parent() {
+        Some(dir) => std::fs::create_dir_all(dir),
+        None => Ok(()),
+    };
+    if let Err(e) = res.and_then(|_| std::fs::write(path, actual)) {
+        panic!("failed to write snapshot {}: {e}", path.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DisplayOptionExt;
+
+    #[test]
+    fn test_assert_display_eq() {
+        assert_display_eq!(Some(1).display(), "1");
+        assert_display_eq!(None::<u8>.display(), "None", "with {}", "message");
+    }
+
+    #[test]
+    #[should_panic(expected = "(-expected +actual)\n a\n-b\n+c")]
+    fn test_assert_display_eq_failure() {
+        assert_display_eq!("a\nc", "a\nb");
+    }
+
+    #[test]
+    #[should_panic(expected = "failed: round 3 (-expected +actual)\n-1\n+2")]
+    fn test_assert_display_eq_failure_message() {
+        assert_display_eq!(2, 1, "round {}", 3);
+    }
+
+    #[cfg(feature = "snapshot")]
+    #[test]
+    fn test_assert_display_snapshot() {
+        let dir = std::env::temp_dir().join(format!("display-more-snap-{}", std::process::id()));
+        let path = dir.join("a.snap");
+
+        // A missing snapshot is created.
+        super::assert_display_snapshot(&path, &"x\ny");
+        assert_eq!("x\ny", std::fs::read_to_string(&path).unwrap());
+        super::assert_display_snapshot(&path, &"x\ny");
+
+        let res = std::panic::catch_unwind(|| super::assert_display_snapshot(&path, &"x\nz"));
+        let msg = res.unwrap_err().downcast::<String>().unwrap();
+        assert!(msg.ends_with("\n x\n-y\n+z"), "{msg}");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
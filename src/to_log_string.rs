@@ -0,0 +1,117 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::fmt::Write;
+use std::string::String;
+use std::string::ToString;
+use std::sync::Arc;
+
+/// The buffer is released after use if it has grown larger than this, so that one huge value
+/// does not keep the memory for the life of the thread.
+const MAX_RETAINED_CAPACITY: usize = 64 * 1024;
+
+thread_local! {
+    static BUF: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Format `d` into the thread-local buffer and convert the result with `f`.
+///
+/// Falls back to a fresh `String` if the buffer is in use, i.e., when called from inside the
+/// `Display` of the value being formatted.
+fn with_buffer<R>(d: &(impl fmt::Display + ?Sized), f: fn(&str) -> R) -> R {
+    let res = BUF.try_with(|buf| {
+        let mut buf = buf.try_borrow_mut().ok()?;
+
+        buf.clear();
+        let res = write!(buf, "{}", d).map(|_| f(&buf));
+
+        if buf.capacity() > MAX_RETAINED_CAPACITY {
+            *buf = String::new();
+        }
+        Some(res.expect("a Display implementation returned an error unexpectedly"))
+    });
+
+    match res {
+        Ok(Some(r)) => r,
+        _ => f(&d.to_string()),
+    }
+}
+
+/// Format a value for logging through a reusable thread-local buffer.
+///
+/// `to_string()` grows its `String` as the output is written, reallocating several times for a
+/// long output. These methods write into a buffer that is kept by the thread and copy the
+/// result out with a single allocation of the exact size.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplaySliceExt;
+/// use display_more::ToLogStringExt;
+///
+/// let v = [1, 2, 3];
+/// assert_eq!(v.display().to_log_string(), "[1,2,3]");
+/// assert_eq!(&*v.display().to_log_arc(), "[1,2,3]");
+/// ```
+pub trait ToLogStringExt: fmt::Display {
+    /// Format into the thread-local buffer and return an owned copy.
+    fn to_log_string(&self) -> String {
+        with_buffer(self, |s| String::from(s))
+    }
+
+    /// Format into the thread-local buffer and return a shared copy.
+    fn to_log_arc(&self) -> Arc<str> {
+        with_buffer(self, |s| Arc::from(s))
+    }
+}
+
+impl<T: fmt::Display + ?Sized> ToLogStringExt for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DisplaySliceExt;
+
+    #[test]
+    fn test_to_log_string() {
+        assert_eq!("x", "x".to_log_string());
+        assert_eq!("[1,2]", [1, 2].display().to_log_string());
+        assert_eq!("", "".to_log_string());
+
+        let s = "a".repeat(MAX_RETAINED_CAPACITY + 1);
+        assert_eq!(s, s.to_log_string());
+        assert_eq!(0, BUF.with(|b| b.borrow().capacity()));
+    }
+
+    #[test]
+    fn test_to_log_arc() {
+        let a: Arc<str> = 12.to_log_arc();
+        assert_eq!("12", &*a);
+    }
+
+    #[test]
+    fn test_to_log_string_reentrant() {
+        struct Nested;
+
+        impl fmt::Display for Nested {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "<{}>", "inner".to_log_string())
+            }
+        }
+
+        assert_eq!("<inner>", Nested.to_log_string());
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+
+use tracing::field::DisplayValue;
+
+use crate::Unstyled;
+
+/// Use a `Display` as a structured `tracing` field without the `%` sigil.
+///
+/// `tracing::Value` is sealed, thus this wraps the value in `tracing`'s own `DisplayValue`. The
+/// value is formatted only if a subscriber records the field, and without an intermediate
+/// `String`. It is formatted without styling, see [`Unstyled`].
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplaySliceExt;
+/// use display_more::TracingFieldExt;
+///
+/// let nodes = vec![1, 2, 3];
+/// tracing::info!(peers = nodes.display().as_field(), "membership changed");
+/// ```
+pub trait TracingFieldExt: fmt::Display {
+    fn as_field(&self) -> DisplayValue<&Unstyled<Self>> {
+        tracing::field::display(Unstyled::from_ref(self))
+    }
+}
+
+impl<T: fmt::Display + ?Sized> TracingFieldExt for T {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    use tracing::field::Field;
+    use tracing::field::Visit;
+    use tracing::span;
+    use tracing::Event;
+    use tracing::Metadata;
+    use tracing::Subscriber;
+
+    use super::*;
+    use crate::DisplayOptionExt;
+    use crate::DisplaySliceExt;
+
+    /// Collect the fields of every event as `name=value`.
+    #[derive(Clone, Default)]
+    struct Collect(Arc<Mutex<Vec<String>>>);
+
+    impl Visit for Collect {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("{}={:?}", field.name(), value));
+        }
+    }
+
+    impl Subscriber for Collect {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            event.record(&mut self.clone());
+        }
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    #[test]
+    fn test_as_field() {
+        let c = Collect::default();
+        let a = [1, 2, 3, 4, 5, 6, 7];
+
+        tracing::subscriber::with_default(c.clone(), || {
+            tracing::info!(
+                peers = a.display().as_field(),
+                leader = None::<u64>.display().as_field(),
+                "x"
+            );
+        });
+
+        assert_eq!(*c.0.lock().unwrap(), vec![
+            "message=x",
+            "peers=[1,2,3,4,..,7]",
+            "leader=None",
+        ]);
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+
+/// Display the inner value without ANSI styling, whatever the [color
+/// mode](crate::color::ColorMode) is.
+///
+/// The serializers and the logging integrations format through it, so that machine-readable
+/// output never contains escape codes. Without the `color` feature it is a no-op.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplayOptionExt;
+/// use display_more::Unstyled;
+///
+/// assert_eq!(Unstyled::new(None::<u64>.display()).to_string(), "None");
+/// ```
+#[repr(transparent)]
+#[must_use]
+pub struct Unstyled<D: ?Sized>(D);
+
+impl<D> Unstyled<D> {
+    pub const fn new(inner: D) -> Self {
+        Self(inner)
+    }
+}
+
+impl<D: ?Sized> Unstyled<D> {
+    /// View a reference as an `Unstyled`, for APIs that borrow the value to format it later.
+    pub fn from_ref(inner: &D) -> &Self {
+        // SAFETY: `Unstyled` is `repr(transparent)` over `D`.
+        unsafe { &*(inner as *const D as *const Self) }
+    }
+}
+
+impl<D: fmt::Display + ?Sized> fmt::Display for Unstyled<D> {
+    #[cfg(feature = "color")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::color::with_color_mode;
+        use crate::color::ColorMode;
+
+        with_color_mode(ColorMode::Never, || self.0.fmt(f))
+    }
+
+    #[cfg(not(feature = "color"))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<D: fmt::Display + ?Sized> fmt::Debug for Unstyled<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
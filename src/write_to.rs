@@ -0,0 +1,100 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+
+/// Stream the output of a value into a writer, without formatting into a `String` first.
+///
+/// # Example
+///
+/// ```rust
+/// use display_more::DisplaySliceExt;
+/// use display_more::WriteToExt;
+///
+/// let mut s = String::from("ids: ");
+/// [1, 2, 3].display().fmt_to(&mut s).unwrap();
+/// assert_eq!(s, "ids: [1,2,3]");
+///
+/// let mut file: Vec<u8> = Vec::new();
+/// [1, 2, 3].display().write_to(&mut file).unwrap();
+/// assert_eq!(file, b"[1,2,3]");
+/// ```
+pub trait WriteToExt: fmt::Display {
+    /// Write the output into a `fmt::Write`, such as a `String`.
+    fn fmt_to<W: fmt::Write + ?Sized>(&self, w: &mut W) -> fmt::Result {
+        write!(w, "{}", self)
+    }
+
+    /// Write the output into an `io::Write`, such as a file or a socket.
+    ///
+    /// The output is written in pieces as it is produced; wrap the writer in a `BufWriter` to
+    /// avoid a system call for each piece.
+    #[cfg(feature = "std")]
+    fn write_to<W: io::Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        write!(w, "{}", self)
+    }
+}
+
+impl<T: fmt::Display + ?Sized> WriteToExt for T {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use super::*;
+    use crate::DisplayOptionExt;
+
+    #[test]
+    fn test_fmt_to() {
+        let mut s = String::new();
+        Some(1).display().fmt_to(&mut s).unwrap();
+        "-".fmt_to(&mut s).unwrap();
+        None::<u8>.display().fmt_to(&mut s).unwrap();
+        assert_eq!("1-None", s);
+
+        let w: &mut dyn fmt::Write = &mut s;
+        "!".fmt_to(w).unwrap();
+        assert_eq!("1-None!", s);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_write_to() {
+        let mut buf = std::vec::Vec::new();
+        Some("a").display().write_to(&mut buf).unwrap();
+        42.write_to(&mut buf).unwrap();
+        assert_eq!(b"a42", buf.as_slice());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_write_to_error() {
+        struct Full;
+
+        impl io::Write for Full {
+            fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::WriteZero, "full"))
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let err = "x".write_to(&mut Full).unwrap_err();
+        assert_eq!(io::ErrorKind::WriteZero, err.kind());
+    }
+}
@@ -0,0 +1,108 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Check that formatting on the hot path does not allocate.
+
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::cell::Cell;
+use std::fmt;
+use std::fmt::Write;
+use std::time::Duration;
+
+use display_more::DisplayUnixTimeStampExt;
+
+/// Count the allocations made by the current thread, so that other tests running in parallel
+/// are not counted.
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// A writer into a fixed buffer on the stack.
+struct StackBuf {
+    buf: [u8; 128],
+    len: usize,
+}
+
+impl StackBuf {
+    fn new() -> Self {
+        Self {
+            buf: [0; 128],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.buf[..self.len]).unwrap()
+    }
+}
+
+impl Write for StackBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Return the number of allocations made by `f`.
+fn count_allocs(f: impl FnOnce()) -> usize {
+    let before = ALLOCS.with(|n| n.get());
+    f();
+    ALLOCS.with(|n| n.get()) - before
+}
+
+#[test]
+fn test_unix_timestamp_does_not_allocate() {
+    let d = Duration::from_millis(1723102819023);
+
+    // Initialize lazily created state, such as the global config, outside of the counting.
+    let mut buf = StackBuf::new();
+    write!(buf, "{}", d.display_unix_timestamp()).unwrap();
+
+    let mut buf = StackBuf::new();
+    let n = count_allocs(|| {
+        write!(buf, "{}", d.display_unix_timestamp()).unwrap();
+    });
+    assert_eq!(n, 0);
+    assert!(buf.as_str().contains("2024-08-08T07:40:19.023000"));
+
+    let mut buf = StackBuf::new();
+    let n = count_allocs(|| {
+        write!(buf, "{}", d.display_unix_timestamp_short()).unwrap();
+    });
+    assert_eq!(n, 0);
+    assert!(buf.as_str().contains("2024-08-08T07:40:19.023"));
+}
@@ -0,0 +1,266 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "derive")]
+
+use std::collections::BTreeSet;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use display_more::DisplayForward;
+use display_more::DisplayMore;
+
+#[derive(DisplayMore)]
+struct Node {
+    id: u64,
+    name: String,
+    leader: Option<u64>,
+    peers: Vec<u64>,
+}
+
+#[test]
+fn test_derive_named() {
+    let n = Node {
+        id: 1,
+        name: "n1".to_string(),
+        leader: None,
+        peers: vec![2, 3, 4, 5, 6, 7],
+    };
+    assert_eq!(
+        "Node{id=1, name=n1, leader=None, peers=[2,3,4,5,..,7]}",
+        n.to_string()
+    );
+}
+
+#[derive(DisplayMore)]
+struct Wrappers<'a> {
+    result: Result<u8, String>,
+    set: BTreeSet<u8>,
+    array: [u8; 2],
+    slice: &'a [u8],
+    opt_ref: &'a Option<u8>,
+    time: SystemTime,
+}
+
+#[test]
+fn test_derive_field_types() {
+    let opt = Some(9);
+    let w = Wrappers {
+        result: Err("bad".to_string()),
+        set: [3, 1, 2].into_iter().collect(),
+        array: [1, 2],
+        slice: &[7, 8],
+        opt_ref: &opt,
+        time: UNIX_EPOCH + Duration::from_millis(1723102819023),
+    };
+    assert_eq!(
+        "Wrappers{result=Err(bad), set=[1,2,3], array=[1,2], slice=[7,8], opt_ref=9, \
+         time=2024-08-08T07:40:19.023000Z+0000}",
+        w.to_string()
+    );
+}
+
+#[derive(DisplayMore)]
+struct Pair(u64, Option<String>);
+
+#[derive(DisplayMore)]
+struct Unit;
+
+#[test]
+fn test_derive_tuple_and_unit() {
+    assert_eq!("Pair(1, a)", Pair(1, Some("a".to_string())).to_string());
+    assert_eq!("Unit", Unit.to_string());
+}
+
+#[derive(DisplayMore)]
+struct Generic<T: std::fmt::Display> {
+    items: Vec<T>,
+}
+
+#[test]
+fn test_derive_generic() {
+    let g = Generic {
+        items: vec!["a", "b"],
+    };
+    assert_eq!("Generic{items=[a,b]}", g.to_string());
+}
+
+mod fmt_helpers {
+    use std::fmt;
+
+    pub fn hex(v: &u32, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#x}", v)
+    }
+}
+
+#[derive(DisplayMore)]
+struct Config {
+    user: String,
+    #[display(redact)]
+    #[allow(dead_code)]
+    password: String,
+    #[display(skip)]
+    #[allow(dead_code)]
+    cache: Vec<u8>,
+    #[display(with = "fmt_helpers::hex")]
+    flags: u32,
+    #[display(limit = 2)]
+    peers: Vec<u64>,
+    #[display(limit = 1)]
+    learners: BTreeSet<u64>,
+}
+
+#[test]
+fn test_derive_field_attributes() {
+    let c = Config {
+        user: "root".to_string(),
+        password: "secret".to_string(),
+        cache: vec![1, 2, 3],
+        flags: 255,
+        peers: vec![1, 2, 3, 4],
+        learners: [5, 6].into_iter().collect(),
+    };
+    assert_eq!(
+        "Config{user=root, password=<redacted>, flags=0xff, peers=[1,..,4], learners=[..,6]}",
+        c.to_string()
+    );
+}
+
+#[derive(DisplayMore)]
+#[allow(dead_code)]
+struct SkipFirst(#[display(skip)] u8, u8);
+
+#[test]
+fn test_derive_skip_first() {
+    assert_eq!("SkipFirst(2)", SkipFirst(1, 2).to_string());
+}
+
+#[derive(DisplayMore)]
+#[allow(dead_code)]
+enum Role {
+    Leader {
+        term: u64,
+        voters: Vec<u64>,
+    },
+    Follower(Option<u64>),
+    Learner,
+    #[display(name_only)]
+    Snapshot(Vec<u8>),
+    Candidate {
+        term: u64,
+        #[display(skip)]
+        votes: u64,
+    },
+    Pending(#[display(skip)] u64, #[display(redact)] String),
+}
+
+#[test]
+fn test_derive_enum() {
+    assert_eq!(
+        "Leader{term=3, voters=[1,2,3]}",
+        Role::Leader {
+            term: 3,
+            voters: vec![1, 2, 3]
+        }
+        .to_string()
+    );
+    assert_eq!("Follower(None)", Role::Follower(None).to_string());
+    assert_eq!("Follower(1)", Role::Follower(Some(1)).to_string());
+    assert_eq!("Learner", Role::Learner.to_string());
+    assert_eq!("Snapshot", Role::Snapshot(vec![0; 1024]).to_string());
+    assert_eq!(
+        "Candidate{term=2}",
+        Role::Candidate { term: 2, votes: 5 }.to_string()
+    );
+    assert_eq!(
+        "Pending(<redacted>)",
+        Role::Pending(1, "x".to_string()).to_string()
+    );
+}
+
+#[derive(DisplayMore)]
+#[display(name_only)]
+#[allow(dead_code)]
+enum Message {
+    Append(Vec<u64>),
+    Vote { term: u64 },
+}
+
+#[test]
+fn test_derive_enum_name_only() {
+    assert_eq!("Append", Message::Append(vec![1, 2]).to_string());
+    assert_eq!("Vote", Message::Vote { term: 1 }.to_string());
+}
+
+/// Fields named like the formatter or the generated bindings.
+#[derive(DisplayMore)]
+enum Shadow {
+    A { f: u32, __dm_f: u32 },
+    B(#[display(with = "fmt_hex")] u32, u32),
+}
+
+#[derive(DisplayMore)]
+struct ShadowStruct {
+    f: u32,
+}
+
+fn fmt_hex(v: &u32, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{:#x}", v)
+}
+
+#[test]
+fn test_derive_field_named_f() {
+    assert_eq!(
+        "A{f=1, __dm_f=2}",
+        Shadow::A { f: 1, __dm_f: 2 }.to_string()
+    );
+    assert_eq!("B(0x10, 3)", Shadow::B(16, 3).to_string());
+    assert_eq!("ShadowStruct{f=1}", ShadowStruct { f: 1 }.to_string());
+}
+
+#[derive(DisplayForward)]
+struct NodeId(u64);
+
+#[derive(DisplayForward)]
+#[display(prefix)]
+struct JobId(u64);
+
+#[derive(DisplayForward)]
+#[display(prefix = "name")]
+struct Name {
+    inner: String,
+}
+
+#[derive(DisplayForward)]
+struct Id<T>(T);
+
+#[test]
+fn test_derive_display_forward() {
+    assert_eq!("3", NodeId(3).to_string());
+    assert_eq!("3", format!("{:?}", NodeId(3)));
+    assert_eq!("  3", format!("{:>3}", NodeId(3)));
+
+    assert_eq!("JobId(42)", JobId(42).to_string());
+    assert_eq!("JobId(0x2a)", format!("{:#x?}", JobId(42)));
+
+    let n = Name {
+        inner: "a".to_string(),
+    };
+    assert_eq!("name(a)", n.to_string());
+    assert_eq!(r#"name("a")"#, format!("{:?}", n));
+
+    assert_eq!("x", Id("x").to_string());
+    assert_eq!("[1, 2]", format!("{:?}", Id(vec![1, 2])));
+}